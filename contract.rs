@@ -1,12 +1,33 @@
 
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use anchor_lang::solana_program::{
+    bpf_loader_upgradeable,
+    ed25519_program,
+    hash::{hash, hashv},
+    instruction::{get_stack_height, AccountMeta, Instruction, TRANSACTION_LEVEL_STACK_HEIGHT},
+    program::invoke,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, Transfer, transfer, Burn, burn},
+    token::{Mint, Token, TokenAccount, Transfer, transfer, Burn, burn, MintTo, mint_to, Approve, approve, Revoke, revoke},
+    token_2022::{self, spl_token_2022::{self, extension::ExtensionType}, Token2022},
+    token_2022_extensions::{non_transferable_mint_initialize, NonTransferableMintInitialize},
+    token_interface,
 };
 
 declare_id!("4CnqZSJakSuNEutooa7T7mBpQRkDWx3SD1Lw5YsqQ2hi"); // Replace with your actual program ID
 
+// Human-readable log line for hot paths whose data is already carried by a structured event.
+// Builds with the `quiet-logs` feature drop the formatting and its compute cost entirely.
+macro_rules! log_msg {
+    ($($arg:tt)*) => {
+        #[cfg(not(feature = "quiet-logs"))]
+        msg!($($arg)*);
+    };
+}
+
 #[program]
 pub mod speedy_token {
     use super::*;
@@ -43,20 +64,17 @@ pub mod speedy_token {
             staking_per_hour_legendary: 25_000_000, // 25 tokens
         };
 
+        game_state.set_defaults();
+
         msg!("$SPEEDY Token initialized successfully!");
         Ok(())
     }
 
-    // Fund the vault with tokens (admin only)
+    // Fund the vault with tokens (admin or treasurer)
     pub fn fund_vault(
         ctx: Context<FundVault>,
         amount: u64,
     ) -> Result<()> {
-        require!(
-            ctx.accounts.authority.key() == ctx.accounts.game_state.authority,
-            ErrorCode::Unauthorized
-        );
-
         // Transfer tokens from authority's token account to vault
         let cpi_accounts = Transfer {
             from: ctx.accounts.authority_token_account.to_account_info(),
@@ -72,75 +90,191 @@ pub mod speedy_token {
         Ok(())
     }
 
-    // Award tokens for race completion
+    // Award tokens for race completion. A replay hash, when given, is committed in the
+    // race's receipt so a published replay can be checked against it in disputes.
     pub fn award_race_tokens(
         ctx: Context<AwardTokens>,
         race_stats: RaceStats,
+        replay_hash: Option<[u8; 32]>,
     ) -> Result<()> {
-        let game_state = &ctx.accounts.game_state;
-        let mut total_reward = 0u64;
+        process_race_award(ctx, &CircuitRace(&race_stats), replay_hash, false)
+    }
 
-        // Calculate rewards based on race performance
-        if race_stats.completed {
-            total_reward = total_reward.checked_add(game_state.token_rates.race_completion)
-                .ok_or(ErrorCode::InvalidRewardAmount)?;
-        }
+    // Award tokens for a race in any game mode; the mode selects the reward strategy
+    pub fn award_game_mode_tokens(
+        ctx: Context<AwardTokens>,
+        game_mode: GameMode,
+        replay_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        process_race_award(ctx, game_mode.strategy().as_ref(), replay_hash, false)
+    }
 
-        if race_stats.won {
-            total_reward = total_reward.checked_add(game_state.token_rates.race_win)
-                .ok_or(ErrorCode::InvalidRewardAmount)?;
-        }
+    // Settle a finished race in one instruction: the receipt, the profile's counters and
+    // rating, and the payout (or the hold inside a dispute window) all land together or not
+    // at all. The receipt is mandatory, and a single RaceSettled event replaces the memo,
+    // the reward event pair and their balance reloads, keeping settlement under 200k CU.
+    pub fn settle_race(
+        ctx: Context<AwardTokens>,
+        game_mode: GameMode,
+        replay_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        require!(ctx.accounts.race_receipt.is_some(), ErrorCode::InvalidRaceReceipt);
+        process_race_award(ctx, game_mode.strategy().as_ref(), replay_hash, true)
+    }
 
-        // Distance bonus (per 100m) - using checked arithmetic
-        let distance_hundreds = race_stats.distance.checked_div(100).unwrap_or(0);
-        let distance_bonus = distance_hundreds.checked_mul(game_state.token_rates.distance_per_100m)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
-        total_reward = total_reward.checked_add(distance_bonus)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+    // Dry run of `award_game_mode_tokens` against live state: the same validation and math
+    // run on a copy of the player's profile, and an AwardPreview event replaces the transfer
+    pub fn preview_award(
+        ctx: Context<PreviewAward>,
+        game_mode: GameMode,
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let strategy = game_mode.strategy();
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player = ctx.accounts.player.key();
 
-        // Obstacle avoidance bonus - using checked arithmetic  
-        let obstacle_bonus = race_stats.obstacles_avoided.checked_mul(game_state.token_rates.obstacle_avoided)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
-        total_reward = total_reward.checked_add(obstacle_bonus)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let mut player_profile = match ctx.accounts.player_profile.as_ref() {
+            Some(existing) => (**existing).clone(),
+            None => PlayerProfile::new(player, 0, now),
+        };
+        let withheld_before = player_profile.withheld_rewards;
 
-        // Bonus collection reward - using checked arithmetic
-        let bonus_reward = race_stats.bonus_boxes_collected.checked_mul(game_state.token_rates.bonus_collected)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
-        total_reward = total_reward.checked_add(bonus_reward)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let total_reward = compute_race_award(
+            game_state,
+            strategy.as_ref(),
+            &mut player_profile,
+            ctx.accounts.price_feed.as_ref(),
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
+        let (payout, released) = settle_probation(&mut player_profile, &game_state.probation, total_reward, now)?;
+        let (payout, balance_adjustment_applied) = settle_balance_adjustment(&mut player_profile, payout)?;
 
         // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, payout, 0)?;
+
+        emit!(AwardPreview {
+            player,
+            amount: payout,
+            reward_type: RewardType::RaceCompletion,
+            race_id: strategy.race_id(),
+            timestamp: now,
+            withheld: player_profile.withheld_rewards.saturating_sub(withheld_before),
+            released,
+            balance_adjustment_applied,
+        });
+
+        msg!("Preview: {} $SPEEDY for {}", payout, strategy.name());
+        Ok(())
+    }
+
+    // Award a relay team's race reward split by `shares_bps` (must sum to 10_000). Each member
+    // passes their token account and player profile as a pair of remaining accounts, in share
+    // order. Every share runs the same gates as a solo race award: oracle liveness, solvency,
+    // buffs, probation, co-signature and the vault liabilities check.
+    pub fn award_relay_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AwardRelayTokens<'info>>,
+        race_stats: RaceStats,
+        shares_bps: Vec<u16>,
+    ) -> Result<()> {
+        let team_size = shares_bps.len();
         require!(
-            ctx.accounts.vault.amount >= total_reward,
-            ErrorCode::InsufficientVaultBalance
+            team_size > 0
+                && team_size <= MAX_RELAY_TEAM_SIZE
+                && ctx.remaining_accounts.len() >= team_size * 2,
+            ErrorCode::InvalidRelayTeam
         );
+        let total_bps = shares_bps.iter().map(|bps| *bps as u32).sum::<u32>();
+        require!(total_bps == BPS_DENOMINATOR as u32, ErrorCode::InvalidRelayTeam);
 
-        // Transfer tokens from vault to player
-        transfer_tokens_from_vault(
-            ctx.accounts.game_state.to_account_info(),
-            ctx.accounts.vault.to_account_info(),
-            ctx.accounts.player_token_account.to_account_info(),
-            ctx.accounts.token_program.to_account_info(),
-            total_reward,
-            ctx.accounts.game_state.bump,
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let game_state = &ctx.accounts.game_state;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        let team_reward = CircuitRace(&race_stats).reward(game_state)?;
+
+        // Convert USD-denominated rewards at the oracle price
+        let team_reward = usd_to_token_amount(
+            game_state,
+            ctx.accounts.price_feed.as_ref(),
+            team_reward,
+            ctx.accounts.token_mint.decimals,
+            now,
         )?;
 
-        // Update game statistics - using checked arithmetic
-        let game_state = &mut ctx.accounts.game_state;
-        game_state.total_distributed = game_state.total_distributed.checked_add(total_reward)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        // Split the reward, then apply each member's buffs and probation
+        let members = &ctx.remaining_accounts[..team_size * 2];
+        let mut payouts: Vec<(&AccountInfo<'info>, Pubkey, u64)> = Vec::with_capacity(team_size);
+        let mut split = 0u64;
+        let mut total_payout = 0u64;
+        for (index, pair) in members.chunks(2).enumerate() {
+            let (member_info, profile_info) = (&pair[0], &pair[1]);
+            let member_account = Account::<TokenAccount>::try_from(member_info)?;
+            require!(
+                member_account.mint == game_state.token_mint
+                    && member_account.delegate.is_none()
+                    && member_account.close_authority.is_none()
+                    && payouts.iter().all(|(_, owner, _)| *owner != member_account.owner),
+                ErrorCode::InvalidPlayerTokenAccount
+            );
+            let mut member_profile = Account::<PlayerProfile>::try_from(profile_info)?;
+            require!(
+                profile_info.is_writable && member_profile.player == member_account.owner,
+                ErrorCode::InvalidRelayTeam
+            );
 
-        // Log transaction
-        emit!(TokenReward {
-            player: ctx.accounts.player.key(),
-            amount: total_reward,
-            reward_type: RewardType::RaceCompletion,
-            race_id: race_stats.race_id,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+            // The last member receives any rounding remainder
+            let share = if index == team_size - 1 {
+                team_reward - split
+            } else {
+                speedy_core::apply_bps(team_reward, shares_bps[index])
+                    .ok_or(ErrorCode::InvalidRewardAmount)?
+            };
+            split = split.checked_add(share).ok_or(ErrorCode::InvalidRewardAmount)?;
+
+            let amount = member_profile.apply_buffs(BuffTarget::Races, share, now)?;
+            let amount = apply_probation(&mut member_profile, &game_state.probation, amount, RewardType::RelayRace, now)?;
+            member_profile.exit(&crate::ID)?;
+
+            total_payout = total_payout.checked_add(amount).ok_or(ErrorCode::InvalidRewardAmount)?;
+            payouts.push((member_info, member_account.owner, amount));
+        }
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), total_payout)?;
+
+        // Check if vault has sufficient balance
+        game_state.require_vault_covers(ctx.accounts.vault.amount, total_payout, 0)?;
+
+        for (member_info, owner, amount) in payouts.iter() {
+            let vault_balance_before = token_balance(&ctx.accounts.vault.to_account_info())?;
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                (*member_info).clone(),
+                ctx.accounts.token_program.to_account_info(),
+                *amount,
+                ctx.accounts.game_state.bump,
+            )?;
 
-        msg!("Awarded {} $SPEEDY tokens for race completion", total_reward);
+            emit_token_reward(
+                TokenReward {
+                    player: *owner,
+                    amount: *amount,
+                    reward_type: RewardType::RelayRace,
+                    race_id: race_stats.race_id,
+                    timestamp: now,
+                },
+                &ctx.accounts.vault.to_account_info(),
+                vault_balance_before,
+                member_info,
+                ctx.accounts.memo_program.as_ref(),
+                ctx.accounts.game_state.current_era()?,
+            )?;
+        }
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(total_payout)?;
+
+        log_msg!("Awarded {} $SPEEDY relay reward across {} members", total_payout, team_size);
         Ok(())
     }
 
@@ -150,19 +284,47 @@ pub mod speedy_token {
         challenge_difficulty: ChallengeDifficulty,
         challenge_id: u64,
     ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        ctx.accounts.game_state.challenge_balance.record_completion(challenge_difficulty, now);
+
         let game_state = &ctx.accounts.game_state;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        let rates = game_state.active_rates()?;
         
         let reward_amount = match challenge_difficulty {
-            ChallengeDifficulty::Easy => game_state.token_rates.daily_challenge_easy,
-            ChallengeDifficulty::Medium => game_state.token_rates.daily_challenge_medium,
-            ChallengeDifficulty::Hard => game_state.token_rates.daily_challenge_hard,
+            ChallengeDifficulty::Easy => rates.daily_challenge_easy,
+            ChallengeDifficulty::Medium => rates.daily_challenge_medium,
+            ChallengeDifficulty::Hard => rates.daily_challenge_hard,
         };
+        let reward_amount = game_state.challenge_balance.scale(challenge_difficulty, reward_amount)?;
+
+        // Convert USD-denominated rewards at the oracle price
+        let reward_amount = usd_to_token_amount(
+            game_state,
+            ctx.accounts.price_feed.as_ref(),
+            reward_amount,
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
+
+        // Apply buffs, then new-wallet probation
+        let player_profile = &mut ctx.accounts.player_profile;
+        if init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now) {
+            front_rent(ctx.accounts.rent_treasury.as_mut(), &ctx.accounts.player.to_account_info(), RentSubsystem::PlayerProfiles, space::PLAYER_PROFILE)?;
+        }
+        let reward_amount = player_profile.apply_buffs(BuffTarget::Challenges, reward_amount, now)?;
+        let reward_amount = apply_probation(player_profile, &game_state.probation, reward_amount, RewardType::DailyChallenge, now)?;
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), reward_amount)?;
+
+        if player_profile.auto_stake {
+            auto_stake_reward(&mut ctx.accounts.game_state, player_profile, ctx.accounts.vault.amount, reward_amount, now)?;
+            log_msg!("Auto-staked {} $SPEEDY from challenge {}", reward_amount, challenge_id);
+            return Ok(());
+        }
 
         // Check if vault has sufficient balance
-        require!(
-            ctx.accounts.vault.amount >= reward_amount,
-            ErrorCode::InsufficientVaultBalance
-        );
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
 
         // Transfer tokens from vault to player
         transfer_tokens_from_vault(
@@ -176,18 +338,24 @@ pub mod speedy_token {
 
         // Update statistics - using checked arithmetic
         let game_state = &mut ctx.accounts.game_state;
-        game_state.total_distributed = game_state.total_distributed.checked_add(reward_amount)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.record_distribution(reward_amount)?;
 
-        emit!(TokenReward {
-            player: ctx.accounts.player.key(),
-            amount: reward_amount,
-            reward_type: RewardType::DailyChallenge,
-            race_id: challenge_id,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: RewardType::DailyChallenge,
+                race_id: challenge_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
 
-        msg!("Awarded {} $SPEEDY tokens for challenge completion", reward_amount);
+        log_msg!("Awarded {} $SPEEDY tokens for challenge completion", reward_amount);
         Ok(())
     }
 
@@ -198,17 +366,62 @@ pub mod speedy_token {
         tournament_id: u64,
     ) -> Result<()> {
         let game_state = &ctx.accounts.game_state;
+        let rates = game_state.active_rates()?;
         
         let reward_amount = match placement {
-            TournamentPlacement::Participation => game_state.token_rates.tournament_participation,
-            TournamentPlacement::Winner => game_state.token_rates.tournament_winner,
+            TournamentPlacement::Participation => rates.tournament_participation,
+            TournamentPlacement::Winner => rates.tournament_winner,
         };
 
+        // Convert USD-denominated rewards at the oracle price
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        let reward_amount = usd_to_token_amount(
+            game_state,
+            ctx.accounts.price_feed.as_ref(),
+            reward_amount,
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
+
+        // Apply buffs, then new-wallet probation
+        let player_profile = &mut ctx.accounts.player_profile;
+        if init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now) {
+            front_rent(ctx.accounts.rent_treasury.as_mut(), &ctx.accounts.player.to_account_info(), RentSubsystem::PlayerProfiles, space::PLAYER_PROFILE)?;
+        }
+        let reward_amount = player_profile.apply_buffs(BuffTarget::Tournaments, reward_amount, now)?;
+        let reward_amount = apply_probation(player_profile, &game_state.probation, reward_amount, RewardType::Tournament, now)?;
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), reward_amount)?;
+
+        // Auto-staked winnings are already locked, so they skip vesting
+        if player_profile.auto_stake {
+            auto_stake_reward(&mut ctx.accounts.game_state, player_profile, ctx.accounts.vault.amount, reward_amount, now)?;
+            log_msg!("Auto-staked {} $SPEEDY from tournament {}", reward_amount, tournament_id);
+            return Ok(());
+        }
+
+        // Large wins stream out over the vesting period instead
+        if game_state.vesting.applies_to(reward_amount) {
+            game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
+            player_profile.vesting.add(reward_amount, game_state.vesting.duration_secs, now)?;
+            let game_state = &mut ctx.accounts.game_state;
+            game_state.total_vesting = game_state.total_vesting.checked_add(reward_amount)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+            index_position(&ctx.accounts.player_index, PositionKind::Vesting, player_profile.key())?;
+
+            emit!(RewardVested {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                vesting_end: player_profile.vesting.end_time,
+                timestamp: now,
+            });
+            log_msg!("Vesting {} $SPEEDY from tournament {}", reward_amount, tournament_id);
+            return Ok(());
+        }
+
         // Check if vault has sufficient balance
-        require!(
-            ctx.accounts.vault.amount >= reward_amount,
-            ErrorCode::InsufficientVaultBalance
-        );
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
 
         transfer_tokens_from_vault(
             ctx.accounts.game_state.to_account_info(),
@@ -220,33 +433,42 @@ pub mod speedy_token {
         )?;
 
         let game_state = &mut ctx.accounts.game_state;
-        game_state.total_distributed = game_state.total_distributed.checked_add(reward_amount)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.record_distribution(reward_amount)?;
 
-        emit!(TokenReward {
-            player: ctx.accounts.player.key(),
-            amount: reward_amount,
-            reward_type: RewardType::Tournament,
-            race_id: tournament_id,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: RewardType::Tournament,
+                race_id: tournament_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
 
-        msg!("Awarded {} $SPEEDY tokens for tournament {}", reward_amount, placement as u8);
+        log_msg!("Awarded {} $SPEEDY tokens for tournament {}", reward_amount, placement as u8);
         Ok(())
     }
 
     // Award welcome bonus for new players
     pub fn award_welcome_bonus(
-        ctx: Context<AwardTokens>,
+        ctx: Context<AwardWelcomeBonus>,
     ) -> Result<()> {
-        let game_state = &ctx.accounts.game_state;
-        let reward_amount = game_state.token_rates.welcome_bonus;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let reward_amount = welcome_bonus_amount(
+            &ctx.accounts.game_state,
+            ctx.accounts.attestation.as_mut(),
+            ctx.accounts.price_feed.as_ref(),
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
 
         // Check if vault has sufficient balance
-        require!(
-            ctx.accounts.vault.amount >= reward_amount,
-            ErrorCode::InsufficientVaultBalance
-        );
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
 
         transfer_tokens_from_vault(
             ctx.accounts.game_state.to_account_info(),
@@ -258,45 +480,61 @@ pub mod speedy_token {
         )?;
 
         let game_state = &mut ctx.accounts.game_state;
-        game_state.total_distributed = game_state.total_distributed.checked_add(reward_amount)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.record_distribution(reward_amount)?;
 
-        emit!(TokenReward {
-            player: ctx.accounts.player.key(),
-            amount: reward_amount,
-            reward_type: RewardType::WelcomeBonus,
-            race_id: 0,
-            timestamp: Clock::get()?.unix_timestamp,
-        });
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: RewardType::WelcomeBonus,
+                race_id: 0,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
 
-        msg!("Awarded {} $SPEEDY welcome bonus to new player", reward_amount);
+        log_msg!("Awarded {} $SPEEDY welcome bonus to new player", reward_amount);
         Ok(())
     }
 
-    // Award staking rewards
-    pub fn award_staking_tokens(
-        ctx: Context<AwardTokens>,
-        car_rarity: CarRarity,
-        hours_staked: u64,
-        car_id: u64,
+    // One-transaction onboarding: creates the player's profile and $SPEEDY token account, mints
+    // the starter Common car and pays the welcome bonus. The profile is created here, so each
+    // wallet can only onboard once and nothing is left half set up if any step fails.
+    pub fn onboard_player(
+        ctx: Context<OnboardPlayer>,
     ) -> Result<()> {
-        let game_state = &ctx.accounts.game_state;
-        
-        let hourly_rate = match car_rarity {
-            CarRarity::Common => game_state.token_rates.staking_per_hour_common,
-            CarRarity::Rare => game_state.token_rates.staking_per_hour_rare,
-            CarRarity::Epic => game_state.token_rates.staking_per_hour_epic,
-            CarRarity::Legendary => game_state.token_rates.staking_per_hour_legendary,
-        };
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player = ctx.accounts.player.key();
+        *ctx.accounts.player_profile = PlayerProfile::new(player, ctx.bumps.player_profile, now);
 
-        let reward_amount = hourly_rate.checked_mul(hours_staked)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let reward_amount = welcome_bonus_amount(
+            &ctx.accounts.game_state,
+            ctx.accounts.attestation.as_mut(),
+            ctx.accounts.price_feed.as_ref(),
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
 
         // Check if vault has sufficient balance
-        require!(
-            ctx.accounts.vault.amount >= reward_amount,
-            ErrorCode::InsufficientVaultBalance
-        );
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
+
+        let game_state_seeds = &[b"game_state".as_ref(), &[ctx.accounts.game_state.bump]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.starter_car_mint.to_account_info(),
+                    to: ctx.accounts.player_car_account.to_account_info(),
+                    authority: ctx.accounts.game_state.to_account_info(),
+                },
+                &[&game_state_seeds[..]],
+            ),
+            1,
+        )?;
 
         transfer_tokens_from_vault(
             ctx.accounts.game_state.to_account_info(),
@@ -308,342 +546,18430 @@ pub mod speedy_token {
         )?;
 
         let game_state = &mut ctx.accounts.game_state;
-        game_state.total_distributed = game_state.total_distributed.checked_add(reward_amount)
-            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.record_distribution(reward_amount)?;
 
-        emit!(TokenReward {
-            player: ctx.accounts.player.key(),
-            amount: reward_amount,
-            reward_type: RewardType::Staking,
-            race_id: car_id,
-            timestamp: Clock::get()?.unix_timestamp,
+        emit_token_reward(
+            TokenReward {
+                player,
+                amount: reward_amount,
+                reward_type: RewardType::WelcomeBonus,
+                race_id: 0,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Onboarded {} with a starter car and {} $SPEEDY", player, reward_amount);
+        Ok(())
+    }
+
+    // Create the 0-decimal starter car mint handed out by `onboard_player`; one unit is
+    // redeemed in game as a Common car (admin only)
+    pub fn initialize_starter_car(
+        ctx: Context<InitializeStarterCar>,
+    ) -> Result<()> {
+        msg!("Starter car mint {} created", ctx.accounts.starter_car_mint.key());
+        Ok(())
+    }
+
+    // Create a promo code (admin only). Only the hash of the code is stored; players redeem
+    // with the plain code. `max_uses` and `per_wallet_limit` of 0 mean unlimited.
+    pub fn create_promo_code(
+        ctx: Context<CreatePromoCode>,
+        code_hash: [u8; 32],
+        promo_id: u64,
+        effect: PromoEffect,
+        max_uses: u32,
+        per_wallet_limit: u16,
+        expires_at: i64,
+    ) -> Result<()> {
+        effect.validate()?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(expires_at > now, ErrorCode::InvalidPromoCode);
+
+        let promo_code = &mut ctx.accounts.promo_code;
+        promo_code.code_hash = code_hash;
+        promo_code.promo_id = promo_id;
+        promo_code.effect = effect;
+        promo_code.max_uses = max_uses;
+        promo_code.uses = 0;
+        promo_code.per_wallet_limit = per_wallet_limit;
+        promo_code.expires_at = expires_at;
+        promo_code.bump = ctx.bumps.promo_code;
+
+        msg!("Promo {} created, expires at {}", promo_id, expires_at);
+        Ok(())
+    }
+
+    // Redeem a promo code and apply its effect. Ticket accounts are required only for
+    // free-ticket codes.
+    pub fn redeem_promo_code(
+        ctx: Context<RedeemPromoCode>,
+        code: String,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let promo_code = &mut ctx.accounts.promo_code;
+        require!(hash(code.as_bytes()).to_bytes() == promo_code.code_hash, ErrorCode::InvalidPromoCode);
+        require!(now < promo_code.expires_at, ErrorCode::PromoCodeExpired);
+        require!(
+            promo_code.max_uses == 0 || promo_code.uses < promo_code.max_uses,
+            ErrorCode::PromoCodeExhausted
+        );
+        promo_code.uses = promo_code.uses.saturating_add(1);
+
+        let redemption = &mut ctx.accounts.promo_redemption;
+        require!(
+            promo_code.per_wallet_limit == 0 || redemption.uses < promo_code.per_wallet_limit,
+            ErrorCode::PromoWalletLimitReached
+        );
+        redemption.uses = redemption.uses.saturating_add(1);
+        redemption.bump = ctx.bumps.promo_redemption;
+
+        let player = ctx.accounts.player.key();
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, player, ctx.bumps.player_profile, now);
+
+        let effect = promo_code.effect;
+        let promo_id = promo_code.promo_id;
+        match effect {
+            PromoEffect::TokenGrant { amount } => {
+                // Check if vault has sufficient balance
+                ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount, 0)?;
+                transfer_tokens_from_vault(
+                    ctx.accounts.game_state.to_account_info(),
+                    ctx.accounts.vault.to_account_info(),
+                    ctx.accounts.player_token_account.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    amount,
+                    ctx.accounts.game_state.bump,
+                )?;
+                ctx.accounts.game_state.record_distribution(amount)?;
+
+                emit_token_reward(
+                    TokenReward {
+                        player,
+                        amount,
+                        reward_type: RewardType::Promo,
+                        race_id: promo_id,
+                        timestamp: now,
+                    },
+                    &ctx.accounts.vault.to_account_info(),
+                    ctx.accounts.vault.amount,
+                    &ctx.accounts.player_token_account.to_account_info(),
+                    None,
+                    ctx.accounts.game_state.current_era()?,
+                )?;
+            }
+            PromoEffect::RewardBoost { target, multiplier_bps, duration_secs } => {
+                player_profile.add_buff(target, multiplier_bps, duration_secs, now)?;
+            }
+            PromoEffect::ShopDiscount { discount_bps } => {
+                player_profile.ticket_discount_bps = discount_bps;
+            }
+            PromoEffect::FreeTickets { quantity } => {
+                let (Some(ticket_config), Some(ticket_mint), Some(player_ticket_account)) = (
+                    ctx.accounts.ticket_config.as_ref(),
+                    ctx.accounts.ticket_mint.as_ref(),
+                    ctx.accounts.player_ticket_account.as_ref(),
+                ) else {
+                    return err!(ErrorCode::InvalidTicketConfig);
+                };
+                let ticket_seeds = &[b"ticket_config".as_ref(), &[ticket_config.bump]];
+                mint_to(
+                    CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        MintTo {
+                            mint: ticket_mint.to_account_info(),
+                            to: player_ticket_account.to_account_info(),
+                            authority: ticket_config.to_account_info(),
+                        },
+                        &[&ticket_seeds[..]],
+                    ),
+                    quantity as u64,
+                )?;
+            }
+            PromoEffect::CosmeticUnlock { item_id } => {
+                let inventory = ctx.accounts.inventory.as_mut()
+                    .ok_or(ErrorCode::InvalidInventory)?;
+                inventory.player = player;
+                inventory.bump = ctx.bumps.inventory.ok_or(ErrorCode::InvalidInventory)?;
+                inventory.add(item_id, 1)?;
+            }
+        }
+
+        emit!(PromoRedeemed {
+            player,
+            promo_id,
+            effect,
+            timestamp: now,
         });
 
-        msg!("Awarded {} $SPEEDY tokens for staking {} car for {} hours", reward_amount, car_rarity as u8, hours_staked);
+        msg!("Player {} redeemed promo {}", player, promo_id);
         Ok(())
     }
 
-    // Spend tokens (for tournaments, upgrades, etc.) - Burns tokens to create deflationary pressure
-    pub fn spend_tokens(
-        ctx: Context<SpendTokens>,
-        amount: u64,
-        spend_type: SpendType,
+    // Grant a buff or debuff, e.g. for an achievement or as a penalty (oracle only)
+    pub fn grant_buff(
+        ctx: Context<GrantBuff>,
+        player: Pubkey,
+        target: BuffTarget,
+        multiplier_bps: u16,
+        duration_secs: i64,
     ) -> Result<()> {
-        // Burn tokens from player's account
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, player, ctx.bumps.player_profile, now);
+        player_profile.add_buff(target, multiplier_bps, duration_secs, now)?;
+
+        emit!(BuffGranted {
+            player,
+            target,
+            multiplier_bps,
+            expires_at: now + duration_secs,
+            timestamp: now,
+        });
+
+        msg!("Granted {} bps buff to {} for {}s", multiplier_bps, player, duration_secs);
+        Ok(())
+    }
+
+    // Buy the buff on sale in the shop, burning its price
+    pub fn purchase_buff(
+        ctx: Context<PurchaseBuff>,
+    ) -> Result<()> {
+        let item = ctx.accounts.game_state.buff_shop;
+        require!(item.price > 0, ErrorCode::FeatureDisabled);
+
         let cpi_accounts = Burn {
             mint: ctx.accounts.token_mint.to_account_info(),
             from: ctx.accounts.player_token_account.to_account_info(),
             authority: ctx.accounts.player.to_account_info(),
         };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), item.price)?;
 
-        burn(cpi_ctx, amount)?;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(item.price)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
 
-        emit!(TokenSpend {
-            player: ctx.accounts.player.key(),
-            amount,
-            spend_type,
-            timestamp: Clock::get()?.unix_timestamp,
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player = ctx.accounts.player.key();
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, player, ctx.bumps.player_profile, now);
+        player_profile.add_buff(item.target, item.multiplier_bps, item.duration_secs, now)?;
+
+        emit!(BuffGranted {
+            player,
+            target: item.target,
+            multiplier_bps: item.multiplier_bps,
+            expires_at: now + item.duration_secs,
+            timestamp: now,
         });
 
-        msg!("Player burned {} $SPEEDY tokens for {}", amount, spend_type as u8);
+        msg!("Bought {} bps buff for {} $SPEEDY", item.multiplier_bps, item.price);
         Ok(())
     }
 
-    // Update token rates (admin only)
-    pub fn update_token_rates(
+    // Put a buff on sale; a price of 0 takes it off sale (admin only)
+    pub fn set_buff_shop_item(
         ctx: Context<UpdateGameState>,
-        new_rates: TokenRates,
+        item: BuffShopItem,
     ) -> Result<()> {
         require!(
-            ctx.accounts.authority.key() == ctx.accounts.game_state.authority,
-            ErrorCode::Unauthorized
+            item.price == 0 || (item.multiplier_bps > BPS_DENOMINATOR && item.duration_secs > 0),
+            ErrorCode::InvalidBuff
         );
+        ctx.accounts.game_state.buff_shop = item;
 
-        ctx.accounts.game_state.token_rates = new_rates;
-        msg!("Token rates updated successfully");
+        msg!("Buff shop sells {} bps for {}s at {} $SPEEDY", item.multiplier_bps, item.duration_secs, item.price);
         Ok(())
     }
-}
 
-// Helper function to transfer tokens from vault
-fn transfer_tokens_from_vault<'info>(
-    game_state: AccountInfo<'info>,
-    vault: AccountInfo<'info>,
-    player_token_account: AccountInfo<'info>,
-    token_program: AccountInfo<'info>,
-    amount: u64,
-    bump: u8,
-) -> Result<()> {
-    let authority_seeds = &[
-        b"game_state".as_ref(),
-        &[bump],
-    ];
-    let signer = &[&authority_seeds[..]];
+    // Put a cosmetic item on sale for $SPEEDY, or change its price; 0 takes it off sale
+    // (admin only)
+    pub fn list_cosmetic(
+        ctx: Context<ListCosmetic>,
+        item_id: u64,
+        price: u64,
+    ) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        listing.item_id = item_id;
+        listing.price = price;
+        listing.bump = ctx.bumps.listing;
 
-    let cpi_accounts = Transfer {
-        from: vault,
-        to: player_token_account,
-        authority: game_state,
-    };
-    let cpi_program = token_program;
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+        msg!("Cosmetic {} listed at {} $SPEEDY", item_id, price);
+        Ok(())
+    }
 
-    transfer(cpi_ctx, amount)?;
-    Ok(())
-}
+    // Buy cosmetics from the shop, burning the price
+    pub fn buy_cosmetic(
+        ctx: Context<BuyCosmetic>,
+        item_id: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        let listing = &mut ctx.accounts.listing;
+        require!(listing.price > 0, ErrorCode::CosmeticNotForSale);
+        require!(quantity > 0, ErrorCode::InvalidRewardAmount);
+        let cost = listing.price.checked_mul(quantity as u64)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        listing.sold = listing.sold.saturating_add(quantity as u64);
 
-// Account Structures
-#[derive(Accounts)]
-#[instruction(decimals: u8)]
-pub struct InitializeToken<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), cost)?;
 
-    #[account(
-        init,
-        payer = authority,
-        mint::decimals = decimals,
-        mint::authority = authority, // Authority is the mint authority, not game_state
-    )]
-    pub token_mint: Account<'info, Mint>,
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(cost)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
 
-    #[account(
-        init,
-        payer = authority,
-        space = 8 + GameState::LEN,
-        seeds = [b"game_state"],
-        bump,
-    )]
-    pub game_state: Account<'info, GameState>,
+        let player = ctx.accounts.player.key();
+        let inventory = &mut ctx.accounts.inventory;
+        inventory.player = player;
+        inventory.bump = ctx.bumps.inventory;
+        let owned = inventory.add(item_id, quantity)?;
 
-    #[account(
-        init,
-        payer = authority,
-        associated_token::mint = token_mint,
-        associated_token::authority = game_state, // Vault is controlled by game_state PDA
-    )]
-    pub vault: Account<'info, TokenAccount>,
+        emit!(CosmeticAcquired {
+            player,
+            item_id,
+            quantity,
+            owned,
+            source: CosmeticSource::Shop,
+        });
 
+        msg!("Bought {} of cosmetic {} for {} $SPEEDY", quantity, item_id, cost);
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token>,
+    // Credit cosmetics won in a lootbox opening resolved by the game server (oracle only)
+    pub fn award_cosmetic(
+        ctx: Context<AwardCosmetic>,
+        player: Pubkey,
+        item_id: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        require!(quantity > 0, ErrorCode::InvalidRewardAmount);
+        let inventory = &mut ctx.accounts.inventory;
+        inventory.player = player;
+        inventory.bump = ctx.bumps.inventory;
+        let owned = inventory.add(item_id, quantity)?;
+
+        emit!(CosmeticAcquired {
+            player,
+            item_id,
+            quantity,
+            owned,
+            source: CosmeticSource::Lootbox,
+        });
+
+        msg!("Awarded {} of cosmetic {} to {}", quantity, item_id, player);
+        Ok(())
+    }
+
+    // Equip or unequip an owned cosmetic; the client mirrors the CosmeticEquipped event
+    pub fn equip_cosmetic(
+        ctx: Context<EquipCosmetic>,
+        item_id: u64,
+        equipped: bool,
+    ) -> Result<()> {
+        let inventory = &mut ctx.accounts.inventory;
+        let item = inventory.items.iter_mut()
+            .find(|item| item.item_id == item_id)
+            .ok_or(ErrorCode::CosmeticNotOwned)?;
+        item.equipped = equipped;
+
+        emit!(CosmeticEquipped {
+            player: inventory.player,
+            item_id,
+            equipped,
+        });
+
+        msg!("Cosmetic {} {}", item_id, if equipped { "equipped" } else { "unequipped" });
+        Ok(())
+    }
+
+    // Award $SPEEDY to a player of a partner game. Only callable via CPI from the grant's
+    // partner program, and capped at the grant's budget per epoch.
+    pub fn award_partner_tokens(
+        ctx: Context<AwardPartnerTokens>,
+        amount: u64,
+        award_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::GRANTS)?;
+        require!(amount > 0, ErrorCode::InvalidRewardAmount);
+        verify_cpi_caller(&ctx.accounts.instructions_sysvar, &ctx.accounts.partner_grant.partner_program)?;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let grant = &mut ctx.accounts.partner_grant;
+        let epoch = (now / grant.epoch_secs) as u64;
+        if epoch != grant.epoch_index {
+            grant.epoch_index = epoch;
+            grant.spent_this_epoch = 0;
+        }
+        grant.spent_this_epoch = grant.spent_this_epoch.checked_add(amount)
+            .filter(|spent| *spent <= grant.budget_per_epoch)
+            .ok_or(ErrorCode::PartnerBudgetExceeded)?;
+        grant.total_awarded = grant.total_awarded.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount,
+                reward_type: RewardType::Partner,
+                race_id: award_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            None,
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Partner {} awarded {} $SPEEDY", ctx.accounts.partner_grant.partner_program, amount);
+        Ok(())
+    }
+
+    // Create or update the per-epoch award budget of a partner program (admin only). A zero
+    // budget suspends the grant.
+    pub fn set_partner_grant(
+        ctx: Context<SetPartnerGrant>,
+        partner_program: Pubkey,
+        budget_per_epoch: u64,
+        epoch_secs: i64,
+    ) -> Result<()> {
+        require!(epoch_secs > 0, ErrorCode::InvalidPartnerGrant);
+
+        let grant = &mut ctx.accounts.partner_grant;
+        grant.partner_program = partner_program;
+        grant.budget_per_epoch = budget_per_epoch;
+        if grant.epoch_secs != epoch_secs {
+            // Epoch boundaries moved; start counting from scratch
+            grant.epoch_secs = epoch_secs;
+            grant.epoch_index = 0;
+            grant.spent_this_epoch = 0;
+        }
+        grant.bump = ctx.bumps.partner_grant;
+
+        msg!("Partner {} may award {} $SPEEDY per {}s", partner_program, budget_per_epoch, epoch_secs);
+        Ok(())
+    }
+
+    // Pay the configured reward for a Honeycomb Protocol mission completion. Must be invoked via
+    // CPI from the configured Honeycomb program, which passes the player's profile trait hashes;
+    // the highest mapped multiplier among them scales the reward. Each completion pays once.
+    pub fn award_honeycomb_mission(
+        ctx: Context<AwardHoneycombMission>,
+        mission_id: u64,
+        completion_id: [u8; 32],
+        trait_hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let config = &ctx.accounts.honeycomb_config;
+        verify_cpi_caller(&ctx.accounts.instructions_sysvar, &config.honeycomb_program)?;
+
+        let base_reward = config.mission_rewards.iter()
+            .find(|m| m.mission_id == mission_id)
+            .map(|m| m.reward)
+            .ok_or(ErrorCode::UnknownMission)?;
+        let multiplier_bps = config.multiplier_bps(&trait_hashes);
+        let reward_amount = u64::try_from(
+            base_reward as u128 * multiplier_bps as u128 / BPS_DENOMINATOR as u128
+        ).map_err(|_| ErrorCode::InvalidRewardAmount)?;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let claim = &mut ctx.accounts.mission_claim;
+        claim.completion_id = completion_id;
+        claim.player = ctx.accounts.player.key();
+        claim.mission_id = mission_id;
+        claim.amount = reward_amount;
+        claim.claimed_at = now;
+        claim.bump = ctx.bumps.mission_claim;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            reward_amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(reward_amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: RewardType::HoneycombMission,
+                race_id: mission_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            None,
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Awarded {} $SPEEDY for Honeycomb mission {}", reward_amount, mission_id);
+        Ok(())
+    }
+
+    // Pay a reward attested by a Wormhole VAA from an allowlisted emitter on another chain. The
+    // VAA must already be verified and posted by the core bridge; each emitter sequence pays once.
+    pub fn claim_bridge_reward(
+        ctx: Context<ClaimBridgeReward>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::BRIDGE)?;
+        let config = &ctx.accounts.bridge_config;
+        let vaa = PostedVaa::load(&ctx.accounts.posted_vaa)?;
+        require!(
+            config.emitters.iter().any(|e| e.chain == vaa.emitter_chain && e.address == vaa.emitter_address),
+            ErrorCode::UnknownBridgeEmitter
+        );
+        let payload = BridgeRewardPayload::try_from_slice(&vaa.payload)
+            .map_err(|_| ErrorCode::InvalidBridgeMessage)?;
+        require!(payload.player == ctx.accounts.player.key(), ErrorCode::InvalidBridgeMessage);
+        require!(
+            payload.amount > 0 && payload.amount <= config.max_amount_per_claim,
+            ErrorCode::InvalidRewardAmount
+        );
+
+        // The claim PDA is keyed by emitter and sequence, so a replayed VAA fails to init it
+        let (expected_claim, claim_bump) = Pubkey::find_program_address(
+            &[
+                b"bridge_claim",
+                &vaa.emitter_chain.to_le_bytes(),
+                vaa.emitter_address.as_ref(),
+                &vaa.sequence.to_le_bytes(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(ctx.accounts.bridge_claim.key(), expected_claim, ErrorCode::InvalidBridgeMessage);
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let claim = BridgeClaim {
+            emitter_chain: vaa.emitter_chain,
+            emitter_address: vaa.emitter_address,
+            sequence: vaa.sequence,
+            player: payload.player,
+            amount: payload.amount,
+            claimed_at: now,
+            bump: claim_bump,
+        };
+        let claim_info = ctx.accounts.bridge_claim.to_account_info();
+        require!(claim_info.data_is_empty(), ErrorCode::BridgeMessageAlreadyClaimed);
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &claim_info,
+            &ctx.accounts.system_program.to_account_info(),
+            space::BRIDGE_CLAIM,
+            ctx.program_id,
+            &[&[
+                b"bridge_claim",
+                &vaa.emitter_chain.to_le_bytes(),
+                vaa.emitter_address.as_ref(),
+                &vaa.sequence.to_le_bytes(),
+                &[claim_bump],
+            ]],
+        )?;
+        claim.try_serialize(&mut &mut claim_info.try_borrow_mut_data()?[..])?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, payload.amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            payload.amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(payload.amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: payload.player,
+                amount: payload.amount,
+                reward_type: RewardType::Bridge,
+                race_id: vaa.sequence,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            None,
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Bridged {} $SPEEDY from chain {} sequence {}", payload.amount, vaa.emitter_chain, vaa.sequence);
+        Ok(())
+    }
+
+    // Configure the Wormhole core bridge, allowed emitters and per-claim cap (admin only)
+    pub fn set_bridge_config(
+        ctx: Context<SetBridgeConfig>,
+        wormhole_program: Pubkey,
+        emitters: Vec<BridgeEmitter>,
+        max_amount_per_claim: u64,
+    ) -> Result<()> {
+        require!(emitters.len() <= BridgeConfig::MAX_EMITTERS, ErrorCode::InvalidBridgeConfig);
+
+        let config = &mut ctx.accounts.bridge_config;
+        config.wormhole_program = wormhole_program;
+        config.emitters = emitters;
+        config.max_amount_per_claim = max_amount_per_claim;
+        config.bump = ctx.bumps.bridge_config;
+
+        msg!("Bridge config set with {} emitters", config.emitters.len());
+        Ok(())
+    }
+
+    // Configure the Honeycomb program, mission rewards and trait multipliers (admin only)
+    pub fn set_honeycomb_config(
+        ctx: Context<SetHoneycombConfig>,
+        honeycomb_program: Pubkey,
+        mission_rewards: Vec<MissionReward>,
+        trait_multipliers: Vec<TraitMultiplier>,
+    ) -> Result<()> {
+        require!(
+            mission_rewards.len() <= HoneycombConfig::MAX_MISSIONS
+                && trait_multipliers.len() <= HoneycombConfig::MAX_TRAITS
+                && trait_multipliers.iter().all(|t| t.multiplier_bps > 0),
+            ErrorCode::InvalidHoneycombConfig
+        );
+
+        let config = &mut ctx.accounts.honeycomb_config;
+        config.honeycomb_program = honeycomb_program;
+        config.mission_rewards = mission_rewards;
+        config.trait_multipliers = trait_multipliers;
+        config.bump = ctx.bumps.honeycomb_config;
+
+        msg!("Honeycomb config set: {} missions, {} traits", config.mission_rewards.len(), config.trait_multipliers.len());
+        Ok(())
+    }
+
+    // Award staking rewards
+    pub fn award_staking_tokens(
+        ctx: Context<AwardTokens>,
+        car_rarity: CarRarity,
+        hours_staked: u64,
+        car_id: u64,
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        let rates = game_state.active_rates()?;
+        
+        let hourly_rate = match car_rarity {
+            CarRarity::Common => rates.staking_per_hour_common,
+            CarRarity::Rare => rates.staking_per_hour_rare,
+            CarRarity::Epic => rates.staking_per_hour_epic,
+            CarRarity::Legendary => rates.staking_per_hour_legendary,
+        };
+
+        let reward_amount = speedy_core::staking_reward(hourly_rate, hours_staked)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        // Convert USD-denominated rewards at the oracle price
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        let reward_amount = usd_to_token_amount(
+            game_state,
+            ctx.accounts.price_feed.as_ref(),
+            reward_amount,
+            ctx.accounts.token_mint.decimals,
+            now,
+        )?;
+
+        // Apply new-wallet probation
+        let player_profile = &mut ctx.accounts.player_profile;
+        if init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now) {
+            front_rent(ctx.accounts.rent_treasury.as_mut(), &ctx.accounts.player.to_account_info(), RentSubsystem::PlayerProfiles, space::PLAYER_PROFILE)?;
+        }
+        let reward_amount = apply_probation(player_profile, &game_state.probation, reward_amount, RewardType::Staking, now)?;
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), reward_amount)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            reward_amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(reward_amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: RewardType::Staking,
+                race_id: car_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Awarded {} $SPEEDY tokens for staking {} car for {} hours", reward_amount, car_rarity as u8, hours_staked);
+        Ok(())
+    }
+
+    // Spend tokens (for tournaments, upgrades, etc.) - Burns tokens to create deflationary pressure
+    pub fn spend_tokens(
+        ctx: Context<SpendTokens>,
+        amount: u64,
+        spend_type: SpendType,
+    ) -> Result<()> {
+        // Route the jackpot share of the spend before burning the rest
+        let jackpot = ctx.accounts.game_state.jackpot;
+        let jackpot_share = speedy_core::apply_bps(amount, jackpot.burn_share_bps)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        if jackpot_share > 0 {
+            let jackpot_vault = ctx.accounts.jackpot_vault.as_ref()
+                .ok_or(ErrorCode::InvalidJackpotVault)?;
+            require!(jackpot_vault.key() == jackpot.vault, ErrorCode::InvalidJackpotVault);
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: jackpot_vault.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), jackpot_share)?;
+        }
+
+        // Players who opted into the charity round-up donate their share of the rest
+        let donation_bps = ctx.accounts.player_profile.as_ref().map_or(0, |profile| profile.donation_bps);
+        let donation = speedy_core::apply_bps(amount - jackpot_share, donation_bps)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        if donation > 0 {
+            let (Some(charity_token_account), Some(donation_stats), Some(player_profile)) = (
+                ctx.accounts.charity_token_account.as_ref(),
+                ctx.accounts.donation_stats.as_mut(),
+                ctx.accounts.player_profile.as_mut(),
+            ) else {
+                return err!(ErrorCode::InvalidCharityAccount);
+            };
+            require!(
+                charity_token_account.owner == donation_stats.charity_wallet,
+                ErrorCode::InvalidCharityAccount
+            );
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: charity_token_account.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), donation)?;
+
+            player_profile.total_donated = player_profile.total_donated.checked_add(donation)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+            donation_stats.record(donation)?;
+        }
+
+        // The burn curve keeps part of the remainder for the vault as circulating supply shrinks
+        let remainder = amount - jackpot_share - donation;
+        let burn_bps = ctx.accounts.game_state.burn_bps()?;
+        let burned = speedy_core::apply_bps(remainder, burn_bps)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let treasury_share = remainder - burned;
+        if treasury_share > 0 {
+            let vault = ctx.accounts.vault.as_ref().ok_or(ErrorCode::InvalidVault)?;
+
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: vault.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), treasury_share)?;
+        }
+
+        // Burn tokens from player's account
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+
+        burn(cpi_ctx, burned)?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(burned)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.total_returned = game_state.total_returned
+            .checked_add(jackpot_share + treasury_share)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let timestamp = current_timestamp(ctx.remaining_accounts)?;
+        emit!(TokenSpendV2 {
+            player: ctx.accounts.player.key(),
+            amount,
+            spend_type,
+            timestamp,
+            burned,
+            jackpot_share,
+            player_balance_before: ctx.accounts.player_token_account.amount,
+            player_balance_after: token_balance(&ctx.accounts.player_token_account.to_account_info())?,
+            donation,
+            burn_bps,
+            treasury_share,
+        });
+        emit!(TokenSpend {
+            player: ctx.accounts.player.key(),
+            amount,
+            spend_type,
+            timestamp,
+        });
+
+        log_msg!("Player burned {} $SPEEDY tokens for {}", amount, spend_type as u8);
+        Ok(())
+    }
+
+    // Opt into (or out of, with 0) donating a share of every spend to the charity wallet
+    pub fn set_donation_bps(
+        ctx: Context<SetDonationBps>,
+        donation_bps: u16,
+    ) -> Result<()> {
+        require!(donation_bps <= BPS_DENOMINATOR, ErrorCode::InvalidDonationBps);
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        player_profile.donation_bps = donation_bps;
+
+        msg!("Donation round-up set to {} bps", donation_bps);
+        Ok(())
+    }
+
+    // Set the charity wallet and current campaign; a new campaign id restarts the campaign
+    // total (admin only)
+    pub fn set_charity_campaign(
+        ctx: Context<SetCharityCampaign>,
+        charity_wallet: Pubkey,
+        campaign_id: u64,
+    ) -> Result<()> {
+        let stats = &mut ctx.accounts.donation_stats;
+        if stats.campaign_id != campaign_id {
+            stats.campaign_id = campaign_id;
+            stats.campaign_donated = 0;
+        }
+        stats.charity_wallet = charity_wallet;
+        stats.bump = ctx.bumps.donation_stats;
+
+        msg!("Charity campaign {} donates to {}", campaign_id, charity_wallet);
+        Ok(())
+    }
+
+    // Burn $SPEEDY for the same amount of in-game credits. One-way: credits can only be spent
+    // in game, for regions where tradable-token payouts are restricted.
+    pub fn convert_to_credits(
+        ctx: Context<ConvertToCredits>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidRewardAmount);
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        burn(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        player_profile.credits = player_profile.credits.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(CreditsChanged {
+            player: ctx.accounts.player.key(),
+            delta: amount as i64,
+            credits: player_profile.credits,
+            reference_id: 0,
+            timestamp: now,
+        });
+
+        log_msg!("Converted {} $SPEEDY to credits", amount);
+        Ok(())
+    }
+
+    // Deduct credits the player spent in game (oracle only)
+    pub fn deduct_credits(
+        ctx: Context<DeductCredits>,
+        _player: Pubkey,
+        amount: u64,
+        reference_id: u64,
+    ) -> Result<()> {
+        let player_profile = &mut ctx.accounts.player_profile;
+        player_profile.credits = player_profile.credits.checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientCredits)?;
+
+        emit!(CreditsChanged {
+            player: player_profile.player,
+            delta: -(amount as i64),
+            credits: player_profile.credits,
+            reference_id,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        log_msg!("Deducted {} credits for {}", amount, reference_id);
+        Ok(())
+    }
+
+    // Update token rates (admin or rate manager)
+    pub fn update_token_rates(
+        ctx: Context<UpdateRates>,
+        new_rates: TokenRates,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        ctx.accounts.game_state.token_rates = new_rates;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        record_rate_snapshot(&ctx.accounts.game_state, ctx.accounts.rate_history.as_mut(), now)?;
+        msg!("Token rates updated successfully");
+        Ok(())
+    }
+
+    // Change a single token rate, leaving the others untouched (admin or rate manager)
+    pub fn update_rate(
+        ctx: Context<UpdateRates>,
+        field: RateField,
+        value: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        apply_rate_change(&mut ctx.accounts.game_state.token_rates, RateChange { field, value }, now);
+        record_rate_snapshot(&ctx.accounts.game_state, ctx.accounts.rate_history.as_mut(), now)?;
+
+        msg!("Token rate updated");
+        Ok(())
+    }
+
+    // Change several token rates at once; rates not listed are left untouched (admin or rate manager)
+    pub fn update_rates(
+        ctx: Context<UpdateRates>,
+        changes: Vec<RateChange>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        require!(
+            !changes.is_empty() && changes.len() <= RateField::COUNT,
+            ErrorCode::InvalidRewardAmount
+        );
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        for change in changes.iter() {
+            apply_rate_change(&mut ctx.accounts.game_state.token_rates, *change, now);
+        }
+        record_rate_snapshot(&ctx.accounts.game_state, ctx.accounts.rate_history.as_mut(), now)?;
+
+        msg!("{} token rates updated", changes.len());
+        Ok(())
+    }
+
+    // Create the rate history with the current rates as its first snapshot (admin only). From
+    // then on every rate change must append to it.
+    pub fn initialize_rate_history(
+        ctx: Context<InitializeRateHistory>,
+    ) -> Result<()> {
+        let rate_history = &mut ctx.accounts.rate_history;
+        rate_history.snapshots = Vec::new();
+        rate_history.next = 0;
+        rate_history.total_recorded = 0;
+        rate_history.bump = ctx.bumps.rate_history;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        ctx.accounts.game_state.rate_history_enabled = true;
+        record_rate_snapshot(&ctx.accounts.game_state, Some(&mut ctx.accounts.rate_history), now)?;
+
+        msg!("Rate history initialized");
+        Ok(())
+    }
+
+    // Fix the emission halving schedule (admin only). It can be set once and never changed,
+    // so later halvings do not depend on admin action. A zero start slot starts it now.
+    pub fn configure_halving(
+        ctx: Context<UpdateGameState>,
+        halving: HalvingConfig,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        require!(!game_state.halving.is_set(), ErrorCode::HalvingAlreadyConfigured);
+        require!(halving.is_set(), ErrorCode::InvalidHalvingConfig);
+
+        let mut halving = halving;
+        if halving.start_slot == 0 {
+            halving.start_slot = Clock::get()?.slot;
+        }
+        game_state.halving = halving;
+
+        msg!(
+            "Rates halve every {} tokens distributed / {} slots from slot {}",
+            halving.every_distributed,
+            halving.every_slots,
+            halving.start_slot
+        );
+        Ok(())
+    }
+
+    // Redeem an oracle-signed reward voucher. The oracle's ed25519 signature over the serialized
+    // voucher must be verified by the preceding instruction, and each voucher hash can be used once
+    // regardless of which reward type it carries.
+    pub fn redeem_reward_voucher(
+        ctx: Context<RedeemRewardVoucher>,
+        voucher: RewardVoucher,
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        require!(game_state.oracle != Pubkey::default(), ErrorCode::OracleNotConfigured);
+        require!(
+            voucher.player == ctx.accounts.player.key(),
+            ErrorCode::VoucherPlayerMismatch
+        );
+        require!(voucher.oracle_epoch == game_state.oracle_epoch, ErrorCode::StaleVoucher);
+
+        let message = voucher.message();
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar,
+            &game_state.oracle,
+            &message,
+        )?;
+
+        // Tolerate a little clock skew between the game server and the cluster
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        require!(
+            now <= voucher.expires_at.saturating_add(game_state.voucher_clock_tolerance_secs),
+            ErrorCode::VoucherExpired
+        );
+        let voucher_hash = hash(&message).to_bytes();
+        let used_voucher = &mut ctx.accounts.used_voucher;
+        used_voucher.voucher_hash = voucher_hash;
+        used_voucher.player = voucher.player;
+        used_voucher.redeemed_at = now;
+        used_voucher.bump = ctx.bumps.used_voucher;
+
+        // Apply new-wallet probation
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        let reward_amount = apply_probation(player_profile, &game_state.probation, voucher.amount, voucher.reward_type, now)?;
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), reward_amount)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, reward_amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            reward_amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(reward_amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: voucher.reward_type,
+                race_id: voucher.reference_id,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Redeemed voucher for {} $SPEEDY tokens", reward_amount);
+        Ok(())
+    }
+
+    // Redeem an oracle-signed voucher against a regional pool. The oracle signs the voucher
+    // together with the region code, so a voucher issued for one region cannot drain another
+    // region's vault shard or the main vault.
+    pub fn redeem_regional_voucher(
+        ctx: Context<RedeemRegionalVoucher>,
+        voucher: RewardVoucher,
+        region: Region,
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        require!(game_state.oracle != Pubkey::default(), ErrorCode::OracleNotConfigured);
+        require!(
+            voucher.player == ctx.accounts.player.key(),
+            ErrorCode::VoucherPlayerMismatch
+        );
+        require!(voucher.oracle_epoch == game_state.oracle_epoch, ErrorCode::StaleVoucher);
+
+        let message = voucher.regional_message(region);
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar,
+            &game_state.oracle,
+            &message,
+        )?;
+
+        // Tolerate a little clock skew between the game server and the cluster
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+        game_state.require_recent_solvency(now)?;
+        require!(
+            now <= voucher.expires_at.saturating_add(game_state.voucher_clock_tolerance_secs),
+            ErrorCode::VoucherExpired
+        );
+        let used_voucher = &mut ctx.accounts.used_voucher;
+        used_voucher.voucher_hash = hash(&message).to_bytes();
+        used_voucher.player = voucher.player;
+        used_voucher.redeemed_at = now;
+        used_voucher.bump = ctx.bumps.used_voucher;
+
+        // Apply new-wallet probation
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        let reward_amount = apply_probation(player_profile, &game_state.probation, voucher.amount, voucher.reward_type, now)?;
+        game_state.require_cosign(ctx.accounts.cosigner.as_ref(), reward_amount)?;
+
+        // Check if vault has sufficient balance
+        require!(
+            ctx.accounts.region_vault.amount >= reward_amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        transfer_from_region_vault(
+            &ctx.accounts.region_pool,
+            ctx.accounts.region_vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            reward_amount,
+        )?;
+
+        let region_pool = &mut ctx.accounts.region_pool;
+        region_pool.total_distributed = region_pool.total_distributed.checked_add(reward_amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(reward_amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount: reward_amount,
+                reward_type: voucher.reward_type,
+                race_id: voucher.reference_id,
+                timestamp: now,
+            },
+            &ctx.accounts.region_vault.to_account_info(),
+            ctx.accounts.region_vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Redeemed region {} voucher for {} $SPEEDY tokens", region as u8, reward_amount);
+        Ok(())
+    }
+
+    // Create a regional reward pool and its vault shard (admin only). Fund the shard with a
+    // plain token transfer; its balance is the region's budget.
+    pub fn initialize_region_pool(
+        ctx: Context<InitializeRegionPool>,
+        region: Region,
+        rates: Option<TokenRates>,
+    ) -> Result<()> {
+        let region_pool = &mut ctx.accounts.region_pool;
+        region_pool.region = region;
+        region_pool.vault = ctx.accounts.region_vault.key();
+        region_pool.rates = rates;
+        region_pool.total_distributed = 0;
+        region_pool.bump = ctx.bumps.region_pool;
+
+        msg!("Region {} pool created", region as u8);
+        Ok(())
+    }
+
+    // Replace a region's rate overrides; None falls back to the global rates (admin or rate manager)
+    pub fn update_region_rates(
+        ctx: Context<UpdateRegionPool>,
+        _region: Region,
+        rates: Option<TokenRates>,
+    ) -> Result<()> {
+        ctx.accounts.region_pool.rates = rates;
+
+        msg!("Region {} rates updated", ctx.accounts.region_pool.region as u8);
+        Ok(())
+    }
+
+    // Set the oracle key that signs reward vouchers (admin only)
+    pub fn set_oracle(
+        ctx: Context<UpdateGameState>,
+        oracle: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.game_state.oracle = oracle;
+        msg!("Oracle set to {}", oracle);
+        Ok(())
+    }
+
+    // Void every signed but unredeemed reward voucher by moving to a new oracle epoch, without
+    // touching the oracle key (admin only). Vouchers signed from now on carry the new epoch, and
+    // Oracle role assignments from the old epoch stop authorizing.
+    pub fn bump_oracle_epoch(
+        ctx: Context<UpdateGameState>,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.oracle_epoch = game_state.oracle_epoch.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(OracleEpochBumped {
+            oracle_epoch: game_state.oracle_epoch,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Oracle epoch bumped to {}", game_state.oracle_epoch);
+        Ok(())
+    }
+
+    // Replace the oracle, co-authority and treasurer keys in one step (authority, plus the
+    // co-authority once one is set). The oracle epoch is bumped, so every voucher signed
+    // before the rotation stops being redeemable. The treasurer moves when the old and/or new
+    // treasurer role accounts are passed.
+    pub fn rotate_keys(
+        ctx: Context<RotateKeys>,
+        new_oracle: Pubkey,
+        new_co_authority: Pubkey,
+        new_treasurer: Pubkey,
+    ) -> Result<()> {
+        let co_authority = ctx.accounts.game_state.co_authority;
+        require!(
+            co_authority == Pubkey::default()
+                || ctx.accounts.co_authority.as_ref().map(|signer| signer.key()) == Some(co_authority),
+            ErrorCode::CoAuthorityRequired
+        );
+        require!(new_oracle != Pubkey::default(), ErrorCode::InvalidKeyRotation);
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let treasurer = match ctx.accounts.new_treasurer_role.as_mut() {
+            Some(assignment) => {
+                assignment.role = Role::Treasurer;
+                assignment.holder = new_treasurer;
+                assignment.granted_at = now;
+                assignment.oracle_epoch = ctx.accounts.game_state.oracle_epoch;
+                assignment.bump = ctx.bumps.new_treasurer_role.ok_or(ErrorCode::InvalidKeyRotation)?;
+                Some(new_treasurer)
+            }
+            None => None,
+        };
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.oracle = new_oracle;
+        game_state.co_authority = new_co_authority;
+        game_state.oracle_epoch = game_state.oracle_epoch.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(KeysRotated {
+            oracle: new_oracle,
+            co_authority: new_co_authority,
+            revoked_treasurer: ctx.accounts.old_treasurer_role.as_ref().map(|assignment| assignment.holder),
+            treasurer,
+            oracle_epoch: game_state.oracle_epoch,
+            timestamp: now,
+        });
+
+        msg!("Keys rotated, oracle {} at epoch {}", new_oracle, game_state.oracle_epoch);
+        Ok(())
+    }
+
+    // Grant a role to a key (admin only). Role holders may run the instructions gated on that
+    // role alongside the authority; the Oracle role admits extra keys to oracle instructions
+    // until the next oracle epoch, after which it must be revoked and granted again.
+    pub fn assign_role(
+        ctx: Context<AssignRole>,
+        role: Role,
+        holder: Pubkey,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let assignment = &mut ctx.accounts.role_assignment;
+        assignment.role = role;
+        assignment.holder = holder;
+        assignment.granted_at = now;
+        assignment.oracle_epoch = ctx.accounts.game_state.oracle_epoch;
+        assignment.bump = ctx.bumps.role_assignment;
+
+        emit!(RoleChanged { role, holder, granted: true, timestamp: now });
+
+        msg!("Role {} granted to {}", role as u8, holder);
+        Ok(())
+    }
+
+    // Take a role away again (admin only)
+    pub fn revoke_role(
+        ctx: Context<RevokeRole>,
+        role: Role,
+        holder: Pubkey,
+    ) -> Result<()> {
+        emit!(RoleChanged {
+            role,
+            holder,
+            granted: false,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Role {} revoked from {}", role as u8, holder);
+        Ok(())
+    }
+
+    // Point the game at a new token mint and vault (admin only). Token-denominated settings are
+    // rescaled to the new mint (see `GameState::rescale_for_mint`), and holders of the old mint
+    // can swap through `swap_legacy_tokens` at `numerator / denominator` whole new tokens per
+    // whole old token. A game can only migrate once, and only while the vault owes players
+    // nothing; the old vault's balance is swept to an allowlisted cold wallet.
+    pub fn migrate_mint(
+        ctx: Context<MigrateMint>,
+        numerator: u64,
+        denominator: u64,
+    ) -> Result<()> {
+        require!(numerator > 0 && denominator > 0, ErrorCode::InvalidMigrationRate);
+        require!(
+            ctx.accounts.new_mint.key() != ctx.accounts.token_mint.key(),
+            ErrorCode::InvalidMigrationRate
+        );
+        // A second migration would replace legacy_mint and strand the first mint's holders
+        require!(
+            ctx.accounts.game_state.legacy_mint == Pubkey::default(),
+            ErrorCode::MintMigrationActive
+        );
+        // Balances owed to players are kept in old-mint units and would be paid from the new
+        // vault at the wrong scale, so they have to be settled first
+        require!(
+            ctx.accounts.game_state.vault_liabilities() == 0 && ctx.accounts.game_state.total_fee_loans == 0,
+            ErrorCode::MigrationLiabilitiesOutstanding
+        );
+
+        // Nothing can sweep the old vault once game_state points at the new one, so its
+        // balance moves to an allowlisted cold wallet now
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let old_balance = ctx.accounts.old_vault.amount;
+        if old_balance > 0 {
+            let destination = ctx.accounts.old_vault_destination.as_ref()
+                .ok_or(ErrorCode::DestinationNotAllowed)?;
+            sweep_vault_tokens(
+                &ctx.accounts.game_state,
+                &ctx.accounts.old_vault,
+                destination,
+                ctx.accounts.token_program.to_account_info(),
+                old_balance,
+                now,
+            )?;
+        }
+
+        let old_decimals = ctx.accounts.token_mint.decimals;
+        let new_decimals = ctx.accounts.new_mint.decimals;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.rescale_for_mint(numerator, denominator, old_decimals, new_decimals)?;
+        game_state.legacy_mint = game_state.token_mint;
+        game_state.token_mint = ctx.accounts.new_mint.key();
+        game_state.vault = ctx.accounts.new_vault.key();
+        game_state.migration_numerator = numerator;
+        game_state.migration_denominator = denominator;
+        record_rate_snapshot(game_state, ctx.accounts.rate_history.as_mut(), now)?;
+
+        emit!(MintMigrated {
+            legacy_mint: game_state.legacy_mint,
+            new_mint: game_state.token_mint,
+            new_vault: game_state.vault,
+            numerator,
+            denominator,
+            timestamp: now,
+        });
+
+        msg!("Migrated $SPEEDY from {} to {}", game_state.legacy_mint, game_state.token_mint);
+        Ok(())
+    }
+
+    // Exchange legacy tokens for the current mint at the configured migration rate.
+    // Legacy tokens are burned and new tokens are paid from the vault.
+    pub fn swap_legacy_tokens(
+        ctx: Context<SwapLegacyTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        require!(game_state.legacy_mint != Pubkey::default(), ErrorCode::NoMintMigration);
+
+        let amount_out = convert_token_amount(
+            amount,
+            game_state.migration_numerator,
+            game_state.migration_denominator,
+            ctx.accounts.legacy_mint.decimals,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        require!(amount_out > 0, ErrorCode::InvalidRewardAmount);
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount_out, 0)?;
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.legacy_mint.to_account_info(),
+            from: ctx.accounts.player_legacy_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        burn(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount_out,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        emit!(LegacyTokensSwapped {
+            player: ctx.accounts.player.key(),
+            amount_in: amount,
+            amount_out,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Swapped {} legacy tokens for {} $SPEEDY", amount, amount_out);
+        Ok(())
+    }
+
+    // Open a SOL-denominated token sale funded from the authority's tokens (admin only).
+    // A zero price slope sells at a fixed price; otherwise the price rises linearly as tokens sell.
+    pub fn create_token_sale(
+        ctx: Context<CreateTokenSale>,
+        config: SaleConfig,
+        fund_amount: u64,
+    ) -> Result<()> {
+        require!(
+            config.end_time > config.start_time && config.base_price_lamports > 0,
+            ErrorCode::InvalidSaleConfig
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.authority_token_account.to_account_info(),
+            to: ctx.accounts.sale_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), fund_amount)?;
+
+        let token_sale = &mut ctx.accounts.token_sale;
+        token_sale.config = config;
+        token_sale.sale_vault = ctx.accounts.sale_vault.key();
+        token_sale.tokens_sold = 0;
+        token_sale.lamports_raised = 0;
+        token_sale.bump = ctx.bumps.token_sale;
+
+        msg!("Token sale created with {} $SPEEDY for sale", fund_amount);
+        Ok(())
+    }
+
+    // Buy tokens from the sale, paying SOL into the sale PDA. `max_lamports` bounds the price paid.
+    pub fn buy_sale_tokens(
+        ctx: Context<BuySaleTokens>,
+        amount: u64,
+        max_lamports: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let token_sale = &ctx.accounts.token_sale;
+        let config = token_sale.config;
+        require!(
+            now >= config.start_time && now < config.end_time,
+            ErrorCode::SaleNotActive
+        );
+        require!(amount > 0, ErrorCode::InvalidRewardAmount);
+        require!(
+            ctx.accounts.sale_vault.amount >= amount,
+            ErrorCode::InsufficientVaultBalance
+        );
+
+        let purchase = &mut ctx.accounts.sale_purchase;
+        let purchased = purchase.amount_purchased.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(purchased <= config.per_wallet_cap, ErrorCode::SaleWalletCapExceeded);
+
+        let cost = sale_cost_lamports(
+            &config,
+            token_sale.tokens_sold,
+            amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        require!(cost > 0, ErrorCode::InvalidRewardAmount);
+        require!(cost <= max_lamports, ErrorCode::SlippageExceeded);
+
+        // Pay SOL into the sale PDA
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.token_sale.to_account_info(),
+                },
+            ),
+            cost,
+        )?;
+
+        // Deliver tokens from the sale vault
+        let sale_seeds = &[b"token_sale".as_ref(), &[ctx.accounts.token_sale.bump]];
+        let signer = &[&sale_seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sale_vault.to_account_info(),
+            to: ctx.accounts.buyer_token_account.to_account_info(),
+            authority: ctx.accounts.token_sale.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), amount)?;
+
+        purchase.buyer = ctx.accounts.buyer.key();
+        purchase.amount_purchased = purchased;
+        purchase.bump = ctx.bumps.sale_purchase;
+
+        let token_sale = &mut ctx.accounts.token_sale;
+        token_sale.tokens_sold = token_sale.tokens_sold.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        token_sale.lamports_raised = token_sale.lamports_raised.checked_add(cost)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(SaleTokensPurchased {
+            buyer: ctx.accounts.buyer.key(),
+            amount,
+            lamports_paid: cost,
+            timestamp: now,
+        });
+
+        msg!("Sold {} $SPEEDY for {} lamports", amount, cost);
+        Ok(())
+    }
+
+    // Withdraw raised SOL from the sale PDA to an allowlisted cold wallet, keeping the PDA
+    // rent-exempt (admin or treasurer)
+    pub fn withdraw_sale_proceeds(
+        ctx: Context<WithdrawSaleProceeds>,
+        lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        withdraw_sale_lamports(
+            &ctx.accounts.game_state,
+            ctx.accounts.token_sale.to_account_info(),
+            ctx.accounts.destination.to_account_info(),
+            lamports,
+            now,
+        )?;
+
+        msg!("Withdrew {} lamports of sale proceeds", lamports);
+        Ok(())
+    }
+
+    // Return unsold tokens to an allowlisted cold wallet once the sale has ended (admin or treasurer)
+    pub fn withdraw_unsold_sale_tokens(
+        ctx: Context<WithdrawUnsoldSaleTokens>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let amount = withdraw_unsold_tokens(
+            &ctx.accounts.game_state,
+            &ctx.accounts.token_sale,
+            &ctx.accounts.sale_vault,
+            &ctx.accounts.destination_token_account,
+            ctx.accounts.token_program.to_account_info(),
+            now,
+        )?;
+
+        msg!("Returned {} unsold $SPEEDY tokens", amount);
+        Ok(())
+    }
+
+    // Release tokens bought with fiat from the sale vault. The payments provider's ed25519
+    // signature over (wallet, amount, order_id) must be verified by the preceding instruction;
+    // each order pays once and draws down the provider's budget.
+    pub fn redeem_onramp_voucher(
+        ctx: Context<RedeemOnrampVoucher>,
+        voucher: OnrampVoucher,
+    ) -> Result<()> {
+        require!(voucher.wallet == ctx.accounts.wallet.key(), ErrorCode::VoucherPlayerMismatch);
+        require!(voucher.amount > 0, ErrorCode::InvalidRewardAmount);
+        verify_ed25519_signature(
+            &ctx.accounts.instructions_sysvar,
+            &ctx.accounts.onramp_provider.provider,
+            &voucher.message(),
+        )?;
+
+        let provider = &mut ctx.accounts.onramp_provider;
+        provider.budget_remaining = provider.budget_remaining.checked_sub(voucher.amount)
+            .ok_or(ErrorCode::OnrampBudgetExceeded)?;
+        provider.total_redeemed = provider.total_redeemed.checked_add(voucher.amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let order = &mut ctx.accounts.onramp_order;
+        order.provider = provider.provider;
+        order.order_id = voucher.order_id;
+        order.wallet = voucher.wallet;
+        order.amount = voucher.amount;
+        order.redeemed_at = now;
+        order.bump = ctx.bumps.onramp_order;
+
+        require!(ctx.accounts.sale_vault.amount >= voucher.amount, ErrorCode::InsufficientVaultBalance);
+        let token_sale = &mut ctx.accounts.token_sale;
+        token_sale.tokens_sold = token_sale.tokens_sold.checked_add(voucher.amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let sale_seeds = &[b"token_sale".as_ref(), &[token_sale.bump]];
+        let signer = &[&sale_seeds[..]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sale_vault.to_account_info(),
+            to: ctx.accounts.wallet_token_account.to_account_info(),
+            authority: token_sale.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new_with_signer(cpi_program, cpi_accounts, signer), voucher.amount)?;
+
+        msg!("Released {} $SPEEDY for onramp order {}", voucher.amount, voucher.order_id);
+        Ok(())
+    }
+
+    // Register a payments provider key or reset its remaining budget (admin only)
+    pub fn set_onramp_provider(
+        ctx: Context<SetOnrampProvider>,
+        provider: Pubkey,
+        budget: u64,
+    ) -> Result<()> {
+        let onramp_provider = &mut ctx.accounts.onramp_provider;
+        onramp_provider.provider = provider;
+        onramp_provider.budget_remaining = budget;
+        onramp_provider.bump = ctx.bumps.onramp_provider;
+
+        msg!("Onramp provider {} budget set to {}", provider, budget);
+        Ok(())
+    }
+
+    // Sweep tokens from a game vault to an allowlisted cold wallet (admin or treasurer). Awards still
+    // held for their dispute window stay in the vault.
+    pub fn sweep_to_cold_storage(
+        ctx: Context<SweepToColdStorage>,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_direct_admin()?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        sweep_vault_tokens(
+            &ctx.accounts.game_state,
+            &ctx.accounts.vault,
+            &ctx.accounts.destination_token_account,
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            now,
+        )?;
+
+        msg!("Swept {} tokens to cold wallet {}", amount, ctx.accounts.destination_token_account.owner);
+        Ok(())
+    }
+
+    // Record a sensitive admin change in a Proposal PDA (admin only). The proposal carries the
+    // full payload and its summary hash, so executing it needs no instruction data beyond the
+    // 32-byte hash a hardware wallet can display and the signer can compare. It becomes
+    // executable once ADMIN_PROPOSAL_DELAY_SECS have passed.
+    pub fn propose_admin_action(
+        ctx: Context<ProposeAdminAction>,
+        proposal_id: u64,
+        action: AdminAction,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.proposal_id = proposal_id;
+        proposal.action = action;
+        proposal.summary_hash = action.summary_hash()?;
+        proposal.proposed_at = now;
+        proposal.executable_at = now.checked_add(ADMIN_PROPOSAL_DELAY_SECS)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        proposal.bump = ctx.bumps.proposal;
+
+        emit!(AdminActionProposed {
+            proposal_id,
+            action,
+            summary_hash: proposal.summary_hash,
+            executable_at: proposal.executable_at,
+            timestamp: now,
+        });
+
+        msg!("Admin proposal {} recorded", proposal_id);
+        Ok(())
+    }
+
+    // Execute a recorded proposal once its delay has passed (admin only). `summary_hash` must
+    // match the stored payload.
+    pub fn execute_admin_proposal(
+        ctx: Context<ExecuteAdminProposal>,
+        summary_hash: [u8; 32],
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        require!(proposal.summary_hash == summary_hash, ErrorCode::ProposalHashMismatch);
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(now >= proposal.executable_at, ErrorCode::ProposalTimelocked);
+
+        match proposal.action {
+            AdminAction::UpdateTokenRates(rates) => {
+                ctx.accounts.game_state.token_rates = rates;
+                record_rate_snapshot(&ctx.accounts.game_state, ctx.accounts.rate_history.as_mut(), now)?;
+            }
+            AdminAction::SweepToColdStorage { destination_token_account, amount } => {
+                let (Some(vault), Some(destination), Some(token_program)) = (
+                    &ctx.accounts.vault,
+                    &ctx.accounts.destination_token_account,
+                    &ctx.accounts.token_program,
+                ) else {
+                    return err!(ErrorCode::ProposalAccountsMissing);
+                };
+                require!(destination.key() == destination_token_account, ErrorCode::DestinationNotAllowed);
+                require!(
+                    ctx.accounts.game_state.vault_for_mint(&vault.mint) == Some(vault.key()),
+                    ErrorCode::InvalidVault
+                );
+                sweep_vault_tokens(
+                    &ctx.accounts.game_state,
+                    vault,
+                    destination,
+                    token_program.to_account_info(),
+                    amount,
+                    now,
+                )?;
+            }
+            AdminAction::WithdrawSaleProceeds { destination, lamports } => {
+                let (Some(token_sale), Some(destination_info)) = (
+                    &ctx.accounts.token_sale,
+                    &ctx.accounts.destination,
+                ) else {
+                    return err!(ErrorCode::ProposalAccountsMissing);
+                };
+                require!(destination_info.key() == destination, ErrorCode::DestinationNotAllowed);
+                withdraw_sale_lamports(
+                    &ctx.accounts.game_state,
+                    token_sale.to_account_info(),
+                    destination_info.to_account_info(),
+                    lamports,
+                    now,
+                )?;
+            }
+            AdminAction::WithdrawUnsoldSaleTokens { destination_token_account } => {
+                let (Some(token_sale), Some(sale_vault), Some(destination), Some(token_program)) = (
+                    &ctx.accounts.token_sale,
+                    &ctx.accounts.vault,
+                    &ctx.accounts.destination_token_account,
+                    &ctx.accounts.token_program,
+                ) else {
+                    return err!(ErrorCode::ProposalAccountsMissing);
+                };
+                require!(destination.key() == destination_token_account, ErrorCode::DestinationNotAllowed);
+                require!(sale_vault.key() == token_sale.sale_vault, ErrorCode::InvalidVault);
+                withdraw_unsold_tokens(
+                    &ctx.accounts.game_state,
+                    token_sale,
+                    sale_vault,
+                    destination,
+                    token_program.to_account_info(),
+                    now,
+                )?;
+            }
+        }
+
+        emit!(AdminActionExecuted {
+            proposal_id: proposal.proposal_id,
+            summary_hash,
+            timestamp: now,
+        });
+
+        msg!("Admin proposal {} executed", proposal.proposal_id);
+        Ok(())
+    }
+
+    // Route rate changes, cold-storage sweeps and sale withdrawals through proposals from now
+    // on; the direct instructions are rejected (admin only). There is no way to switch back.
+    pub fn require_admin_proposals(
+        ctx: Context<UpdateGameState>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.proposals_required = true;
+
+        msg!("Admin proposals are now required");
+        Ok(())
+    }
+
+    // Add a cold wallet to the withdrawal allowlist; it becomes usable after the activation
+    // delay (admin only)
+    pub fn add_withdrawal_destination(
+        ctx: Context<UpdateGameState>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        require!(wallet != Pubkey::default(), ErrorCode::DestinationNotAllowed);
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let allowlist = &mut ctx.accounts.game_state.withdrawal_allowlist;
+        require!(
+            !allowlist.destinations.iter().any(|d| d.wallet == wallet),
+            ErrorCode::DestinationAlreadyListed
+        );
+        let slot = allowlist.destinations.iter_mut()
+            .find(|d| d.wallet == Pubkey::default())
+            .ok_or(ErrorCode::AllowlistFull)?;
+        slot.wallet = wallet;
+        slot.active_at = now.checked_add(WITHDRAWAL_DESTINATION_DELAY_SECS)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        msg!("Withdrawal destination {} usable from {}", wallet, slot.active_at);
+        Ok(())
+    }
+
+    // Remove a cold wallet from the withdrawal allowlist immediately (admin only)
+    pub fn remove_withdrawal_destination(
+        ctx: Context<UpdateGameState>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        let allowlist = &mut ctx.accounts.game_state.withdrawal_allowlist;
+        let slot = allowlist.destinations.iter_mut()
+            .find(|d| d.wallet == wallet && wallet != Pubkey::default())
+            .ok_or(ErrorCode::DestinationNotAllowed)?;
+        *slot = WithdrawalDestination::default();
+
+        msg!("Withdrawal destination {} removed", wallet);
+        Ok(())
+    }
+
+    // Register an ecosystem grant paid from the reward vault in milestones (admin only)
+    pub fn create_grant(
+        ctx: Context<CreateGrant>,
+        grant_id: u64,
+        recipient: Pubkey,
+        milestone_amounts: Vec<u64>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::GRANTS)?;
+        require!(
+            !milestone_amounts.is_empty() && milestone_amounts.len() <= Grant::MAX_MILESTONES,
+            ErrorCode::InvalidGrantSchedule
+        );
+
+        let mut total_amount = 0u64;
+        for amount in milestone_amounts.iter() {
+            require!(*amount > 0, ErrorCode::InvalidGrantSchedule);
+            total_amount = total_amount.checked_add(*amount)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+        }
+
+        let grant = &mut ctx.accounts.grant;
+        grant.grant_id = grant_id;
+        grant.recipient = recipient;
+        grant.total_amount = total_amount;
+        grant.released_amount = 0;
+        grant.milestones_released = 0;
+        grant.milestone_amounts = milestone_amounts;
+        grant.created_at = current_timestamp(ctx.remaining_accounts)?;
+        grant.bump = ctx.bumps.grant;
+
+        msg!("Grant {} created for {} totalling {} $SPEEDY", grant_id, recipient, total_amount);
+        Ok(())
+    }
+
+    // Release the next grant milestone to the recipient (admin sign-off)
+    pub fn release_grant_milestone(
+        ctx: Context<ReleaseGrantMilestone>,
+        _grant_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::GRANTS)?;
+        let grant = &ctx.accounts.grant;
+        let milestone = grant.milestones_released as usize;
+        let amount = *grant.milestone_amounts.get(milestone)
+            .ok_or(ErrorCode::GrantCompleted)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(amount)?;
+
+        let grant = &mut ctx.accounts.grant;
+        grant.milestones_released = grant.milestones_released.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        grant.released_amount = grant.released_amount.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(GrantMilestoneReleased {
+            grant_id: grant.grant_id,
+            recipient: grant.recipient,
+            milestone: milestone as u8,
+            amount,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Released grant {} milestone {} ({} $SPEEDY)", grant.grant_id, milestone, amount);
+        Ok(())
+    }
+
+    // Cancel a grant, forfeiting unreleased milestones and reclaiming rent (admin only)
+    pub fn cancel_grant(
+        ctx: Context<CancelGrant>,
+        _grant_id: u64,
+    ) -> Result<()> {
+        let grant = &ctx.accounts.grant;
+        msg!(
+            "Grant {} cancelled with {} of {} $SPEEDY released",
+            grant.grant_id,
+            grant.released_amount,
+            grant.total_amount
+        );
+        Ok(())
+    }
+
+    // Commit a merkle root of the week's final standings (oracle only). Leaves are
+    // `standings_leaf(player, week, rank, amount)`; the prize pool is paid from the vault.
+    pub fn commit_weekly_standings(
+        ctx: Context<CommitWeeklyStandings>,
+        week: u64,
+        merkle_root: [u8; 32],
+        total_prize: u64,
+    ) -> Result<()> {
+        let standings = &mut ctx.accounts.weekly_standings;
+        standings.week = week;
+        standings.merkle_root = merkle_root;
+        standings.total_prize = total_prize;
+        standings.claimed_amount = 0;
+        standings.committed_at = current_timestamp(ctx.remaining_accounts)?;
+        standings.bump = ctx.bumps.weekly_standings;
+
+        msg!("Committed standings for week {} with {} $SPEEDY in prizes", week, total_prize);
+        Ok(())
+    }
+
+    // Claim a ranked weekly prize with a merkle proof against the committed standings
+    pub fn claim_standings_prize(
+        ctx: Context<ClaimStandingsPrize>,
+        week: u64,
+        rank: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let player = ctx.accounts.player.key();
+        let leaf = standings_leaf(&player, week, rank, amount);
+        require!(
+            verify_merkle_proof(&proof, &ctx.accounts.weekly_standings.merkle_root, leaf),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        let standings = &mut ctx.accounts.weekly_standings;
+        let claimed_amount = standings.claimed_amount.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(claimed_amount <= standings.total_prize, ErrorCode::InvalidRewardAmount);
+        standings.claimed_amount = claimed_amount;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let claim = &mut ctx.accounts.standings_claim;
+        claim.week = week;
+        claim.player = player;
+        claim.rank = rank;
+        claim.amount = amount;
+        claim.claimed_at = now;
+        claim.bump = ctx.bumps.standings_claim;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(amount)?;
+
+        emit_token_reward(
+            TokenReward {
+                player,
+                amount,
+                reward_type: RewardType::LeaderboardPrize,
+                race_id: week,
+                timestamp: now,
+            },
+            &ctx.accounts.vault.to_account_info(),
+            ctx.accounts.vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            game_state.current_era()?,
+        )?;
+
+        log_msg!("Awarded {} $SPEEDY for rank {} in week {}", amount, rank, week);
+        Ok(())
+    }
+
+    // Register a track whose ownership NFT holder earns a cut of race awards paid on it
+    // (admin only). A default owner mint registers the track without an owner.
+    pub fn register_track(
+        ctx: Context<RegisterTrack>,
+        track_id: u64,
+        owner_mint: Pubkey,
+        owner_cut_bps: u16,
+    ) -> Result<()> {
+        require!(owner_cut_bps <= Track::MAX_OWNER_CUT_BPS, ErrorCode::InvalidTrackOwner);
+        let track = &mut ctx.accounts.track;
+        track.track_id = track_id;
+        track.owner_mint = owner_mint;
+        track.owner_cut_bps = owner_cut_bps;
+        track.total_owner_paid = 0;
+        track.races_paid = 0;
+        track.bump = ctx.bumps.track;
+
+        msg!("Track {} registered, owner cut {} bps", track_id, owner_cut_bps);
+        Ok(())
+    }
+
+    // Change a track's ownership NFT and cut (admin only)
+    pub fn update_track_owner(
+        ctx: Context<UpdateTrack>,
+        track_id: u64,
+        owner_mint: Pubkey,
+        owner_cut_bps: u16,
+    ) -> Result<()> {
+        require!(owner_cut_bps <= Track::MAX_OWNER_CUT_BPS, ErrorCode::InvalidTrackOwner);
+        let track = &mut ctx.accounts.track;
+        track.owner_mint = owner_mint;
+        track.owner_cut_bps = owner_cut_bps;
+
+        msg!("Track {} owner cut {} bps", track_id, owner_cut_bps);
+        Ok(())
+    }
+
+    // Propose a community track by staking $SPEEDY into the submission escrow; curation
+    // voting opens immediately
+    pub fn submit_track(
+        ctx: Context<SubmitTrack>,
+        track_id: u64,
+        content_hash: [u8; 32],
+        stake: u64,
+    ) -> Result<()> {
+        let curation = ctx.accounts.game_state.curation;
+        require!(
+            curation.min_stake > 0 && stake >= curation.min_stake,
+            ErrorCode::InvalidTrackSubmission
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.creator_token_account.to_account_info(),
+            to: ctx.accounts.submission_escrow.to_account_info(),
+            authority: ctx.accounts.creator.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), stake)?;
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let submission = &mut ctx.accounts.track_submission;
+        submission.track_id = track_id;
+        submission.creator = ctx.accounts.creator.key();
+        submission.content_hash = content_hash;
+        submission.stake = stake;
+        submission.votes_for = 0;
+        submission.votes_against = 0;
+        submission.voting_ends_at = now.checked_add(curation.voting_secs)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        submission.status = SubmissionStatus::Voting;
+        submission.escrow = ctx.accounts.submission_escrow.key();
+        submission.bump = ctx.bumps.track_submission;
+        index_position(&ctx.accounts.player_index, PositionKind::TrackSubmission, ctx.accounts.track_submission.key())?;
+
+        msg!("Track {} submitted with {} $SPEEDY stake", track_id, stake);
+        Ok(())
+    }
+
+    // Vote to accept or reject a track submission, weighted by the $SPEEDY staked with the vote
+    pub fn vote_on_track(
+        ctx: Context<VoteOnTrack>,
+        track_id: u64,
+        approve: bool,
+        stake: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let submission = &ctx.accounts.track_submission;
+        require!(
+            submission.status == SubmissionStatus::Voting && now < submission.voting_ends_at,
+            ErrorCode::CurationVotingClosed
+        );
+        require!(
+            stake > 0 && ctx.accounts.curator.key() != submission.creator,
+            ErrorCode::InvalidTrackSubmission
+        );
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.curator_token_account.to_account_info(),
+            to: ctx.accounts.submission_escrow.to_account_info(),
+            authority: ctx.accounts.curator.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), stake)?;
+
+        let submission = &mut ctx.accounts.track_submission;
+        if approve {
+            submission.votes_for = submission.votes_for.checked_add(stake)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+        } else {
+            submission.votes_against = submission.votes_against.checked_add(stake)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+        }
+
+        let vote = &mut ctx.accounts.curation_vote;
+        vote.track_id = track_id;
+        vote.curator = ctx.accounts.curator.key();
+        vote.stake = stake;
+        vote.approve = approve;
+        vote.bump = ctx.bumps.curation_vote;
+        index_position(&ctx.accounts.player_index, PositionKind::CurationVote, ctx.accounts.curation_vote.key())?;
+
+        msg!("Curation vote on track {}: {} with {} $SPEEDY", track_id, if approve { "accept" } else { "reject" }, stake);
+        Ok(())
+    }
+
+    // Close curation once voting has ended (permissionless). The track is accepted when more
+    // stake voted for it than against; a rejected creator's stake is slashed to the vault and
+    // the rest returned.
+    pub fn finalize_track_submission(
+        ctx: Context<FinalizeTrackSubmission>,
+        track_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let submission = &ctx.accounts.track_submission;
+        require!(submission.status == SubmissionStatus::Voting, ErrorCode::CurationVotingClosed);
+        require!(now >= submission.voting_ends_at, ErrorCode::CurationVotingOpen);
+
+        let accepted = submission.votes_for > submission.votes_against;
+        let slashed = if accepted {
+            0
+        } else {
+            speedy_core::apply_bps(submission.stake, ctx.accounts.game_state.curation.slash_bps)
+                .ok_or(ErrorCode::InvalidRewardAmount)?
+        };
+        let refund = submission.stake - slashed;
+
+        if slashed > 0 {
+            transfer_from_track_submission(
+                submission,
+                ctx.accounts.submission_escrow.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                slashed,
+            )?;
+        }
+        if refund > 0 {
+            transfer_from_track_submission(
+                submission,
+                ctx.accounts.submission_escrow.to_account_info(),
+                ctx.accounts.creator_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                refund,
+            )?;
+        }
+
+        let submission = &mut ctx.accounts.track_submission;
+        submission.status = if accepted { SubmissionStatus::Accepted } else { SubmissionStatus::Rejected };
+        unindex_position(&ctx.accounts.player_index, &submission.key())?;
+
+        emit!(TrackSubmissionFinalized {
+            track_id,
+            creator: submission.creator,
+            accepted,
+            votes_for: submission.votes_for,
+            votes_against: submission.votes_against,
+            slashed,
+            timestamp: now,
+        });
+
+        msg!("Track {} submission {}", track_id, if accepted { "accepted" } else { "rejected" });
+        Ok(())
+    }
+
+    // Return a curator's stake once voting on the submission is over
+    pub fn withdraw_curation_stake(
+        ctx: Context<WithdrawCurationStake>,
+        track_id: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.track_submission.status != SubmissionStatus::Voting,
+            ErrorCode::CurationVotingOpen
+        );
+
+        let stake = ctx.accounts.curation_vote.stake;
+        transfer_from_track_submission(
+            &ctx.accounts.track_submission,
+            ctx.accounts.submission_escrow.to_account_info(),
+            ctx.accounts.curator_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            stake,
+        )?;
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.curation_vote.key())?;
+
+        msg!("Returned {} $SPEEDY curation stake on track {}", stake, track_id);
+        Ok(())
+    }
+
+    // Add an accepted community track to the registry and mint its ownership NFT to the
+    // creator, who then earns the configured creator cut of race awards on it (permissionless)
+    pub fn register_community_track(
+        ctx: Context<RegisterCommunityTrack>,
+        track_id: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.track_submission.status == SubmissionStatus::Accepted,
+            ErrorCode::InvalidTrackSubmission
+        );
+
+        let game_state_seeds = &[b"game_state".as_ref(), &[ctx.accounts.game_state.bump]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.track_owner_mint.to_account_info(),
+                    to: ctx.accounts.creator_nft_account.to_account_info(),
+                    authority: ctx.accounts.game_state.to_account_info(),
+                },
+                &[&game_state_seeds[..]],
+            ),
+            1,
+        )?;
+
+        let track = &mut ctx.accounts.track;
+        track.track_id = track_id;
+        track.owner_mint = ctx.accounts.track_owner_mint.key();
+        track.owner_cut_bps = ctx.accounts.game_state.curation.creator_cut_bps;
+        track.total_owner_paid = 0;
+        track.races_paid = 0;
+        track.bump = ctx.bumps.track;
+
+        msg!("Community track {} registered for {}", track_id, ctx.accounts.track_submission.creator);
+        Ok(())
+    }
+
+    // Set the track curation stakes, voting period, slash and creator cut (admin only)
+    pub fn update_curation_config(
+        ctx: Context<UpdateGameState>,
+        curation: CurationConfig,
+    ) -> Result<()> {
+        require!(
+            curation.voting_secs > 0
+                && curation.slash_bps <= BPS_DENOMINATOR
+                && curation.creator_cut_bps <= Track::MAX_OWNER_CUT_BPS,
+            ErrorCode::InvalidTrackSubmission
+        );
+        ctx.accounts.game_state.curation = curation;
+
+        msg!("Track curation: {} $SPEEDY minimum stake, {}s voting", curation.min_stake, curation.voting_secs);
+        Ok(())
+    }
+
+    // Record a replay-verified time that beats a track's world record (oracle only). The new
+    // holder is paid the standing bounty, which is then topped back up from the vault to the
+    // configured amount. The first time recorded on a track sets the record without a bounty.
+    pub fn submit_world_record(
+        ctx: Context<SubmitWorldRecord>,
+        track_id: u64,
+        time_ms: u64,
+        replay_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(time_ms > 0, ErrorCode::NotAWorldRecord);
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let holder = ctx.accounts.player.key();
+        let world_record = &mut ctx.accounts.world_record;
+        let first_record = world_record.records_set == 0;
+        if first_record {
+            world_record.track_id = track_id;
+            world_record.bounty = ctx.accounts.game_state.world_record_bounty;
+            world_record.bump = ctx.bumps.world_record;
+        } else {
+            require!(time_ms < world_record.time_ms, ErrorCode::NotAWorldRecord);
+        }
+
+        // Check if vault has sufficient balance
+        let bounty = if first_record { 0 } else { world_record.bounty };
+        if bounty > 0 {
+            ctx.accounts.game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+            ctx.accounts.game_state.require_cosign(ctx.accounts.cosigner.as_ref(), bounty)?;
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, bounty, 0)?;
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                bounty,
+                ctx.accounts.game_state.bump,
+            )?;
+            ctx.accounts.game_state.record_distribution(bounty)?;
+        }
+
+        let previous_holder = ctx.accounts.world_record.holder;
+        let previous_time_ms = ctx.accounts.world_record.time_ms;
+        let world_record = &mut ctx.accounts.world_record;
+        world_record.holder = holder;
+        world_record.time_ms = time_ms;
+        world_record.replay_hash = replay_hash;
+        world_record.set_at = now;
+        world_record.records_set = world_record.records_set.saturating_add(1);
+        if bounty > 0 {
+            world_record.bounty = ctx.accounts.game_state.world_record_bounty;
+        }
+
+        emit!(WorldRecordSet {
+            track_id,
+            holder,
+            time_ms,
+            previous_holder,
+            previous_time_ms,
+            bounty_paid: bounty,
+            replay_hash,
+            timestamp: now,
+        });
+
+        log_msg!("World record on track {}: {} ms by {}, bounty {} $SPEEDY", track_id, time_ms, holder, bounty);
+        Ok(())
+    }
+
+    // Add to a track's standing world record bounty; the tokens go to the game vault
+    pub fn fund_world_record_bounty(
+        ctx: Context<FundWorldRecordBounty>,
+        track_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidRewardAmount);
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        let world_record = &mut ctx.accounts.world_record;
+        world_record.bounty = world_record.bounty.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        msg!("Track {} world record bounty now {} $SPEEDY", track_id, world_record.bounty);
+        Ok(())
+    }
+
+    // Set the bounty each track's world record is topped back up to after it is claimed
+    // (admin only)
+    pub fn set_world_record_bounty(
+        ctx: Context<UpdateGameState>,
+        bounty: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.world_record_bounty = bounty;
+
+        msg!("World record bounty set to {} $SPEEDY", bounty);
+        Ok(())
+    }
+
+    // Record a player's score for a season/track (oracle only). Only a new personal best is
+    // stored and placed on the leaderboard; anything else is a no-op that emits `ScoreIgnored`.
+    // Entries are kept in `speedy_core::leaderboard_order`, so equal scores rank by earlier
+    // submission slot, then by the smaller player key.
+    pub fn submit_score(
+        ctx: Context<SubmitScore>,
+        season: u64,
+        track_id: u64,
+        player: Pubkey,
+        score: u64,
+    ) -> Result<()> {
+        let slot = Clock::get()?.slot;
+        let player_best = &mut ctx.accounts.player_best;
+        let first_submission = player_best.player == Pubkey::default();
+        if first_submission {
+            player_best.season = season;
+            player_best.track_id = track_id;
+            player_best.player = player;
+            player_best.bump = ctx.bumps.player_best;
+        }
+        player_best.submissions = player_best.submissions.saturating_add(1);
+
+        if !first_submission && score <= player_best.best_score {
+            emit!(ScoreIgnored {
+                season,
+                track_id,
+                player,
+                score,
+                best_score: player_best.best_score,
+            });
+            log_msg!("Score {} does not beat best {}", score, player_best.best_score);
+            return Ok(());
+        }
+
+        let previous_best = player_best.best_score;
+        player_best.best_score = score;
+        player_best.best_slot = slot;
+
+        let leaderboard = &mut ctx.accounts.leaderboard;
+        leaderboard.season = season;
+        leaderboard.track_id = track_id;
+        leaderboard.bump = ctx.bumps.leaderboard;
+        let rank = leaderboard.insert(LeaderboardEntry { player, score, slot });
+
+        emit!(NewPersonalBest {
+            season,
+            track_id,
+            player,
+            score,
+            previous_best,
+            slot,
+            rank,
+        });
+
+        log_msg!("Personal best {} on track {} season {} ranks {}", score, track_id, season, rank);
+        Ok(())
+    }
+
+    // Open a prediction pool on who tops a week's leaderboard (admin only)
+    pub fn create_prediction_market(
+        ctx: Context<CreatePredictionMarket>,
+        week: u64,
+        candidates: Vec<Pubkey>,
+        close_time: i64,
+        rake_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::PREDICTION_MARKETS)?;
+        require!(
+            candidates.len() >= 2
+                && candidates.len() <= PredictionMarket::MAX_OUTCOMES
+                && rake_bps <= BPS_DENOMINATOR
+                && close_time > current_timestamp(ctx.remaining_accounts)?,
+            ErrorCode::InvalidPredictionMarket
+        );
+
+        let market = &mut ctx.accounts.prediction_market;
+        market.week = week;
+        market.outcome_pools = vec![0; candidates.len()];
+        market.candidates = candidates;
+        market.close_time = close_time;
+        market.rake_bps = rake_bps;
+        market.total_pool = 0;
+        market.settled = false;
+        market.winning_outcome = None;
+        market.escrow = ctx.accounts.escrow.key();
+        market.bump = ctx.bumps.prediction_market;
+
+        append_to_registry(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.registry_page,
+            ctx.bumps.registry_page,
+            RegistryKind::PredictionMarket,
+            ctx.accounts.prediction_market.key(),
+        )?;
+
+        msg!("Prediction market opened for week {}", week);
+        Ok(())
+    }
+
+    // Create the player's opt-in position index. From then on every instruction that opens or
+    // closes an indexed position updates it.
+    pub fn create_player_index(
+        ctx: Context<CreatePlayerIndex>,
+    ) -> Result<()> {
+        let player_index = &mut ctx.accounts.player_index;
+        player_index.player = ctx.accounts.player.key();
+        player_index.entries = Vec::new();
+        player_index.bump = ctx.bumps.player_index;
+
+        msg!("Position index created for player {}", player_index.player);
+        Ok(())
+    }
+
+    // Drop finished positions from the player's index, passed as remaining accounts: closed
+    // accounts, and tournament and jackpot entries, which stay on chain as participation records
+    pub fn prune_player_index(
+        ctx: Context<PrunePlayerIndex>,
+    ) -> Result<()> {
+        let player_index = &mut ctx.accounts.player_index;
+        let listed = player_index.entries.len();
+        for position in ctx.remaining_accounts {
+            let Some(entry) = player_index.entries.iter().find(|entry| entry.address == position.key()) else {
+                continue;
+            };
+            let closed = position.owner != &crate::ID || position.data_is_empty();
+            let record = matches!(entry.kind, PositionKind::TournamentEntry | PositionKind::JackpotEntry);
+            require!(closed || record, ErrorCode::PositionStillOpen);
+            player_index.remove(&position.key());
+        }
+
+        msg!("Pruned {} positions from the index of {}", listed - player_index.entries.len(), player_index.player);
+        Ok(())
+    }
+
+    // Stake on a candidate topping the week; repeat stakes must back the same candidate
+    pub fn place_prediction(
+        ctx: Context<PlacePrediction>,
+        _week: u64,
+        outcome: u8,
+        amount: u64,
+    ) -> Result<()> {
+        let market = &mut ctx.accounts.prediction_market;
+        require!(
+            current_timestamp(ctx.remaining_accounts)? < market.close_time,
+            ErrorCode::PredictionMarketClosed
+        );
+        require!(
+            amount > 0 && (outcome as usize) < market.candidates.len(),
+            ErrorCode::InvalidPredictionMarket
+        );
+
+        let stake = &mut ctx.accounts.prediction_stake;
+        if stake.player == Pubkey::default() {
+            stake.week = market.week;
+            stake.player = ctx.accounts.player.key();
+            stake.outcome = outcome;
+            stake.amount = 0;
+            stake.claimed = false;
+            stake.bump = ctx.bumps.prediction_stake;
+        }
+        require!(stake.outcome == outcome, ErrorCode::InvalidPredictionMarket);
+        stake.amount = stake.amount.checked_add(amount).ok_or(ErrorCode::InvalidRewardAmount)?;
+        index_position(&ctx.accounts.player_index, PositionKind::PredictionStake, stake.key())?;
+
+        let pool = &mut market.outcome_pools[outcome as usize];
+        *pool = pool.checked_add(amount).ok_or(ErrorCode::InvalidRewardAmount)?;
+        market.total_pool = market.total_pool.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        msg!("Player {} staked {} $SPEEDY on outcome {}", stake.player, amount, outcome);
+        Ok(())
+    }
+
+    // Settle against the committed weekly standings by proving the rank-1 leaf. If the top
+    // racer was not a candidate, or nobody backed them, the market is void and stakes refund.
+    // Otherwise the rake moves to the vault.
+    pub fn settle_prediction_market(
+        ctx: Context<SettlePredictionMarket>,
+        week: u64,
+        top_racer: Pubkey,
+        prize_amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::PREDICTION_MARKETS)?;
+        let market = &mut ctx.accounts.prediction_market;
+        require!(!market.settled, ErrorCode::PredictionMarketSettled);
+        require!(
+            current_timestamp(ctx.remaining_accounts)? >= market.close_time,
+            ErrorCode::PredictionMarketNotSettled
+        );
+
+        let leaf = standings_leaf(&top_racer, week, 1, prize_amount);
+        require!(
+            verify_merkle_proof(&proof, &ctx.accounts.weekly_standings.merkle_root, leaf),
+            ErrorCode::InvalidMerkleProof
+        );
+
+        market.settled = true;
+        market.winning_outcome = market.candidates.iter()
+            .position(|candidate| *candidate == top_racer)
+            .filter(|index| market.outcome_pools[*index] > 0)
+            .map(|index| index as u8);
+
+        if market.winning_outcome.is_some() {
+            let rake = speedy_core::apply_bps(market.total_pool, market.rake_bps)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+            if rake > 0 {
+                let week_bytes = week.to_le_bytes();
+                let market_seeds = &[
+                    b"prediction_market".as_ref(),
+                    week_bytes.as_ref(),
+                    &[market.bump],
+                ];
+                let cpi_accounts = Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: market.to_account_info(),
+                };
+                let cpi_program = ctx.accounts.token_program.to_account_info();
+                transfer(
+                    CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&market_seeds[..]]),
+                    rake,
+                )?;
+            }
+        }
+
+        msg!("Prediction market for week {} settled, winner {}", week, top_racer);
+        Ok(())
+    }
+
+    // Claim winnings (or a refund from a void market) from a settled prediction market
+    pub fn claim_prediction(
+        ctx: Context<ClaimPrediction>,
+        week: u64,
+    ) -> Result<()> {
+        let market = &ctx.accounts.prediction_market;
+        require!(market.settled, ErrorCode::PredictionMarketNotSettled);
+        let stake = &ctx.accounts.prediction_stake;
+        require!(!stake.claimed, ErrorCode::PredictionAlreadyClaimed);
+
+        let payout = match market.winning_outcome {
+            None => stake.amount,
+            Some(winning_outcome) => {
+                require!(stake.outcome == winning_outcome, ErrorCode::PredictionLost);
+                speedy_core::prediction_payout(
+                    stake.amount,
+                    market.outcome_pools[winning_outcome as usize],
+                    market.total_pool,
+                    market.rake_bps,
+                ).ok_or(ErrorCode::InvalidRewardAmount)?
+            }
+        };
+
+        let week_bytes = week.to_le_bytes();
+        let market_seeds = &[
+            b"prediction_market".as_ref(),
+            week_bytes.as_ref(),
+            &[market.bump],
+        ];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.escrow.to_account_info(),
+            to: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.prediction_market.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&market_seeds[..]]),
+            payout,
+        )?;
+
+        ctx.accounts.prediction_stake.claimed = true;
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.prediction_stake.key())?;
+
+        msg!("Player {} claimed {} $SPEEDY from week {} prediction", ctx.accounts.player.key(), payout, week);
+        Ok(())
+    }
+
+    // Open a per-second payment stream from the vault to a partner or creator (admin or treasurer)
+    pub fn create_payment_stream(
+        ctx: Context<CreatePaymentStream>,
+        stream_id: u64,
+        recipient: Pubkey,
+        rate_per_second: u64,
+        start_time: i64,
+        cliff_time: i64,
+        end_time: i64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::PAYMENT_STREAMS)?;
+        require!(
+            rate_per_second > 0 && start_time <= cliff_time && cliff_time <= end_time && start_time < end_time,
+            ErrorCode::InvalidStreamSchedule
+        );
+
+        let stream = &mut ctx.accounts.payment_stream;
+        stream.stream_id = stream_id;
+        stream.recipient = recipient;
+        stream.rate_per_second = rate_per_second;
+        stream.start_time = start_time;
+        stream.cliff_time = cliff_time;
+        stream.end_time = end_time;
+        stream.withdrawn = 0;
+        stream.bump = ctx.bumps.payment_stream;
+        index_position(&ctx.accounts.player_index, PositionKind::PaymentStream, ctx.accounts.payment_stream.key())?;
+
+        msg!("Payment stream {} created for {} at {} per second", stream_id, recipient, rate_per_second);
+        Ok(())
+    }
+
+    // Withdraw everything streamed so far (recipient only)
+    pub fn claim_payment_stream(
+        ctx: Context<ClaimPaymentStream>,
+        _stream_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let stream = &ctx.accounts.payment_stream;
+        let claimable = stream.vested_amount(now)?
+            .checked_sub(stream.withdrawn)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(claimable > 0, ErrorCode::NothingToClaim);
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, claimable, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.recipient_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            claimable,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(claimable)?;
+
+        let stream = &mut ctx.accounts.payment_stream;
+        stream.withdrawn = stream.withdrawn.checked_add(claimable)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        // A fully paid-out stream is no longer an open position
+        if now >= stream.end_time {
+            unindex_position(&ctx.accounts.player_index, &stream.key())?;
+        }
+
+        emit!(PaymentStreamClaimed {
+            stream_id: stream.stream_id,
+            recipient: stream.recipient,
+            amount: claimable,
+            timestamp: now,
+        });
+
+        log_msg!("Claimed {} $SPEEDY from stream {}", claimable, stream.stream_id);
+        Ok(())
+    }
+
+    // Withdraw the vested part of the player's large awards
+    pub fn claim_vested_rewards(
+        ctx: Context<ClaimVestedRewards>,
+    ) -> Result<()> {
+        ctx.accounts.player_profile.require_payout_currency(PoolCurrency::Speedy)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        ctx.accounts.player_profile.prune_buffs(now);
+        let player_profile = &mut ctx.accounts.player_profile;
+        let claimable = player_profile.vesting.take_claimable(now);
+        require!(claimable > 0, ErrorCode::NothingToClaim);
+
+        // Entry fee loans are settled first; those tokens already left the vault
+        let repaid = claimable.min(player_profile.fee_loan);
+        player_profile.fee_loan -= repaid;
+        let payout = claimable - repaid;
+
+        if payout > 0 {
+            // Check if vault has sufficient balance
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, payout, payout)?;
+
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                payout,
+                ctx.accounts.game_state.bump,
+            )?;
+        }
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_vesting = game_state.total_vesting.saturating_sub(claimable);
+        game_state.total_fee_loans = game_state.total_fee_loans.saturating_sub(repaid);
+        game_state.record_distribution(payout)?;
+        if ctx.accounts.player_profile.vesting.outstanding() == 0 {
+            unindex_position(&ctx.accounts.player_index, &ctx.accounts.player_profile.key())?;
+        }
+
+        emit!(VestedRewardsClaimed {
+            player: ctx.accounts.player.key(),
+            amount: payout,
+            timestamp: now,
+        });
+
+        log_msg!("Claimed {} vested $SPEEDY, {} repaid entry fee loans", payout, repaid);
+        Ok(())
+    }
+
+    // Opt into (or out of) having race, challenge and tournament awards auto-staked. Opting
+    // out does not unlock winnings already staked.
+    pub fn set_auto_stake(
+        ctx: Context<SetAutoStake>,
+        enabled: bool,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        player_profile.auto_stake = enabled;
+
+        msg!("Auto-stake {}", if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    // Withdraw auto-staked winnings and bonuses once the lock has passed
+    pub fn unstake_winnings(
+        ctx: Context<UnstakeWinnings>,
+    ) -> Result<()> {
+        ctx.accounts.player_profile.require_payout_currency(PoolCurrency::Speedy)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        player_profile.prune_buffs(now);
+        let amount = player_profile.auto_staked;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+        require!(now >= player_profile.auto_stake_unlock_at, ErrorCode::StakeLocked);
+        player_profile.auto_staked = 0;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, amount, amount)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_auto_staked = game_state.total_auto_staked.saturating_sub(amount);
+        game_state.record_distribution(amount)?;
+
+        emit!(WinningsUnstaked {
+            player: ctx.accounts.player.key(),
+            amount,
+            timestamp: now,
+        });
+
+        log_msg!("Unstaked {} $SPEEDY of winnings", amount);
+        Ok(())
+    }
+
+    // Sweep everything a returning player can claim in one transaction: race awards whose
+    // dispute window has closed (their receipts passed as remaining accounts), unlocked
+    // auto-staked winnings and vested rewards, net of entry fee loans. Empty sources are skipped.
+    pub fn claim_everything<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimEverything<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.player_profile.require_payout_currency(PoolCurrency::Speedy)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player = ctx.accounts.player.key();
+
+        let sweep = sweep_claimable(ctx.remaining_accounts, &mut ctx.accounts.player_profile, now)?;
+        let payout = sweep.payout()?;
+
+        if payout > 0 {
+            // Check if vault has sufficient balance
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, payout, payout)?;
+
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                payout,
+                ctx.accounts.game_state.bump,
+            )?;
+        }
+
+        sweep.settle(&mut ctx.accounts.game_state, payout)?;
+        if ctx.accounts.player_profile.vesting.outstanding() == 0 {
+            unindex_position(&ctx.accounts.player_index, &ctx.accounts.player_profile.key())?;
+        }
+        emit!(EverythingClaimed {
+            player,
+            race_awards: sweep.race_awards,
+            unstaked: sweep.unstaked,
+            vested: sweep.vested,
+            loan_repaid: sweep.repaid,
+            timestamp: now,
+        });
+
+        log_msg!("Claimed {} $SPEEDY across race awards, auto-stake and vesting", payout);
+        Ok(())
+    }
+
+    // Choose whether claims pay out in $SPEEDY or are swapped to the buyback stablecoin.
+    // Players paid in stablecoin claim through `claim_everything_as_stable`.
+    pub fn set_payout_currency(
+        ctx: Context<SetAutoStake>,
+        currency: PoolCurrency,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+        player_profile.payout_currency = currency;
+
+        msg!("Payouts now in {}", if currency == PoolCurrency::Stable { "stablecoin" } else { "$SPEEDY" });
+        Ok(())
+    }
+
+    // `claim_everything` for players paid in stablecoin. The first `receipt_count` remaining
+    // accounts are race receipts; the rest are forwarded to the configured AMM with
+    // `swap_data`. The payout lands in the player's $SPEEDY account and the player signs the
+    // swap, so it can only spend the player's own tokens. The swap may spend at most the
+    // payout and must deliver at least `min_stable_out`.
+    pub fn claim_everything_as_stable<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimEverythingAsStable<'info>>,
+        receipt_count: u8,
+        min_stable_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.player_profile.require_payout_currency(PoolCurrency::Stable)?;
+        require!(
+            ctx.accounts.amm_program.key() == ctx.accounts.buyback_config.amm_program,
+            ErrorCode::InvalidAmmProgram
+        );
+        require!(
+            receipt_count as usize <= ctx.remaining_accounts.len(),
+            ErrorCode::InvalidRaceReceipt
+        );
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player = ctx.accounts.player.key();
+        let (receipts, swap_accounts) = ctx.remaining_accounts.split_at(receipt_count as usize);
+
+        let sweep = sweep_claimable(receipts, &mut ctx.accounts.player_profile, now)?;
+        let payout = sweep.payout()?;
+        require!(payout > 0, ErrorCode::NothingToClaim);
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, payout, payout)?;
+
+        let tokens_before = ctx.accounts.player_token_account.amount;
+        let stable_before = ctx.accounts.player_stable_account.amount;
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            payout,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let accounts: Vec<AccountMeta> = swap_accounts
+            .iter()
+            .map(|account| {
+                if account.is_writable {
+                    AccountMeta::new(*account.key, account.is_signer)
+                } else {
+                    AccountMeta::new_readonly(*account.key, account.is_signer)
+                }
+            })
+            .collect();
+        let swap_ix = Instruction {
+            program_id: ctx.accounts.amm_program.key(),
+            accounts,
+            data: swap_data,
+        };
+        invoke(&swap_ix, swap_accounts)?;
+
+        ctx.accounts.player_token_account.reload()?;
+        ctx.accounts.player_stable_account.reload()?;
+        require!(
+            ctx.accounts.player_token_account.amount >= tokens_before,
+            ErrorCode::SlippageExceeded
+        );
+        let tokens_swapped = tokens_before
+            .checked_add(payout)
+            .and_then(|total| total.checked_sub(ctx.accounts.player_token_account.amount))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let stable_out = ctx.accounts.player_stable_account.amount.saturating_sub(stable_before);
+        require!(stable_out >= min_stable_out, ErrorCode::SlippageExceeded);
+
+        sweep.settle(&mut ctx.accounts.game_state, payout)?;
+        if ctx.accounts.player_profile.vesting.outstanding() == 0 {
+            unindex_position(&ctx.accounts.player_index, &ctx.accounts.player_profile.key())?;
+        }
+        emit!(EverythingClaimed {
+            player,
+            race_awards: sweep.race_awards,
+            unstaked: sweep.unstaked,
+            vested: sweep.vested,
+            loan_repaid: sweep.repaid,
+            timestamp: now,
+        });
+        emit!(PayoutSwapped {
+            player,
+            tokens_in: tokens_swapped,
+            stable_out,
+            timestamp: now,
+        });
+
+        log_msg!("Claimed {} $SPEEDY, swapped {} for {} stable", payout, tokens_swapped, stable_out);
+        Ok(())
+    }
+
+    // Write a player's earnings for a finished calendar year into their yearly statement
+    // (permissionless). The caller pays for the statement account.
+    pub fn close_yearly_statement(
+        ctx: Context<CloseYearlyStatement>,
+        player: Pubkey,
+        year: u16,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let earnings = ctx.accounts.player_profile.year_earnings[(year % 2) as usize];
+        require!(
+            year < speedy_core::calendar_year(now) && earnings.year == year && earnings.count > 0,
+            ErrorCode::YearlyStatementUnavailable
+        );
+
+        let statement = &mut ctx.accounts.yearly_statement;
+        statement.player = player;
+        statement.year = year;
+        statement.reward_count = earnings.count;
+        statement.total = earnings.total;
+        statement.by_type = earnings.by_type;
+        statement.closed_at = now;
+        statement.bump = ctx.bumps.yearly_statement;
+        ctx.accounts.player_profile.year_earnings[(year % 2) as usize] = YearEarnings::default();
+
+        emit!(YearlyStatementClosed {
+            player,
+            year,
+            reward_count: earnings.count,
+            total: earnings.total,
+            timestamp: now,
+        });
+
+        msg!("Closed {} statement for {}: {} $SPEEDY over {} rewards", year, player, earnings.total, earnings.count);
+        Ok(())
+    }
+
+    // Set the auto-stake bonus and lock period (admin only)
+    pub fn update_auto_stake_config(
+        ctx: Context<UpdateGameState>,
+        auto_stake: AutoStakeConfig,
+    ) -> Result<()> {
+        require!(
+            auto_stake.bonus_bps <= BPS_DENOMINATOR && auto_stake.lock_secs >= 0,
+            ErrorCode::InvalidAutoStakeConfig
+        );
+        ctx.accounts.game_state.auto_stake = auto_stake;
+
+        msg!("Auto-stake pays {} bps bonus for a {}s lock", auto_stake.bonus_bps, auto_stake.lock_secs);
+        Ok(())
+    }
+
+    // Set the award size above which payouts vest, and the vesting period (admin only)
+    pub fn update_vesting_config(
+        ctx: Context<UpdateGameState>,
+        vesting: VestingConfig,
+    ) -> Result<()> {
+        require!(vesting.duration_secs > 0, ErrorCode::InvalidVestingConfig);
+        ctx.accounts.game_state.vesting = vesting;
+
+        msg!("Awards above {} now vest over {}s", vesting.threshold, vesting.duration_secs);
+        Ok(())
+    }
+
+    // Stop a stream at the current time; amounts already streamed stay claimable (admin or treasurer)
+    pub fn cancel_payment_stream(
+        ctx: Context<CancelPaymentStream>,
+        _stream_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let stream = &mut ctx.accounts.payment_stream;
+        if now < stream.end_time {
+            stream.end_time = now.max(stream.start_time);
+        }
+
+        msg!("Payment stream {} cancelled", stream.stream_id);
+        Ok(())
+    }
+
+    // Create the stablecoin side vault that funds stable prize pools (admin only)
+    pub fn initialize_stable_vault(
+        ctx: Context<InitializeStableVault>,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        require!(game_state.stable_mint == Pubkey::default(), ErrorCode::StableVaultAlreadyInitialized);
+        game_state.stable_mint = ctx.accounts.stable_mint.key();
+        game_state.stable_vault = ctx.accounts.stable_vault.key();
+
+        msg!("Stable side vault created for mint {}", game_state.stable_mint);
+        Ok(())
+    }
+
+    // Configure the AMM used for treasury buybacks and create the stablecoin treasury (admin only)
+    pub fn configure_buyback(
+        ctx: Context<ConfigureBuyback>,
+        amm_program: Pubkey,
+    ) -> Result<()> {
+        let buyback_config = &mut ctx.accounts.buyback_config;
+        buyback_config.amm_program = amm_program;
+        buyback_config.stable_mint = ctx.accounts.stable_mint.key();
+        buyback_config.stable_treasury = ctx.accounts.stable_treasury.key();
+        buyback_config.bump = ctx.bumps.buyback_config;
+
+        msg!("Buyback configured with AMM {}", amm_program);
+        Ok(())
+    }
+
+    // Swap treasury stablecoins for $SPEEDY through the configured AMM and burn the proceeds
+    // (admin or treasurer). Swap accounts are passed as remaining accounts and `swap_data` is forwarded
+    // verbatim; spend and output bounds are enforced from balance deltas around the CPI. The game
+    // state PDA never signs the swap: the caller is approved as delegate over exactly
+    // `max_stable_in` of the stable treasury and signs it as the transfer authority.
+    pub fn execute_buyback<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExecuteBuyback<'info>>,
+        max_stable_in: u64,
+        min_tokens_out: u64,
+        swap_data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::BUYBACK)?;
+        require!(
+            ctx.accounts.amm_program.key() == ctx.accounts.buyback_config.amm_program,
+            ErrorCode::InvalidAmmProgram
+        );
+
+        let stable_before = ctx.accounts.stable_treasury.amount;
+        let tokens_before = ctx.accounts.vault.amount;
+
+        // Let the caller move at most `max_stable_in` out of the stable treasury
+        let authority_seeds = &[b"game_state".as_ref(), &[ctx.accounts.game_state.bump]];
+        let cpi_accounts = Approve {
+            to: ctx.accounts.stable_treasury.to_account_info(),
+            delegate: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.game_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        approve(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&authority_seeds[..]]),
+            max_stable_in,
+        )?;
+
+        let accounts: Vec<AccountMeta> = ctx.remaining_accounts
+            .iter()
+            .map(|account| {
+                if account.is_writable {
+                    AccountMeta::new(*account.key, account.is_signer)
+                } else {
+                    AccountMeta::new_readonly(*account.key, account.is_signer)
+                }
+            })
+            .collect();
+        let swap_ix = Instruction {
+            program_id: ctx.accounts.amm_program.key(),
+            accounts,
+            data: swap_data,
+        };
+        invoke(&swap_ix, ctx.remaining_accounts)?;
+
+        let cpi_accounts = Revoke {
+            source: ctx.accounts.stable_treasury.to_account_info(),
+            authority: ctx.accounts.game_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        revoke(CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&authority_seeds[..]]))?;
+
+        ctx.accounts.stable_treasury.reload()?;
+        ctx.accounts.vault.reload()?;
+        require!(ctx.accounts.vault.amount >= tokens_before, ErrorCode::SlippageExceeded);
+        let stable_spent = stable_before.saturating_sub(ctx.accounts.stable_treasury.amount);
+        let tokens_bought = ctx.accounts.vault.amount - tokens_before;
+        require!(stable_spent <= max_stable_in, ErrorCode::SlippageExceeded);
+        require!(tokens_bought >= min_tokens_out, ErrorCode::SlippageExceeded);
+
+        // Burn exactly what the swap delivered into the vault
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.game_state.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        burn(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&authority_seeds[..]]),
+            tokens_bought,
+        )?;
+
+        let buyback_config = &mut ctx.accounts.buyback_config;
+        buyback_config.total_stable_spent = buyback_config.total_stable_spent.checked_add(stable_spent)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        buyback_config.total_tokens_burned = buyback_config.total_tokens_burned.checked_add(tokens_bought)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(tokens_bought)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(BuybackExecuted {
+            stable_spent,
+            tokens_burned: tokens_bought,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Bought back and burned {} $SPEEDY for {} stable", tokens_bought, stable_spent);
+        Ok(())
+    }
+
+    // Configure USD-pegged rewards (admin or rate manager). When enabled, `usd_rates` (in USD cents) are
+    // converted to $SPEEDY at award time using the configured Pyth price feed.
+    pub fn update_usd_pricing(
+        ctx: Context<UpdateRates>,
+        usd_pricing: UsdPricingConfig,
+        usd_rates: TokenRates,
+    ) -> Result<()> {
+        require!(
+            usd_pricing.max_confidence_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidPriceFeed
+        );
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.usd_pricing = usd_pricing;
+        game_state.usd_rates = usd_rates;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        record_rate_snapshot(game_state, ctx.accounts.rate_history.as_mut(), now)?;
+        msg!("USD pricing {}", if usd_pricing.enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    // Set how many seconds past expiry a voucher is still accepted (admin only)
+    pub fn set_voucher_clock_tolerance(
+        ctx: Context<UpdateGameState>,
+        tolerance_secs: i64,
+    ) -> Result<()> {
+        require!(tolerance_secs >= 0, ErrorCode::InvalidClockTolerance);
+
+        ctx.accounts.game_state.voucher_clock_tolerance_secs = tolerance_secs;
+        msg!("Voucher clock tolerance set to {}s", tolerance_secs);
+        Ok(())
+    }
+
+    // Check that every singleton PDA passed in is owned by the program and carries the
+    // discriminator and allocated size this build expects. Run after upgrades, before
+    // re-enabling awards; singletons that don't exist yet are simply left out. A game state
+    // still in the original layout is reported separately so it can be fixed with
+    // `migrate_game_state`.
+    pub fn verify_layout(
+        ctx: Context<VerifyLayout>,
+    ) -> Result<()> {
+        let accounts = &ctx.accounts;
+        require!(
+            accounts.game_state.data_len() != space::GAME_STATE_V1,
+            ErrorCode::GameStateMigrationRequired
+        );
+        verify_account_layout(&accounts.game_state, GameState::DISCRIMINATOR, space::GAME_STATE)?;
+        if let Some(buyback_config) = accounts.buyback_config.as_ref() {
+            verify_account_layout(buyback_config, BuybackConfig::DISCRIMINATOR, space::BUYBACK_CONFIG)?;
+        }
+        if let Some(jackpot) = accounts.jackpot.as_ref() {
+            verify_account_layout(jackpot, Jackpot::DISCRIMINATOR, space::JACKPOT)?;
+        }
+        if let Some(token_sale) = accounts.token_sale.as_ref() {
+            verify_account_layout(token_sale, TokenSale::DISCRIMINATOR, space::TOKEN_SALE)?;
+        }
+        if let Some(config_export) = accounts.config_export.as_ref() {
+            verify_account_layout(config_export, ConfigExport::DISCRIMINATOR, space::CONFIG_EXPORT)?;
+        }
+
+        msg!("Account layouts verified");
+        Ok(())
+    }
+
+    // Grow a game state written by the original release to the current layout (admin only).
+    // The original fields are kept and every field added since gets the same default as a
+    // fresh `initialize_token`; the authority pays the extra rent. Until this runs, every
+    // instruction that loads the game state fails to deserialize it.
+    pub fn migrate_game_state(
+        ctx: Context<MigrateGameState>,
+    ) -> Result<()> {
+        let game_state_info = ctx.accounts.game_state.to_account_info();
+        verify_account_layout(&game_state_info, GameState::DISCRIMINATOR, space::GAME_STATE_V1)?;
+
+        let rent = Rent::get()?.minimum_balance(space::GAME_STATE);
+        let top_up = rent.saturating_sub(game_state_info.lamports());
+        if top_up > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.authority.to_account_info(),
+                        to: game_state_info.clone(),
+                    },
+                ),
+                top_up,
+            )?;
+        }
+        // New bytes are zeroed, which every field type deserializes from
+        game_state_info.realloc(space::GAME_STATE, true)?;
+
+        let mut game_state = GameState::try_deserialize(&mut &game_state_info.try_borrow_data()?[..])?;
+        require_keys_eq!(game_state.authority, ctx.accounts.authority.key(), ErrorCode::Unauthorized);
+        game_state.set_defaults();
+        game_state.try_serialize(&mut &mut game_state_info.try_borrow_mut_data()?[..])?;
+
+        msg!("Game state migrated to {} bytes", space::GAME_STATE);
+        Ok(())
+    }
+
+    // Snapshot the economy config into the export buffer (admin only) so another environment
+    // can apply it with `import_config`
+    pub fn export_config(
+        ctx: Context<ExportConfig>,
+    ) -> Result<()> {
+        let charity_wallet = ctx.accounts.donation_stats.as_ref()
+            .map_or(Pubkey::default(), |stats| stats.charity_wallet);
+        let config = ctx.accounts.game_state.config(charity_wallet);
+        let config_hash = config.hash()?;
+
+        let config_export = &mut ctx.accounts.config_export;
+        config_export.version = GameConfig::VERSION;
+        config_export.exported_at = current_timestamp(ctx.remaining_accounts)?;
+        config_export.config_hash = config_hash;
+        config_export.config = config;
+        config_export.bump = ctx.bumps.config_export;
+
+        msg!("Exported config v{}", GameConfig::VERSION);
+        Ok(())
+    }
+
+    // Apply an exported config in one step (admin only). `expected_hash` must match the
+    // exported buffer's hash so a truncated or edited config is rejected.
+    pub fn import_config(
+        ctx: Context<ImportConfig>,
+        config: GameConfig,
+        expected_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(config.hash()? == expected_hash, ErrorCode::InvalidConfigImport);
+        config.validate()?;
+        ctx.accounts.game_state.require_direct_admin()?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        require!(
+            config.jackpot_burn_share_bps == 0 || game_state.jackpot.vault != Pubkey::default(),
+            ErrorCode::InvalidJackpotConfig
+        );
+        // The halving schedule is set once, same as configure_halving
+        require!(
+            !game_state.halving.is_set() || game_state.halving == config.halving,
+            ErrorCode::HalvingAlreadyConfigured
+        );
+        let mut config = config;
+        if config.halving.is_set() && config.halving.start_slot == 0 {
+            config.halving.start_slot = Clock::get()?.slot;
+        }
+        game_state.apply_config(&config);
+
+        let donation_stats = &mut ctx.accounts.donation_stats;
+        donation_stats.charity_wallet = config.charity_wallet;
+        donation_stats.bump = ctx.bumps.donation_stats;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        record_rate_snapshot(game_state, ctx.accounts.rate_history.as_mut(), now)?;
+
+        msg!("Imported config v{}", GameConfig::VERSION);
+        Ok(())
+    }
+
+    // Create a tournament with an escrowed prize pool and optional entry gates (admin only)
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        config: TournamentConfig,
+        metadata: Metadata,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        metadata.validate()?;
+        let TournamentConfig {
+            entry_fee,
+            max_entrants,
+            registration_deadline,
+            eligibility,
+            fee_curve,
+            disqualification_forfeit_bps,
+            prize_currency,
+            prize_claim_secs,
+            ..
+        } = config;
+        require!(
+            max_entrants > 0
+                && registration_deadline > current_timestamp(ctx.remaining_accounts)?
+                && disqualification_forfeit_bps <= BPS_DENOMINATOR
+                && prize_claim_secs >= 0,
+            ErrorCode::InvalidTournamentConfig
+        );
+        require!(
+            fee_curve.late_discount_bps <= BPS_DENOMINATOR
+                && fee_curve.fill_premium_bps <= u16::MAX - BPS_DENOMINATOR
+                && fee_curve.late_window_secs >= 0,
+            ErrorCode::InvalidTournamentConfig
+        );
+
+        // Prizes come from the escrow when they share the entry currency, otherwise from the
+        // game vault holding the prize currency
+        let prize_mint = ctx.accounts.game_state.mint_for(prize_currency)
+            .ok_or(ErrorCode::StableVaultNotInitialized)?;
+        let prize_source = if prize_mint == ctx.accounts.entry_mint.key() {
+            ctx.accounts.prize_escrow.key()
+        } else {
+            ctx.accounts.game_state.vault_for_mint(&prize_mint)
+                .ok_or(ErrorCode::StableVaultNotInitialized)?
+        };
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.entry_fee = entry_fee;
+        tournament.max_entrants = max_entrants;
+        tournament.registered_count = 0;
+        tournament.registration_deadline = registration_deadline;
+        tournament.prize_escrow = ctx.accounts.prize_escrow.key();
+        tournament.eligibility = eligibility;
+        tournament.fee_curve = fee_curve;
+        tournament.disqualification_forfeit_bps = disqualification_forfeit_bps;
+        tournament.total_prizes_paid = 0;
+        tournament.sponsors = Vec::new();
+        tournament.waitlist_head = 0;
+        tournament.waitlist_tail = 0;
+        tournament.prize_source = prize_source;
+        tournament.prize_claim_deadline = if prize_claim_secs == 0 {
+            0
+        } else {
+            registration_deadline.checked_add(prize_claim_secs).ok_or(ErrorCode::InvalidTournamentConfig)?
+        };
+        tournament.metadata = metadata;
+        tournament.bump = ctx.bumps.tournament;
+
+        append_to_registry(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.registry_page,
+            ctx.bumps.registry_page,
+            RegistryKind::Tournament,
+            ctx.accounts.tournament.key(),
+        )?;
+
+        msg!("Tournament {} created with {} slots", tournament_id, max_entrants);
+        Ok(())
+    }
+
+    // Register for a tournament, paying the entry fee into its prize escrow. Car rarity gates
+    // need the oracle to co-sign the declared rarity; allowlist gates need a merkle proof.
+    pub fn join_tournament(
+        ctx: Context<JoinTournament>,
+        _tournament_id: u64,
+        car_rarity: CarRarity,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+        // Freed slots go to the waitlist first
+        require!(
+            tournament.registered_count < tournament.max_entrants
+                && tournament.waitlist_head == tournament.waitlist_tail,
+            ErrorCode::TournamentFull
+        );
+
+        let player = ctx.accounts.player.key();
+        check_tournament_eligibility(
+            &tournament.eligibility,
+            &player,
+            &ctx.accounts.game_state.oracle,
+            ctx.accounts.oracle.as_ref(),
+            ctx.accounts.player_profile.as_ref(),
+            car_rarity,
+            &allowlist_proof,
+        )?;
+
+        let entry_fee = tournament.current_entry_fee(now)?;
+        if entry_fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: ctx.accounts.prize_escrow.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), entry_fee)?;
+        }
+
+        let entry = &mut ctx.accounts.tournament_entry;
+        entry.tournament_id = tournament.tournament_id;
+        entry.player = player;
+        entry.fee_paid = entry_fee;
+        entry.joined_at = now;
+        entry.bump = ctx.bumps.tournament_entry;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        index_position(&ctx.accounts.player_index, PositionKind::TournamentEntry, ctx.accounts.tournament_entry.key())?;
+
+        msg!("Player {} joined tournament {}", player, tournament.tournament_id);
+        Ok(())
+    }
+
+    // Leave a tournament before it starts with a full entry fee refund
+    pub fn withdraw_from_tournament(
+        ctx: Context<WithdrawFromTournament>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(
+            now < ctx.accounts.tournament.registration_deadline,
+            ErrorCode::RegistrationClosed
+        );
+
+        let refund = ctx.accounts.tournament_entry.fee_paid;
+        if refund > 0 {
+            transfer_from_tournament_escrow(
+                &ctx.accounts.tournament,
+                ctx.accounts.prize_escrow.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                refund,
+            )?;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.saturating_sub(1);
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.tournament_entry.key())?;
+
+        msg!("Player {} withdrew from tournament {}", ctx.accounts.player.key(), tournament_id);
+        Ok(())
+    }
+
+    // Create the tournament ticket mint and its exchange rates (admin only). One ticket is
+    // one tournament entry; tickets are ordinary SPL tokens, so they can be gifted or
+    // handed out in promotions.
+    pub fn initialize_tickets(
+        ctx: Context<InitializeTickets>,
+        price: u64,
+        refund_bps: u16,
+    ) -> Result<()> {
+        require!(price > 0 && refund_bps <= BPS_DENOMINATOR, ErrorCode::InvalidTicketConfig);
+        let ticket_config = &mut ctx.accounts.ticket_config;
+        ticket_config.ticket_mint = ctx.accounts.ticket_mint.key();
+        ticket_config.price = price;
+        ticket_config.refund_bps = refund_bps;
+        ticket_config.tickets_sold = 0;
+        ticket_config.tickets_spent = 0;
+        ticket_config.bump = ctx.bumps.ticket_config;
+
+        msg!("Tickets priced at {} $SPEEDY, refundable at {} bps", price, refund_bps);
+        Ok(())
+    }
+
+    // Change the ticket price and refund discount (admin only); tickets already sold refund
+    // at the new rates
+    pub fn update_ticket_rates(
+        ctx: Context<UpdateTicketConfig>,
+        price: u64,
+        refund_bps: u16,
+    ) -> Result<()> {
+        require!(price > 0 && refund_bps <= BPS_DENOMINATOR, ErrorCode::InvalidTicketConfig);
+        let ticket_config = &mut ctx.accounts.ticket_config;
+        ticket_config.price = price;
+        ticket_config.refund_bps = refund_bps;
+
+        msg!("Tickets priced at {} $SPEEDY, refundable at {} bps", price, refund_bps);
+        Ok(())
+    }
+
+    // Exchange $SPEEDY for tickets; the payment goes to the game vault
+    pub fn buy_tickets(
+        ctx: Context<BuyTickets>,
+        quantity: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TICKETS)?;
+        require!(quantity > 0, ErrorCode::InvalidRewardAmount);
+        let ticket_config = &ctx.accounts.ticket_config;
+        let mut cost = ticket_config.price.checked_mul(quantity)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        // A promo shop discount applies once, to this purchase
+        if let Some(player_profile) = ctx.accounts.player_profile.as_mut() {
+            if player_profile.ticket_discount_bps > 0 {
+                let discount = speedy_core::apply_bps(cost, player_profile.ticket_discount_bps)
+                    .ok_or(ErrorCode::InvalidRewardAmount)?;
+                cost -= discount;
+                player_profile.ticket_discount_bps = 0;
+            }
+        }
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), cost)?;
+
+        let ticket_seeds = &[b"ticket_config".as_ref(), &[ticket_config.bump]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.ticket_mint.to_account_info(),
+                    to: ctx.accounts.player_ticket_account.to_account_info(),
+                    authority: ticket_config.to_account_info(),
+                },
+                &[&ticket_seeds[..]],
+            ),
+            quantity,
+        )?;
+
+        let ticket_config = &mut ctx.accounts.ticket_config;
+        ticket_config.tickets_sold = ticket_config.tickets_sold.checked_add(quantity)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        msg!("Bought {} tickets for {} $SPEEDY", quantity, cost);
+        Ok(())
+    }
+
+    // Burn unused tickets for $SPEEDY from the vault at the discounted refund rate
+    pub fn refund_tickets(
+        ctx: Context<RefundTickets>,
+        quantity: u64,
+    ) -> Result<()> {
+        require!(quantity > 0, ErrorCode::InvalidRewardAmount);
+        let ticket_config = &ctx.accounts.ticket_config;
+        let refund = ticket_config.price.checked_mul(quantity)
+            .and_then(|value| speedy_core::apply_bps(value, ticket_config.refund_bps))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.ticket_mint.to_account_info(),
+            from: ctx.accounts.player_ticket_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), quantity)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, refund, 0)?;
+
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            refund,
+            ctx.accounts.game_state.bump,
+        )?;
+        ctx.accounts.game_state.record_distribution(refund)?;
+
+        msg!("Refunded {} tickets for {} $SPEEDY", quantity, refund);
+        Ok(())
+    }
+
+    // Enter a $SPEEDY tournament by burning one ticket instead of paying the fee. The vault
+    // funds the entry fee into the prize escrow, so the prize pool grows as for paid entries,
+    // as long as the fee does not exceed the ticket price. Ticket entries have no fee to
+    // refund on withdrawal.
+    pub fn spend_ticket(
+        ctx: Context<SpendTicket>,
+        _tournament_id: u64,
+        car_rarity: CarRarity,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+        // Freed slots go to the waitlist first
+        require!(
+            tournament.registered_count < tournament.max_entrants
+                && tournament.waitlist_head == tournament.waitlist_tail,
+            ErrorCode::TournamentFull
+        );
+
+        let player = ctx.accounts.player.key();
+        check_tournament_eligibility(
+            &tournament.eligibility,
+            &player,
+            &ctx.accounts.game_state.oracle,
+            ctx.accounts.oracle.as_ref(),
+            ctx.accounts.player_profile.as_ref(),
+            car_rarity,
+            &allowlist_proof,
+        )?;
+
+        let entry_fee = tournament.current_entry_fee(now)?;
+        require!(entry_fee <= ctx.accounts.ticket_config.price, ErrorCode::TicketValueTooLow);
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.ticket_mint.to_account_info(),
+            from: ctx.accounts.player_ticket_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), 1)?;
+
+        if entry_fee > 0 {
+            // Check if vault has sufficient balance
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, entry_fee, 0)?;
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.prize_escrow.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                entry_fee,
+                ctx.accounts.game_state.bump,
+            )?;
+        }
+
+        let entry = &mut ctx.accounts.tournament_entry;
+        entry.tournament_id = tournament.tournament_id;
+        entry.player = player;
+        entry.fee_paid = 0;
+        entry.joined_at = now;
+        entry.bump = ctx.bumps.tournament_entry;
+
+        let ticket_config = &mut ctx.accounts.ticket_config;
+        ticket_config.tickets_spent = ticket_config.tickets_spent.saturating_add(1);
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        index_position(&ctx.accounts.player_index, PositionKind::TournamentEntry, ctx.accounts.tournament_entry.key())?;
+
+        msg!("Player {} joined tournament {} with a ticket", player, tournament.tournament_id);
+        Ok(())
+    }
+
+    // Register for a $SPEEDY tournament without paying from the wallet: the vault pays the
+    // entry fee and an equal amount of the player's unclaimed vesting balance is locked as an
+    // interest-free loan, repaid automatically from the next vested claims. Like ticket entries,
+    // entries on credit are not refunded on withdrawal.
+    pub fn join_tournament_on_credit(
+        ctx: Context<JoinTournamentOnCredit>,
+        _tournament_id: u64,
+        car_rarity: CarRarity,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+        // Freed slots go to the waitlist first
+        require!(
+            tournament.registered_count < tournament.max_entrants
+                && tournament.waitlist_head == tournament.waitlist_tail,
+            ErrorCode::TournamentFull
+        );
+
+        let player = ctx.accounts.player.key();
+        check_tournament_eligibility(
+            &tournament.eligibility,
+            &player,
+            &ctx.accounts.game_state.oracle,
+            ctx.accounts.oracle.as_ref(),
+            Some(&ctx.accounts.player_profile),
+            car_rarity,
+            &allowlist_proof,
+        )?;
+
+        let entry_fee = tournament.current_entry_fee(now)?;
+        require!(entry_fee > 0, ErrorCode::InvalidRewardAmount);
+
+        // The vesting balance not already lent against has to cover the fee
+        let player_profile = &mut ctx.accounts.player_profile;
+        let fee_loan = player_profile.fee_loan.checked_add(entry_fee)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(
+            fee_loan <= player_profile.vesting.outstanding(),
+            ErrorCode::InsufficientBalance
+        );
+        player_profile.fee_loan = fee_loan;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, entry_fee, entry_fee)?;
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.prize_escrow.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            entry_fee,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        let entry = &mut ctx.accounts.tournament_entry;
+        entry.tournament_id = tournament.tournament_id;
+        entry.player = player;
+        entry.fee_paid = 0;
+        entry.joined_at = now;
+        entry.bump = ctx.bumps.tournament_entry;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_fee_loans = game_state.total_fee_loans.checked_add(entry_fee)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(EntryFeeLoaned {
+            player,
+            tournament_id: tournament.tournament_id,
+            amount: entry_fee,
+            outstanding: fee_loan,
+            timestamp: now,
+        });
+
+        index_position(&ctx.accounts.player_index, PositionKind::TournamentEntry, ctx.accounts.tournament_entry.key())?;
+
+        msg!("Player {} joined tournament {} on credit", player, tournament.tournament_id);
+        Ok(())
+    }
+
+    // Join a full tournament's waitlist, escrowing the current entry fee. Waitlisted players
+    // are promoted in order by `promote_waitlisted` and refunded if registration closes first.
+    pub fn join_tournament_waitlist(
+        ctx: Context<JoinTournamentWaitlist>,
+        _tournament_id: u64,
+        car_rarity: CarRarity,
+        allowlist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+        require!(
+            tournament.registered_count >= tournament.max_entrants
+                || tournament.waitlist_head != tournament.waitlist_tail,
+            ErrorCode::TournamentNotFull
+        );
+
+        let player = ctx.accounts.player.key();
+        check_tournament_eligibility(
+            &tournament.eligibility,
+            &player,
+            &ctx.accounts.game_state.oracle,
+            ctx.accounts.oracle.as_ref(),
+            ctx.accounts.player_profile.as_ref(),
+            car_rarity,
+            &allowlist_proof,
+        )?;
+
+        let entry_fee = tournament.current_entry_fee(now)?;
+        if entry_fee > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.player_token_account.to_account_info(),
+                to: ctx.accounts.prize_escrow.to_account_info(),
+                authority: ctx.accounts.player.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), entry_fee)?;
+        }
+
+        let waitlist_entry = &mut ctx.accounts.waitlist_entry;
+        waitlist_entry.tournament_id = tournament.tournament_id;
+        waitlist_entry.player = player;
+        waitlist_entry.position = tournament.waitlist_tail;
+        waitlist_entry.fee_paid = entry_fee;
+        waitlist_entry.joined_at = now;
+        waitlist_entry.bump = ctx.bumps.waitlist_entry;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.waitlist_tail = tournament.waitlist_tail.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        msg!("Player {} waitlisted at position {}", player, waitlist_entry.position);
+        Ok(())
+    }
+
+    // Promote the earliest waitlisted player into a free slot (permissionless crank).
+    // Their escrowed fee becomes the entry fee; the cranker fronts the entry rent and
+    // receives the waitlist entry's rent in return. A waitlisted player who is already
+    // entered is refunded and skipped.
+    pub fn promote_waitlisted(
+        ctx: Context<PromoteWaitlisted>,
+        _tournament_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+        require!(
+            tournament.registered_count < tournament.max_entrants,
+            ErrorCode::TournamentFull
+        );
+
+        pay_crank_tip(
+            &mut ctx.accounts.game_state,
+            CrankKind::WaitlistPromotion,
+            &ctx.accounts.vault,
+            ctx.accounts.tip_account.as_ref(),
+            ctx.accounts.token_program.to_account_info(),
+            now,
+        )?;
+
+        let waitlist_entry = &ctx.accounts.waitlist_entry;
+        if ctx.accounts.tournament_entry.player != Pubkey::default() {
+            transfer_from_tournament_escrow(
+                &ctx.accounts.tournament,
+                ctx.accounts.prize_escrow.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                waitlist_entry.fee_paid,
+            )?;
+
+            let tournament = &mut ctx.accounts.tournament;
+            tournament.waitlist_head = tournament.waitlist_head.checked_add(1)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+            msg!("Skipped already entered player {}", waitlist_entry.player);
+            return Ok(());
+        }
+
+        let entry = &mut ctx.accounts.tournament_entry;
+        entry.tournament_id = waitlist_entry.tournament_id;
+        entry.player = waitlist_entry.player;
+        entry.fee_paid = waitlist_entry.fee_paid;
+        entry.joined_at = now;
+        entry.bump = ctx.bumps.tournament_entry;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        tournament.waitlist_head = tournament.waitlist_head.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        index_position(&ctx.accounts.player_index, PositionKind::TournamentEntry, entry.key())?;
+
+        msg!("Promoted {} from the waitlist of tournament {}", entry.player, tournament.tournament_id);
+        Ok(())
+    }
+
+    // Refund a waitlisted player who was never promoted once registration has closed
+    pub fn refund_waitlist_entry(
+        ctx: Context<RefundWaitlistEntry>,
+        _tournament_id: u64,
+        _position: u64,
+    ) -> Result<()> {
+        require!(
+            current_timestamp(ctx.remaining_accounts)? >= ctx.accounts.tournament.registration_deadline,
+            ErrorCode::RegistrationOpen
+        );
+
+        let refund = ctx.accounts.waitlist_entry.fee_paid;
+        if refund > 0 {
+            transfer_from_tournament_escrow(
+                &ctx.accounts.tournament,
+                ctx.accounts.prize_escrow.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                refund,
+            )?;
+        }
+
+        msg!("Refunded {} $SPEEDY to waitlisted player {}", refund, ctx.accounts.player.key());
+        Ok(())
+    }
+
+    // Add sponsor tokens to a tournament's prize pool; repeat contributions from the
+    // same sponsor accumulate in its existing slot
+    pub fn sponsor_tournament(
+        ctx: Context<SponsorTournament>,
+        tournament_id: u64,
+        amount: u64,
+        name_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidRewardAmount);
+
+        let sponsor = ctx.accounts.sponsor.key();
+        let tournament = &mut ctx.accounts.tournament;
+        match tournament.sponsors.iter_mut().find(|slot| slot.sponsor == sponsor) {
+            Some(slot) => {
+                slot.name_hash = name_hash;
+                slot.amount = slot.amount.checked_add(amount)
+                    .ok_or(ErrorCode::InvalidRewardAmount)?;
+            }
+            None => {
+                require!(
+                    tournament.sponsors.len() < Tournament::MAX_SPONSORS,
+                    ErrorCode::SponsorSlotsFull
+                );
+                tournament.sponsors.push(TournamentSponsor { sponsor, name_hash, amount });
+            }
+        }
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.sponsor_token_account.to_account_info(),
+            to: ctx.accounts.prize_source.to_account_info(),
+            authority: ctx.accounts.sponsor.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), amount)?;
+
+        emit!(TournamentSponsored {
+            tournament_id,
+            sponsor,
+            name_hash,
+            amount,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Sponsor {} added {} prize tokens to tournament {}", sponsor, amount, tournament_id);
+        Ok(())
+    }
+
+    // Disqualify an entrant before the tournament starts at its registration deadline (oracle only).
+    // The entry fee is refunded minus the tournament's forfeit share, which stays in the prize
+    // escrow, and the slot is freed for another player.
+    pub fn disqualify_entrant(
+        ctx: Context<DisqualifyEntrant>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(now < tournament.registration_deadline, ErrorCode::RegistrationClosed);
+
+        let fee_paid = ctx.accounts.tournament_entry.fee_paid;
+        let forfeited = speedy_core::apply_bps(fee_paid, tournament.disqualification_forfeit_bps)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        let refund = fee_paid - forfeited;
+
+        if refund > 0 {
+            transfer_from_tournament_escrow(
+                &ctx.accounts.tournament,
+                ctx.accounts.prize_escrow.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                refund,
+            )?;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.registered_count = tournament.registered_count.saturating_sub(1);
+
+        emit!(EntrantDisqualified {
+            tournament_id,
+            player: ctx.accounts.player.key(),
+            refund,
+            forfeited,
+            timestamp: now,
+        });
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.tournament_entry.key())?;
+
+        msg!("Player {} disqualified from tournament {}", ctx.accounts.player.key(), tournament_id);
+        Ok(())
+    }
+
+    // Pay a prize from the tournament escrow to a registered entrant (oracle only). Prizes
+    // above the hold threshold go to a per-player hold escrow instead, released by
+    // `release_prize_hold` once the hold period ends.
+    pub fn pay_tournament_prize(
+        ctx: Context<PayTournamentPrize>,
+        tournament_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::TOURNAMENTS)?;
+        // Check if vault has sufficient balance
+        if ctx.accounts.prize_source.key() == ctx.accounts.game_state.vault {
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.prize_source.amount, amount, 0)?;
+        } else {
+            require!(
+                ctx.accounts.prize_source.amount >= amount,
+                ErrorCode::InsufficientVaultBalance
+            );
+        }
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(!ctx.accounts.tournament.prize_claim_expired(now), ErrorCode::PrizeClaimExpired);
+        let hold = ctx.accounts.game_state.prize_hold;
+        let destination = if hold.applies_to(amount) {
+            let (Some(prize_hold), Some(hold_escrow)) = (ctx.accounts.prize_hold.as_mut(), ctx.accounts.hold_escrow.as_ref()) else {
+                return err!(ErrorCode::PrizeHoldRequired);
+            };
+            prize_hold.tournament_id = tournament_id;
+            prize_hold.player = ctx.accounts.player.key();
+            prize_hold.amount = amount;
+            prize_hold.escrow = hold_escrow.key();
+            prize_hold.release_at = now.checked_add(hold.hold_secs).ok_or(ErrorCode::InvalidRewardAmount)?;
+            prize_hold.status = PrizeHoldStatus::Held;
+            prize_hold.bump = ctx.bumps.prize_hold.ok_or(ErrorCode::PrizeHoldRequired)?;
+
+            // The oracle paid for the hold and its escrow; a funded rent treasury covers both
+            let oracle = ctx.accounts.oracle.to_account_info();
+            front_rent(ctx.accounts.rent_treasury.as_mut(), &oracle, RentSubsystem::Escrows, space::PRIZE_HOLD)?;
+            front_rent(ctx.accounts.rent_treasury.as_mut(), &oracle, RentSubsystem::Escrows, TokenAccount::LEN)?;
+            hold_escrow.to_account_info()
+        } else {
+            ctx.accounts.player_token_account.to_account_info()
+        };
+
+        if ctx.accounts.tournament.prize_source == ctx.accounts.tournament.prize_escrow {
+            transfer_from_tournament_escrow(
+                &ctx.accounts.tournament,
+                ctx.accounts.prize_source.to_account_info(),
+                destination,
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+            )?;
+        } else {
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.prize_source.to_account_info(),
+                destination,
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+                ctx.accounts.game_state.bump,
+            )?;
+        }
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.total_prizes_paid = tournament.total_prizes_paid.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        if let Some(prize_hold) = ctx.accounts.prize_hold.as_ref().filter(|_| hold.applies_to(amount)) {
+            emit!(PrizeHeld {
+                tournament_id,
+                player: prize_hold.player,
+                amount,
+                release_at: prize_hold.release_at,
+                timestamp: now,
+            });
+            msg!("Holding {} prize tokens in tournament {} until {}", amount, tournament_id, prize_hold.release_at);
+            return Ok(());
+        }
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.player.key(),
+                amount,
+                reward_type: RewardType::Tournament,
+                race_id: tournament_id,
+                timestamp: now,
+            },
+            &ctx.accounts.prize_source.to_account_info(),
+            ctx.accounts.prize_source.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            ctx.accounts.game_state.current_era()?,
+        )?;
+
+        msg!("Paid {} prize tokens in tournament {}", amount, tournament_id);
+        Ok(())
+    }
+
+    // Pay out a held prize once its hold period has ended (permissionless)
+    pub fn release_prize_hold(
+        ctx: Context<ReleasePrizeHold>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let prize_hold = &ctx.accounts.prize_hold;
+        require!(prize_hold.status == PrizeHoldStatus::Held, ErrorCode::PrizeHoldSettled);
+        require!(now >= prize_hold.release_at, ErrorCode::PrizeHoldActive);
+
+        let amount = prize_hold.amount;
+        let vault_balance_before = ctx.accounts.hold_escrow.amount;
+        transfer_from_prize_hold(
+            prize_hold,
+            ctx.accounts.hold_escrow.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        ctx.accounts.prize_hold.status = PrizeHoldStatus::Released;
+
+        emit_token_reward(
+            TokenReward {
+                player: ctx.accounts.prize_hold.player,
+                amount,
+                reward_type: RewardType::Tournament,
+                race_id: tournament_id,
+                timestamp: now,
+            },
+            &ctx.accounts.hold_escrow.to_account_info(),
+            vault_balance_before,
+            &ctx.accounts.player_token_account.to_account_info(),
+            None,
+            ctx.accounts.game_state.current_era()?,
+        )?;
+
+        msg!("Released {} held prize tokens in tournament {}", amount, tournament_id);
+        Ok(())
+    }
+
+    // Return a held prize to the tournament's prize source after a confirmed cheating verdict
+    // (admin or moderator). Only possible while the hold period is still running.
+    pub fn clawback_prize_hold(
+        ctx: Context<ClawbackPrizeHold>,
+        tournament_id: u64,
+        verdict_hash: [u8; 32],
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let prize_hold = &ctx.accounts.prize_hold;
+        require!(prize_hold.status == PrizeHoldStatus::Held, ErrorCode::PrizeHoldSettled);
+        require!(now < prize_hold.release_at, ErrorCode::PrizeHoldExpired);
+
+        let amount = prize_hold.amount;
+        transfer_from_prize_hold(
+            prize_hold,
+            ctx.accounts.hold_escrow.to_account_info(),
+            ctx.accounts.prize_source.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+        )?;
+        ctx.accounts.prize_hold.status = PrizeHoldStatus::ClawedBack;
+
+        let tournament = &mut ctx.accounts.tournament;
+        tournament.total_prizes_paid = tournament.total_prizes_paid.saturating_sub(amount);
+
+        emit!(PrizeHoldClawedBack {
+            tournament_id,
+            player: ctx.accounts.prize_hold.player,
+            amount,
+            verdict_hash,
+            timestamp: now,
+        });
+
+        msg!("Clawed back {} held prize tokens in tournament {}", amount, tournament_id);
+        Ok(())
+    }
+
+    // Set the prize size above which tournament prizes are held, and the hold period
+    // (admin only)
+    pub fn update_prize_hold_config(
+        ctx: Context<UpdateGameState>,
+        prize_hold: PrizeHoldConfig,
+    ) -> Result<()> {
+        require!(prize_hold.hold_secs > 0, ErrorCode::InvalidPrizeHoldConfig);
+        ctx.accounts.game_state.prize_hold = prize_hold;
+
+        msg!("Prizes above {} are held for {}s", prize_hold.threshold, prize_hold.hold_secs);
+        Ok(())
+    }
+
+    // Set the payout size above which awards also need an authority co-signature; 0 disables
+    // the requirement (admin only)
+    pub fn set_cosign_threshold(
+        ctx: Context<UpdateGameState>,
+        threshold: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.cosign_threshold = threshold;
+
+        msg!("Payouts above {} now require an authority co-signature", threshold);
+        Ok(())
+    }
+
+    // Move whatever is left in a tournament's escrow once its prize claim deadline has passed
+    // (permissionless). The balance seeds the prize pool of a tournament still taking
+    // registrations when one in the same currency is passed, and goes back to the game vault
+    // otherwise. Waitlisted players must all have been refunded first.
+    pub fn rollover_unclaimed(
+        ctx: Context<RolloverUnclaimed>,
+        tournament_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let tournament = &ctx.accounts.tournament;
+        require!(
+            tournament.prize_claim_expired(now) && tournament.waitlist_head == tournament.waitlist_tail,
+            ErrorCode::PrizeClaimWindowOpen
+        );
+        let amount = ctx.accounts.prize_escrow.amount;
+        require!(amount > 0, ErrorCode::NothingToClaim);
+
+        let (destination, next_tournament_id) = match (
+            ctx.accounts.next_tournament.as_ref(),
+            ctx.accounts.next_prize_escrow.as_ref(),
+        ) {
+            (Some(next_tournament), Some(next_prize_escrow)) => {
+                require!(
+                    next_tournament.tournament_id != tournament_id
+                        && next_tournament.prize_source == next_tournament.prize_escrow
+                        && next_prize_escrow.key() == next_tournament.prize_escrow
+                        && next_prize_escrow.mint == ctx.accounts.prize_escrow.mint
+                        && now < next_tournament.registration_deadline,
+                    ErrorCode::InvalidTournamentConfig
+                );
+                (next_prize_escrow.to_account_info(), Some(next_tournament.tournament_id))
+            }
+            _ => {
+                let treasury = ctx.accounts.treasury.as_ref()
+                    .ok_or(ErrorCode::InvalidTournamentConfig)?;
+                (treasury.to_account_info(), None)
+            }
+        };
+
+        transfer_from_tournament_escrow(
+            tournament,
+            ctx.accounts.prize_escrow.to_account_info(),
+            destination,
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+
+        emit!(UnclaimedPrizesRolledOver {
+            tournament_id,
+            next_tournament_id,
+            amount,
+            timestamp: now,
+        });
+
+        msg!("Rolled over {} unclaimed prize tokens from tournament {}", amount, tournament_id);
+        Ok(())
+    }
+
+    // Move entry fees of a mixed-currency tournament into the game vault of the entry currency
+    // once registration has closed (admin or treasurer). Fees still owed to waitlisted players must be
+    // left in the escrow.
+    pub fn sweep_tournament_fees(
+        ctx: Context<SweepTournamentFees>,
+        tournament_id: u64,
+        amount: u64,
+    ) -> Result<()> {
+        let tournament = &ctx.accounts.tournament;
+        require!(tournament.prize_source != tournament.prize_escrow, ErrorCode::InvalidTournamentConfig);
+        require!(
+            current_timestamp(ctx.remaining_accounts)? >= tournament.registration_deadline,
+            ErrorCode::RegistrationOpen
+        );
+        require!(ctx.accounts.prize_escrow.amount >= amount, ErrorCode::InsufficientVaultBalance);
+
+        transfer_from_tournament_escrow(
+            tournament,
+            ctx.accounts.prize_escrow.to_account_info(),
+            ctx.accounts.treasury.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            amount,
+        )?;
+
+        msg!("Swept {} entry fee tokens from tournament {}", amount, tournament_id);
+        Ok(())
+    }
+
+    // Create a round-robin league season (admin only). Relegated players from a finalized
+    // previous season of the same league cannot be carried into the new participant set.
+    pub fn create_league(
+        ctx: Context<CreateLeague>,
+        league_id: u64,
+        participants: Vec<Pubkey>,
+        prize_by_rank: Vec<u64>,
+        promotion_count: u8,
+        relegation_count: u8,
+        metadata: Metadata,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::LEAGUES)?;
+        metadata.validate()?;
+        require!(
+            participants.len() >= 2
+                && participants.len() <= League::MAX_PARTICIPANTS
+                && prize_by_rank.len() <= participants.len()
+                && promotion_count as usize + relegation_count as usize <= participants.len(),
+            ErrorCode::InvalidLeagueConfig
+        );
+        for (index, player) in participants.iter().enumerate() {
+            require!(!participants[..index].contains(player), ErrorCode::InvalidLeagueConfig);
+        }
+
+        if let Some(previous_season) = ctx.accounts.previous_season.as_ref() {
+            require!(previous_season.finalized, ErrorCode::LeagueNotFinalized);
+            let relegated = previous_season.standings.iter()
+                .any(|standing| {
+                    standing.flag == LeagueFlag::Relegated && participants.contains(&standing.player)
+                });
+            require!(!relegated, ErrorCode::InvalidLeagueConfig);
+        }
+
+        let league = &mut ctx.accounts.league;
+        league.league_id = league_id;
+        league.standings = participants.into_iter()
+            .map(|player| LeagueStanding { player, points: 0, played: 0, flag: LeagueFlag::None })
+            .collect();
+        league.prize_by_rank = prize_by_rank;
+        league.fixtures_reported = 0;
+        league.promotion_count = promotion_count;
+        league.relegation_count = relegation_count;
+        league.finalized = false;
+        league.metadata = metadata;
+        league.bump = ctx.bumps.league;
+
+        append_to_registry(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.registry_page,
+            ctx.bumps.registry_page,
+            RegistryKind::League,
+            league.key(),
+        )?;
+
+        msg!("League {} created with {} participants", league_id, league.standings.len());
+        Ok(())
+    }
+
+    // Record a fixture result between two participants (oracle only)
+    pub fn report_league_fixture(
+        ctx: Context<ReportLeagueFixture>,
+        _league_id: u64,
+        home: u8,
+        away: u8,
+        outcome: FixtureOutcome,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::LEAGUES)?;
+        let league = &mut ctx.accounts.league;
+        require!(!league.finalized, ErrorCode::LeagueFinalized);
+
+        let participant_count = league.standings.len();
+        let (home, away) = (home as usize, away as usize);
+        require!(
+            home != away && home < participant_count && away < participant_count,
+            ErrorCode::InvalidFixture
+        );
+        let fixture_bit = 1u64 << speedy_core::fixture_index(participant_count, home, away);
+        require!(league.fixtures_reported & fixture_bit == 0, ErrorCode::InvalidFixture);
+        league.fixtures_reported |= fixture_bit;
+
+        let (home_points, away_points) = match outcome {
+            FixtureOutcome::HomeWin => (League::WIN_POINTS, 0),
+            FixtureOutcome::AwayWin => (0, League::WIN_POINTS),
+            FixtureOutcome::Draw => (League::DRAW_POINTS, League::DRAW_POINTS),
+        };
+        for (index, points) in [(home, home_points), (away, away_points)] {
+            let standing = &mut league.standings[index];
+            standing.points = standing.points.checked_add(points)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+            standing.played = standing.played.saturating_add(1);
+        }
+
+        msg!("League {} fixture {} vs {} reported", league.league_id, home, away);
+        Ok(())
+    }
+
+    // Rank the finished season, flag promotion/relegation and pay prizes from the vault.
+    // Remaining accounts are the prize winners' token accounts in final rank order.
+    pub fn finalize_league_season<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeLeagueSeason<'info>>,
+        _league_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::LEAGUES)?;
+        let league = &mut ctx.accounts.league;
+        require!(!league.finalized, ErrorCode::LeagueFinalized);
+        let participant_count = league.standings.len();
+        require!(
+            league.fixtures_reported.count_ones() as usize
+                == speedy_core::fixture_count(participant_count),
+            ErrorCode::SeasonIncomplete
+        );
+
+        // Stable sort keeps registration order as the tie-break
+        league.standings.sort_by_key(|standing| std::cmp::Reverse(standing.points));
+        let promotion_count = league.promotion_count as usize;
+        let relegation_count = league.relegation_count as usize;
+        for (rank, standing) in league.standings.iter_mut().enumerate() {
+            standing.flag = if rank < promotion_count {
+                LeagueFlag::Promoted
+            } else if rank >= participant_count - relegation_count {
+                LeagueFlag::Relegated
+            } else {
+                LeagueFlag::None
+            };
+        }
+        league.finalized = true;
+
+        let winners = ctx.remaining_accounts;
+        require!(winners.len() == league.prize_by_rank.len(), ErrorCode::InvalidPlayerTokenAccount);
+        let total_prizes = league.prize_by_rank.iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        // Check if vault has sufficient balance
+        ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, total_prizes, 0)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let league = &ctx.accounts.league;
+        for (rank, winner_info) in winners.iter().enumerate() {
+            let winner_account = Account::<TokenAccount>::try_from(winner_info)?;
+            require!(
+                winner_account.mint == ctx.accounts.game_state.token_mint
+                    && winner_account.owner == league.standings[rank].player
+                    && winner_account.delegate.is_none()
+                    && winner_account.close_authority.is_none(),
+                ErrorCode::InvalidPlayerTokenAccount
+            );
+
+            let amount = league.prize_by_rank[rank];
+            let vault_balance_before = token_balance(&ctx.accounts.vault.to_account_info())?;
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                winner_info.clone(),
+                ctx.accounts.token_program.to_account_info(),
+                amount,
+                ctx.accounts.game_state.bump,
+            )?;
+
+            emit_token_reward(
+                TokenReward {
+                    player: winner_account.owner,
+                    amount,
+                    reward_type: RewardType::LeaguePrize,
+                    race_id: league.league_id,
+                    timestamp: now,
+                },
+                &ctx.accounts.vault.to_account_info(),
+                vault_balance_before,
+                winner_info,
+                ctx.accounts.memo_program.as_ref(),
+                ctx.accounts.game_state.current_era()?,
+            )?;
+        }
+
+        let league_id = league.league_id;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(total_prizes)?;
+
+        msg!("League {} season finalized, {} $SPEEDY in prizes", league_id, total_prizes);
+        Ok(())
+    }
+
+    // Create the jackpot PDA and its token account and start routing a share of spends to it (admin only)
+    pub fn initialize_jackpot(
+        ctx: Context<InitializeJackpot>,
+        burn_share_bps: u16,
+        draw_interval_secs: i64,
+        seed_commitment: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::JACKPOT)?;
+        require!(
+            burn_share_bps <= BPS_DENOMINATOR && draw_interval_secs > 0,
+            ErrorCode::InvalidJackpotConfig
+        );
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.current_draw_id = 0;
+        jackpot.entry_count = 0;
+        jackpot.last_draw_at = current_timestamp(ctx.remaining_accounts)?;
+        jackpot.reserved_prizes = 0;
+        jackpot.seed_commitment = seed_commitment;
+        jackpot.bump = ctx.bumps.jackpot;
+
+        ctx.accounts.game_state.jackpot = JackpotConfig {
+            burn_share_bps,
+            draw_interval_secs,
+            vault: ctx.accounts.jackpot_vault.key(),
+        };
+
+        msg!("Jackpot initialized with {} bps of spends", burn_share_bps);
+        Ok(())
+    }
+
+    // Enter the current jackpot draw; only players who completed a race this week are eligible
+    pub fn enter_jackpot_draw(
+        ctx: Context<EnterJackpotDraw>,
+        draw_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::JACKPOT)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(
+            ctx.accounts.player_profile.last_race_week == speedy_core::week_index(now),
+            ErrorCode::NotEligible
+        );
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        require!(draw_id == jackpot.current_draw_id, ErrorCode::InvalidJackpotDraw);
+
+        let entry = &mut ctx.accounts.jackpot_entry;
+        entry.draw_id = draw_id;
+        entry.player = ctx.accounts.player.key();
+        entry.index = jackpot.entry_count;
+        entry.bump = ctx.bumps.jackpot_entry;
+
+        jackpot.entry_count = jackpot.entry_count.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        index_position(&ctx.accounts.player_index, PositionKind::JackpotEntry, entry.key())?;
+
+        msg!("Player {} entered jackpot draw {}", entry.player, draw_id);
+        Ok(())
+    }
+
+    // Draw the current jackpot (oracle only) by revealing the seed committed before the draw
+    // opened, so the oracle cannot pick a seed after seeing the entries. The commitment for the
+    // next draw is posted in the same instruction. The winning entry index and prize snapshot
+    // are recorded in the draw history.
+    pub fn draw_jackpot(
+        ctx: Context<DrawJackpot>,
+        draw_id: u64,
+        seed: [u8; 32],
+        next_seed_commitment: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::JACKPOT)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let jackpot = &mut ctx.accounts.jackpot;
+        require!(draw_id == jackpot.current_draw_id, ErrorCode::InvalidJackpotDraw);
+        let next_draw_at = jackpot.last_draw_at
+            .checked_add(ctx.accounts.game_state.jackpot.draw_interval_secs)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(now >= next_draw_at, ErrorCode::JackpotDrawTooEarly);
+        require!(hash(&seed).to_bytes() == jackpot.seed_commitment, ErrorCode::InvalidJackpotSeed);
+
+        let randomness = hashv(&[&seed, &draw_id.to_le_bytes()]).to_bytes();
+        let winning_index = speedy_core::draw_winner_index(&randomness, jackpot.entry_count)
+            .ok_or(ErrorCode::NoJackpotEntries)?;
+
+        let draw = &mut ctx.accounts.jackpot_draw;
+        draw.draw_id = draw_id;
+        draw.randomness = randomness;
+        draw.entry_count = jackpot.entry_count;
+        draw.winning_index = winning_index;
+        // Earlier unclaimed prizes stay reserved for their winners
+        draw.prize = ctx.accounts.jackpot_vault.amount.saturating_sub(jackpot.reserved_prizes);
+        draw.winner = Pubkey::default();
+        draw.drawn_at = now;
+        draw.claimed = false;
+        draw.bump = ctx.bumps.jackpot_draw;
+
+        jackpot.current_draw_id = draw_id.checked_add(1).ok_or(ErrorCode::InvalidRewardAmount)?;
+        jackpot.entry_count = 0;
+        jackpot.last_draw_at = now;
+        jackpot.seed_commitment = next_seed_commitment;
+        jackpot.reserved_prizes = jackpot.reserved_prizes.checked_add(draw.prize)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(JackpotDrawn {
+            draw_id,
+            entry_count: draw.entry_count,
+            winning_index,
+            prize: draw.prize,
+            timestamp: now,
+        });
+
+        msg!("Jackpot draw {} picked entry {} of {}", draw_id, winning_index, draw.entry_count);
+        Ok(())
+    }
+
+    // Claim a drawn jackpot with the winning entry
+    pub fn claim_jackpot(
+        ctx: Context<ClaimJackpot>,
+        draw_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::JACKPOT)?;
+        let draw = &ctx.accounts.jackpot_draw;
+        require!(!draw.claimed, ErrorCode::JackpotAlreadyClaimed);
+        require!(
+            ctx.accounts.jackpot_entry.index == draw.winning_index,
+            ErrorCode::NotJackpotWinner
+        );
+        let prize = draw.prize;
+
+        let jackpot_seeds = &[b"jackpot".as_ref(), &[ctx.accounts.jackpot.bump]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.jackpot_vault.to_account_info(),
+            to: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.jackpot.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(
+            CpiContext::new_with_signer(cpi_program, cpi_accounts, &[&jackpot_seeds[..]]),
+            prize,
+        )?;
+
+        let jackpot = &mut ctx.accounts.jackpot;
+        jackpot.reserved_prizes = jackpot.reserved_prizes.saturating_sub(prize);
+
+        let draw = &mut ctx.accounts.jackpot_draw;
+        draw.winner = ctx.accounts.player.key();
+        draw.claimed = true;
+
+        emit_token_reward(
+            TokenReward {
+                player: draw.winner,
+                amount: prize,
+                reward_type: RewardType::Jackpot,
+                race_id: draw_id,
+                timestamp: current_timestamp(ctx.remaining_accounts)?,
+            },
+            &ctx.accounts.jackpot_vault.to_account_info(),
+            ctx.accounts.jackpot_vault.amount,
+            &ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            ctx.accounts.game_state.current_era()?,
+        )?;
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.jackpot_entry.key())?;
+
+        msg!("Player {} claimed {} $SPEEDY jackpot from draw {}", draw.winner, prize, draw_id);
+        Ok(())
+    }
+
+    // Enter the ranked queue by staking the configured refundable amount into the vault
+    pub fn enter_ranked_queue(
+        ctx: Context<EnterRankedQueue>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::RANKED_QUEUE)?;
+        let stake_amount = ctx.accounts.game_state.ranked_queue.stake_amount;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), stake_amount)?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_staked = game_state.total_staked.checked_add(stake_amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let queue_ticket = &mut ctx.accounts.queue_ticket;
+        queue_ticket.player = ctx.accounts.player.key();
+        queue_ticket.stake_amount = stake_amount;
+        queue_ticket.entered_at = current_timestamp(ctx.remaining_accounts)?;
+        queue_ticket.bump = ctx.bumps.queue_ticket;
+
+        index_position(&ctx.accounts.player_index, PositionKind::QueueTicket, queue_ticket.key())?;
+
+        msg!("Player {} entered ranked queue with {} $SPEEDY stake", queue_ticket.player, stake_amount);
+        Ok(())
+    }
+
+    // Resolve a queue ticket after a match (oracle only): rage-quits forfeit the stake to the
+    // vault, everything else is refunded
+    pub fn resolve_queue_ticket(
+        ctx: Context<ResolveQueueTicket>,
+        rage_quit: bool,
+    ) -> Result<()> {
+        let stake_amount = ctx.accounts.queue_ticket.stake_amount;
+
+        if !rage_quit {
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                stake_amount,
+                ctx.accounts.game_state.bump,
+            )?;
+        }
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_staked = game_state.total_staked.saturating_sub(stake_amount);
+
+        emit!(QueueTicketResolved {
+            player: ctx.accounts.queue_ticket.player,
+            stake_amount,
+            forfeited: rage_quit,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.queue_ticket.key())?;
+
+        msg!("Queue ticket resolved, stake {}", if rage_quit { "forfeited" } else { "refunded" });
+        Ok(())
+    }
+
+    // Refund a queue ticket nobody reported on within the timeout (permissionless)
+    pub fn refund_expired_queue_ticket(
+        ctx: Context<RefundExpiredQueueTicket>,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let queue_ticket = &ctx.accounts.queue_ticket;
+        require!(
+            now >= queue_ticket.entered_at.saturating_add(ctx.accounts.game_state.ranked_queue.ticket_timeout_secs),
+            ErrorCode::QueueTicketNotExpired
+        );
+        let stake_amount = queue_ticket.stake_amount;
+
+        // Return the stake first so the tip can only come out of what the vault does not owe
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            stake_amount,
+            ctx.accounts.game_state.bump,
+        )?;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_staked = game_state.total_staked.saturating_sub(stake_amount);
+        ctx.accounts.vault.reload()?;
+
+        pay_crank_tip(
+            &mut ctx.accounts.game_state,
+            CrankKind::QueueTicketRefund,
+            &ctx.accounts.vault,
+            ctx.accounts.tip_account.as_ref(),
+            ctx.accounts.token_program.to_account_info(),
+            now,
+        )?;
+
+        emit!(QueueTicketResolved {
+            player: queue_ticket.player,
+            stake_amount,
+            forfeited: false,
+            timestamp: now,
+        });
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.queue_ticket.key())?;
+
+        msg!("Expired queue ticket refunded {} $SPEEDY", stake_amount);
+        Ok(())
+    }
+
+    // Configure the ranked queue stake and refund timeout (admin only)
+    pub fn update_ranked_queue_config(
+        ctx: Context<UpdateGameState>,
+        ranked_queue: RankedQueueConfig,
+    ) -> Result<()> {
+        require!(ranked_queue.ticket_timeout_secs > 0, ErrorCode::InvalidRankedQueueConfig);
+
+        ctx.accounts.game_state.ranked_queue = ranked_queue;
+        msg!("Ranked queue stake set to {}", ranked_queue.stake_amount);
+        Ok(())
+    }
+
+    // Record an oracle-reported result and update the player's rating.
+    // `score_bps` is 0 for a loss, 5000 for a draw and 10000 for a win.
+    pub fn report_match_result(
+        ctx: Context<ReportMatchResult>,
+        opponent_rating: u32,
+        score_bps: u16,
+    ) -> Result<()> {
+        require!(score_bps <= BPS_DENOMINATOR, ErrorCode::InvalidMatchResult);
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let player_profile = &mut ctx.accounts.player_profile;
+        init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now);
+
+        let old_rating = player_profile.rating;
+        player_profile.rating = speedy_core::update_rating(old_rating, opponent_rating, score_bps);
+
+        emit!(RatingUpdated {
+            player: player_profile.player,
+            old_rating,
+            new_rating: player_profile.rating,
+            timestamp: now,
+        });
+
+        msg!("Rating for {} updated {} -> {}", player_profile.player, old_rating, player_profile.rating);
+        Ok(())
+    }
+
+    // Configure rating-based reward handicaps (admin only)
+    pub fn update_handicap_config(
+        ctx: Context<UpdateGameState>,
+        handicap: HandicapConfig,
+    ) -> Result<()> {
+        require!(
+            handicap.low_rating_threshold <= handicap.high_rating_threshold,
+            ErrorCode::InvalidHandicapConfig
+        );
+
+        ctx.accounts.game_state.handicap = handicap;
+        msg!("Handicap config updated");
+        Ok(())
+    }
+
+    // Update drift/stunt mode scoring rates (admin or rate manager)
+    pub fn update_drift_rates(
+        ctx: Context<UpdateRates>,
+        drift_rates: DriftRates,
+    ) -> Result<()> {
+        ctx.accounts.game_state.drift_rates = drift_rates;
+        msg!("Drift rates updated: {} per 1000 points, capped at {}", drift_rates.per_1000_points, drift_rates.max_reward);
+        Ok(())
+    }
+
+    // Update elimination royale rates (admin or rate manager)
+    pub fn update_elimination_rates(
+        ctx: Context<UpdateRates>,
+        elimination_rates: EliminationRates,
+    ) -> Result<()> {
+        ctx.accounts.game_state.elimination_rates = elimination_rates;
+        msg!("Elimination rates updated");
+        Ok(())
+    }
+
+    // Update the supply-linked burn curve for spends (admin or rate manager)
+    pub fn update_burn_curve(
+        ctx: Context<UpdateRates>,
+        burn_curve: BurnCurve,
+    ) -> Result<()> {
+        require!(
+            burn_curve.floor_bps <= burn_curve.ceiling_bps
+                && burn_curve.ceiling_bps <= BPS_DENOMINATOR
+                && burn_curve.low_supply <= burn_curve.high_supply,
+            ErrorCode::InvalidBurnCurve
+        );
+        ctx.accounts.game_state.burn_curve = burn_curve;
+        msg!(
+            "Burn curve updated: {} bps at {} circulating to {} bps at {}",
+            burn_curve.floor_bps,
+            burn_curve.low_supply,
+            burn_curve.ceiling_bps,
+            burn_curve.high_supply
+        );
+        Ok(())
+    }
+
+    // Set the delay between proposing and executing a clawback (admin only)
+    pub fn set_clawback_timelock(
+        ctx: Context<UpdateGameState>,
+        timelock_secs: i64,
+    ) -> Result<()> {
+        require!(timelock_secs >= 0, ErrorCode::InvalidClawback);
+
+        ctx.accounts.game_state.clawback_timelock_secs = timelock_secs;
+        msg!("Clawback timelock set to {}s", timelock_secs);
+        Ok(())
+    }
+
+    // Propose clawing back an erroneous payout (admin or moderator). It can be executed once the
+    // timelock has passed, giving the player and observers time to dispute it.
+    pub fn propose_clawback(
+        ctx: Context<ProposeClawback>,
+        player: Pubkey,
+        amount: u64,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidClawback);
+
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let clawback = &mut ctx.accounts.clawback;
+        clawback.player = player;
+        clawback.amount = amount;
+        clawback.reason_hash = reason_hash;
+        clawback.proposed_at = now;
+        clawback.executable_at = now.checked_add(ctx.accounts.game_state.clawback_timelock_secs)
+            .ok_or(ErrorCode::InvalidClawback)?;
+        clawback.bump = ctx.bumps.clawback;
+
+        emit!(ClawbackProposed {
+            player,
+            amount,
+            reason_hash,
+            executable_at: clawback.executable_at,
+            timestamp: now,
+        });
+
+        msg!("Clawback of {} $SPEEDY proposed for {}", amount, player);
+        Ok(())
+    }
+
+    // Execute a proposed clawback after its timelock (admin or moderator). Withheld rewards are
+    // reduced first; anything left becomes debt offset against the player's future awards.
+    pub fn execute_clawback(
+        ctx: Context<ExecuteClawback>,
+    ) -> Result<()> {
+        let clawback = &ctx.accounts.clawback;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(now >= clawback.executable_at, ErrorCode::ClawbackTimelocked);
+
+        let player_profile = &mut ctx.accounts.player_profile;
+        let from_withheld = clawback.amount.min(player_profile.withheld_rewards);
+        player_profile.withheld_rewards -= from_withheld;
+        let added_debt = clawback.amount - from_withheld;
+        player_profile.balance_adjustment = i64::try_from(added_debt).ok()
+            .and_then(|debt| player_profile.balance_adjustment.checked_sub(debt))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(ClawbackExecuted {
+            player: clawback.player,
+            amount: clawback.amount,
+            from_withheld,
+            added_debt,
+            reason_hash: clawback.reason_hash,
+            timestamp: now,
+        });
+
+        msg!("Clawback executed: {} from withheld, {} as debt", from_withheld, added_debt);
+        Ok(())
+    }
+
+    // Credit a player for a refund or accounting correction (admin or moderator); the credit is paid
+    // on top of their next award. Negative adjustments go through the clawback timelock.
+    pub fn credit_balance_adjustment(
+        ctx: Context<CreditBalanceAdjustment>,
+        _player: Pubkey,
+        amount: u64,
+        reason_hash: [u8; 32],
+    ) -> Result<()> {
+        let delta = i64::try_from(amount).map_err(|_| ErrorCode::InvalidRewardAmount)?;
+        require!(delta > 0, ErrorCode::InvalidRewardAmount);
+
+        let player_profile = &mut ctx.accounts.player_profile;
+        player_profile.balance_adjustment = player_profile.balance_adjustment.checked_add(delta)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        emit!(BalanceAdjusted {
+            player: player_profile.player,
+            delta,
+            balance_adjustment: player_profile.balance_adjustment,
+            reason_hash,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Credited {} $SPEEDY to {}", amount, player_profile.player);
+        Ok(())
+    }
+
+    // Drop a proposed clawback before execution (admin or moderator)
+    pub fn cancel_clawback(
+        ctx: Context<CancelClawback>,
+    ) -> Result<()> {
+        msg!("Clawback for {} cancelled", ctx.accounts.clawback.player);
+        Ok(())
+    }
+
+    // Create the soulbound VIP role mint (Token-2022 with the NonTransferable extension) and
+    // set the claim criteria (admin only)
+    pub fn initialize_vip_role(
+        ctx: Context<InitializeVipRole>,
+        min_lifetime_earnings: u64,
+        min_token_balance: u64,
+    ) -> Result<()> {
+        let mint_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(
+            &[ExtensionType::NonTransferable],
+        )?;
+        let mint_seeds = &[b"vip_role_mint".as_ref(), &[ctx.bumps.vip_role_mint]];
+        create_pda_account(
+            &ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.vip_role_mint.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            mint_len,
+            &token_2022::ID,
+            &[&mint_seeds[..]],
+        )?;
+
+        // Extensions must be initialized before the mint itself
+        non_transferable_mint_initialize(CpiContext::new(
+            ctx.accounts.token_2022_program.to_account_info(),
+            NonTransferableMintInitialize {
+                token_program_id: ctx.accounts.token_2022_program.to_account_info(),
+                mint: ctx.accounts.vip_role_mint.to_account_info(),
+            },
+        ))?;
+        token_2022::initialize_mint2(
+            CpiContext::new(
+                ctx.accounts.token_2022_program.to_account_info(),
+                token_2022::InitializeMint2 {
+                    mint: ctx.accounts.vip_role_mint.to_account_info(),
+                },
+            ),
+            0,
+            &ctx.accounts.game_state.key(),
+            None,
+        )?;
+
+        ctx.accounts.game_state.vip_role = VipRoleConfig {
+            mint: ctx.accounts.vip_role_mint.key(),
+            min_lifetime_earnings,
+            min_token_balance,
+        };
+
+        msg!("VIP role mint created at {}", ctx.accounts.vip_role_mint.key());
+        Ok(())
+    }
+
+    // Update the VIP role claim criteria (admin only)
+    pub fn update_vip_role_criteria(
+        ctx: Context<UpdateGameState>,
+        min_lifetime_earnings: u64,
+        min_token_balance: u64,
+    ) -> Result<()> {
+        let vip_role = &mut ctx.accounts.game_state.vip_role;
+        vip_role.min_lifetime_earnings = min_lifetime_earnings;
+        vip_role.min_token_balance = min_token_balance;
+        msg!("VIP role criteria updated");
+        Ok(())
+    }
+
+    // Mint one soulbound VIP role token to a player meeting the on-chain criteria
+    pub fn claim_vip_role(
+        ctx: Context<ClaimVipRole>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::VIP_ROLE)?;
+        let vip_role = &ctx.accounts.game_state.vip_role;
+        require!(
+            ctx.accounts.player_profile.lifetime_earnings >= vip_role.min_lifetime_earnings
+                && ctx.accounts.player_token_account.amount >= vip_role.min_token_balance,
+            ErrorCode::NotEligible
+        );
+        require!(ctx.accounts.player_role_account.amount == 0, ErrorCode::VipRoleAlreadyClaimed);
+
+        let authority_seeds = &[b"game_state".as_ref(), &[ctx.accounts.game_state.bump]];
+        token_2022::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_2022_program.to_account_info(),
+                token_2022::MintTo {
+                    mint: ctx.accounts.vip_role_mint.to_account_info(),
+                    to: ctx.accounts.player_role_account.to_account_info(),
+                    authority: ctx.accounts.game_state.to_account_info(),
+                },
+                &[&authority_seeds[..]],
+            ),
+            1,
+        )?;
+
+        msg!("VIP role issued to {}", ctx.accounts.player.key());
+        Ok(())
+    }
+
+    // Announce a car drop that players can pre-order at a fixed $SPEEDY price (admin only).
+    // Each delivered car is one unit of the drop's 0-decimal mint, redeemed in game.
+    pub fn create_car_drop(
+        ctx: Context<CreateCarDrop>,
+        drop_id: u64,
+        price: u64,
+        supply: u32,
+        drop_time: i64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::CAR_DROPS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(price > 0 && supply > 0 && drop_time > now, ErrorCode::InvalidCarDrop);
+
+        let car_drop = &mut ctx.accounts.car_drop;
+        car_drop.drop_id = drop_id;
+        car_drop.price = price;
+        car_drop.supply = supply;
+        car_drop.preordered = 0;
+        car_drop.drop_time = drop_time;
+        car_drop.cancelled = false;
+        car_drop.car_mint = ctx.accounts.car_mint.key();
+        car_drop.escrow = ctx.accounts.escrow.key();
+        car_drop.bump = ctx.bumps.car_drop;
+
+        msg!("Car drop {} opened: {} cars at {} $SPEEDY", drop_id, supply, price);
+        Ok(())
+    }
+
+    // Lock $SPEEDY in the drop's escrow for `quantity` cars (before the drop time)
+    pub fn preorder_car(
+        ctx: Context<PreorderCar>,
+        drop_id: u64,
+        quantity: u32,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::CAR_DROPS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let car_drop = &mut ctx.accounts.car_drop;
+        require!(!car_drop.cancelled && now < car_drop.drop_time, ErrorCode::CarDropClosed);
+        require!(quantity > 0, ErrorCode::InvalidCarDrop);
+        car_drop.preordered = car_drop.preordered.checked_add(quantity)
+            .filter(|preordered| *preordered <= car_drop.supply)
+            .ok_or(ErrorCode::CarDropSoldOut)?;
+        let cost = car_drop.price.checked_mul(quantity as u64)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.escrow.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        transfer(CpiContext::new(cpi_program, cpi_accounts), cost)?;
+
+        let preorder = &mut ctx.accounts.preorder;
+        preorder.player = ctx.accounts.player.key();
+        preorder.drop_id = drop_id;
+        preorder.quantity = preorder.quantity.checked_add(quantity)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        preorder.amount_locked = preorder.amount_locked.checked_add(cost)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        preorder.bump = ctx.bumps.preorder;
+
+        index_position(&ctx.accounts.player_index, PositionKind::Preorder, ctx.accounts.preorder.key())?;
+
+        msg!("Pre-ordered {} cars from drop {} for {} $SPEEDY", quantity, drop_id, cost);
+        Ok(())
+    }
+
+    // Mint a pre-order's cars to the player once the drop time has passed and move the locked
+    // $SPEEDY into the game vault (permissionless)
+    pub fn deliver_preorder(
+        ctx: Context<DeliverPreorder>,
+        drop_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let car_drop = &ctx.accounts.car_drop;
+        require!(!car_drop.cancelled, ErrorCode::CarDropClosed);
+        require!(now >= car_drop.drop_time, ErrorCode::CarDropNotReady);
+
+        let preorder = &ctx.accounts.preorder;
+        let drop_id_bytes = drop_id.to_le_bytes();
+        let drop_seeds = &[b"car_drop".as_ref(), drop_id_bytes.as_ref(), &[car_drop.bump]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.car_mint.to_account_info(),
+                    to: ctx.accounts.player_car_account.to_account_info(),
+                    authority: car_drop.to_account_info(),
+                },
+                &[&drop_seeds[..]],
+            ),
+            preorder.quantity as u64,
+        )?;
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                    authority: car_drop.to_account_info(),
+                },
+                &[&drop_seeds[..]],
+            ),
+            preorder.amount_locked,
+        )?;
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.preorder.key())?;
+
+        msg!("Delivered {} cars from drop {} to {}", preorder.quantity, drop_id, preorder.player);
+        Ok(())
+    }
+
+    // Cancel a car drop; every pre-order becomes refundable (admin only)
+    pub fn cancel_car_drop(
+        ctx: Context<CancelCarDrop>,
+        drop_id: u64,
+    ) -> Result<()> {
+        ctx.accounts.car_drop.cancelled = true;
+
+        msg!("Car drop {} cancelled", drop_id);
+        Ok(())
+    }
+
+    // Return a cancelled drop's locked $SPEEDY to the player (permissionless)
+    pub fn refund_preorder(
+        ctx: Context<RefundPreorder>,
+        drop_id: u64,
+    ) -> Result<()> {
+        let car_drop = &ctx.accounts.car_drop;
+        require!(car_drop.cancelled, ErrorCode::CarDropNotCancelled);
+
+        let drop_id_bytes = drop_id.to_le_bytes();
+        let drop_seeds = &[b"car_drop".as_ref(), drop_id_bytes.as_ref(), &[car_drop.bump]];
+        let amount = ctx.accounts.preorder.amount_locked;
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.escrow.to_account_info(),
+                    to: ctx.accounts.player_token_account.to_account_info(),
+                    authority: car_drop.to_account_info(),
+                },
+                &[&drop_seeds[..]],
+            ),
+            amount,
+        )?;
+
+        unindex_position(&ctx.accounts.player_index, &ctx.accounts.preorder.key())?;
+
+        msg!("Refunded {} $SPEEDY from drop {} to {}", amount, drop_id, ctx.accounts.preorder.player);
+        Ok(())
+    }
+
+    // Open a declining-price primary sale of car mints paid in $SPEEDY (admin only)
+    pub fn create_dutch_auction(
+        ctx: Context<CreateDutchAuction>,
+        auction_id: u64,
+        config: DutchAuctionConfig,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::AUCTIONS)?;
+        require!(
+            config.floor_price > 0
+                && config.start_price >= config.floor_price
+                && config.supply > 0
+                && config.per_wallet_cap > 0,
+            ErrorCode::InvalidAuctionConfig
+        );
+
+        let auction = &mut ctx.accounts.auction;
+        auction.auction_id = auction_id;
+        auction.config = config;
+        auction.sold = 0;
+        auction.closed = false;
+        auction.car_mint = ctx.accounts.car_mint.key();
+        auction.bump = ctx.bumps.auction;
+
+        msg!("Dutch auction {} opened for {} cars", auction_id, config.supply);
+        Ok(())
+    }
+
+    // Buy cars at the current auction price, paid into the game vault. `max_price` caps the
+    // per-car price the buyer accepts; the auction closes itself once supply runs out.
+    pub fn buy_from_auction(
+        ctx: Context<BuyFromAuction>,
+        auction_id: u64,
+        quantity: u32,
+        max_price: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::AUCTIONS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let auction = &mut ctx.accounts.auction;
+        require!(!auction.closed && now >= auction.config.start_time, ErrorCode::AuctionNotOpen);
+        require!(quantity > 0, ErrorCode::InvalidAuctionConfig);
+
+        let price = auction.price_at(now);
+        require!(price <= max_price, ErrorCode::AuctionPriceAboveMax);
+        auction.sold = auction.sold.checked_add(quantity)
+            .filter(|sold| *sold <= auction.config.supply)
+            .ok_or(ErrorCode::AuctionSoldOut)?;
+        let purchase = &mut ctx.accounts.purchase;
+        purchase.quantity = purchase.quantity.checked_add(quantity)
+            .filter(|bought| *bought <= auction.config.per_wallet_cap)
+            .ok_or(ErrorCode::AuctionWalletCapReached)?;
+        purchase.player = ctx.accounts.player.key();
+        purchase.auction_id = auction_id;
+        purchase.bump = ctx.bumps.purchase;
+
+        let cost = price.checked_mul(quantity as u64).ok_or(ErrorCode::InvalidRewardAmount)?;
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), cost)?;
+
+        let auction_id_bytes = auction_id.to_le_bytes();
+        let auction_seeds = &[b"dutch_auction".as_ref(), auction_id_bytes.as_ref(), &[auction.bump]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.car_mint.to_account_info(),
+                    to: ctx.accounts.player_car_account.to_account_info(),
+                    authority: auction.to_account_info(),
+                },
+                &[&auction_seeds[..]],
+            ),
+            quantity as u64,
+        )?;
+
+        if auction.sold == auction.config.supply {
+            auction.closed = true;
+            emit!(DutchAuctionClosed {
+                auction_id,
+                sold: auction.sold,
+                final_price: price,
+                timestamp: now,
+            });
+        }
+
+        msg!("Bought {} cars from auction {} at {} $SPEEDY each", quantity, auction_id, price);
+        Ok(())
+    }
+
+    // List a physical merch item redeemable by burning $SPEEDY (admin only)
+    pub fn create_redemption_offer(
+        ctx: Context<CreateRedemptionOffer>,
+        item_id: u64,
+        cost: u64,
+        supply: u32,
+        deadline: i64,
+        fulfiller: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::REDEMPTIONS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(cost > 0 && supply > 0 && deadline > now, ErrorCode::InvalidRedemptionOffer);
+
+        let offer = &mut ctx.accounts.offer;
+        offer.item_id = item_id;
+        offer.cost = cost;
+        offer.supply = supply;
+        offer.redeemed = 0;
+        offer.deadline = deadline;
+        offer.fulfiller = fulfiller;
+        offer.bump = ctx.bumps.offer;
+
+        msg!("Redemption offer {} listed: {} items at {} $SPEEDY", item_id, supply, cost);
+        Ok(())
+    }
+
+    // Burn the offer's cost and record a redemption receipt for fulfillment. Shipping details
+    // stay off-chain; only their hash is committed.
+    pub fn redeem_offer(
+        ctx: Context<RedeemOffer>,
+        item_id: u64,
+        shipping_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::REDEMPTIONS)?;
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let offer = &mut ctx.accounts.offer;
+        require!(now <= offer.deadline, ErrorCode::RedemptionClosed);
+        require!(offer.redeemed < offer.supply, ErrorCode::RedemptionSoldOut);
+        let index = offer.redeemed;
+        offer.redeemed += 1;
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.player_token_account.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        burn(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), offer.cost)?;
+
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_burned = game_state.total_burned.checked_add(offer.cost)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let receipt = &mut ctx.accounts.receipt;
+        receipt.player = ctx.accounts.player.key();
+        receipt.item_id = item_id;
+        receipt.index = index;
+        receipt.shipping_hash = shipping_hash;
+        receipt.redeemed_at = now;
+        receipt.shipped = false;
+        receipt.tracking_hash = [0; 32];
+        receipt.bump = ctx.bumps.receipt;
+
+        msg!("Redeemed item {} (#{}) for {} $SPEEDY", item_id, index, offer.cost);
+        Ok(())
+    }
+
+    // Mark a redemption as shipped (offer fulfiller only)
+    pub fn mark_redemption_shipped(
+        ctx: Context<MarkRedemptionShipped>,
+        _item_id: u64,
+        _index: u32,
+        tracking_hash: [u8; 32],
+    ) -> Result<()> {
+        let receipt = &mut ctx.accounts.receipt;
+        require!(!receipt.shipped, ErrorCode::RedemptionAlreadyShipped);
+        receipt.shipped = true;
+        receipt.tracking_hash = tracking_hash;
+
+        msg!("Redemption {} #{} shipped", receipt.item_id, receipt.index);
+        Ok(())
+    }
+
+    // Snapshot the emission totals of a finished week into an immutable report (permissionless)
+    pub fn finalize_week(
+        ctx: Context<FinalizeWeek>,
+        week: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(
+            week.checked_add(1) == Some(speedy_core::week_index(now)),
+            ErrorCode::WeekNotOver
+        );
+
+        let game_state = &ctx.accounts.game_state;
+        let report = &mut ctx.accounts.weekly_report;
+        report.week = week;
+        report.total_distributed = game_state.total_distributed;
+        report.total_burned = game_state.total_burned;
+        report.total_staked = game_state.total_staked;
+        report.vault_balance = ctx.accounts.vault.amount;
+        report.token_supply = ctx.accounts.token_mint.supply;
+        report.finalized_at = now;
+        report.bump = ctx.bumps.weekly_report;
+
+        pay_crank_tip(
+            &mut ctx.accounts.game_state,
+            CrankKind::WeeklyReport,
+            &ctx.accounts.vault,
+            ctx.accounts.tip_account.as_ref(),
+            ctx.accounts.token_program.to_account_info(),
+            now,
+        )?;
+
+        emit!(WeeklyReportFinalized {
+            week,
+            total_distributed: report.total_distributed,
+            total_burned: report.total_burned,
+            total_staked: report.total_staked,
+            vault_balance: report.vault_balance,
+            token_supply: report.token_supply,
+            timestamp: now,
+        });
+
+        msg!("Week {} finalized: {} distributed, {} burned", week, report.total_distributed, report.total_burned);
+        Ok(())
+    }
+
+    // Pin the time reported by `current_timestamp` (admin only). Production builds reject it.
+    pub fn set_test_clock(
+        ctx: Context<SetTestClock>,
+        unix_timestamp: i64,
+    ) -> Result<()> {
+        require!(cfg!(feature = "test-clock"), ErrorCode::TestClockDisabled);
+
+        let test_clock = &mut ctx.accounts.test_clock;
+        test_clock.unix_timestamp = unix_timestamp;
+        test_clock.bump = ctx.bumps.test_clock;
+
+        msg!("Test clock set to {}", unix_timestamp);
+        Ok(())
+    }
+
+    // Set how much account rent the fee pool may cover per player (admin only)
+    pub fn set_fee_sponsor_cap(
+        ctx: Context<UpdateGameState>,
+        cap_lamports: u64,
+    ) -> Result<()> {
+        ctx.accounts.game_state.fee_sponsor_cap_lamports = cap_lamports;
+
+        msg!("Fee sponsorship cap set to {} lamports per player", cap_lamports);
+        Ok(())
+    }
+
+    // Turn a subsystem on or off (admin only)
+    pub fn set_feature(
+        ctx: Context<UpdateGameState>,
+        feature: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        require!(
+            feature.count_ones() == 1 && feature & FeatureGate::ALL == feature,
+            ErrorCode::InvalidFeature
+        );
+        let features = &mut ctx.accounts.game_state.features;
+        if enabled {
+            features.0 |= feature;
+        } else {
+            features.0 &= !feature;
+        }
+
+        emit!(FeatureToggled {
+            feature,
+            enabled,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Feature {:#x} {}", feature, if enabled { "enabled" } else { "disabled" });
+        Ok(())
+    }
+
+    // Configure the tip paid to permissionless crank callers (admin only)
+    pub fn update_crank_tip(
+        ctx: Context<UpdateGameState>,
+        crank_tip: CrankTipConfig,
+    ) -> Result<()> {
+        require!(crank_tip.cooldown_secs >= 0, ErrorCode::InvalidRewardAmount);
+        ctx.accounts.game_state.crank_tip = crank_tip;
+
+        msg!("Crank tip set to {} $SPEEDY every {} seconds", crank_tip.amount, crank_tip.cooldown_secs);
+        Ok(())
+    }
+
+    // Top up the lamport pool that pays account rent for new players
+    pub fn fund_fee_pool(
+        ctx: Context<FundFeePool>,
+        amount: u64,
+    ) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.fee_pool.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Fee pool funded with {} lamports", amount);
+        Ok(())
+    }
+
+    // Create a player's $SPEEDY token account and profile with the fee pool paying the rent,
+    // up to the per-player cap (oracle only), so new players can receive rewards without SOL
+    pub fn sponsor_player_accounts(
+        ctx: Context<SponsorPlayerAccounts>,
+    ) -> Result<()> {
+        ctx.accounts.game_state.require_feature(FeatureGate::FEE_SPONSORSHIP)?;
+        let rent = Rent::get()?;
+        let player = ctx.accounts.player.key();
+        let profile_info = ctx.accounts.player_profile.to_account_info();
+        let profile_exists = profile_info.owner == &crate::ID;
+
+        // Lamports already sent to the profile address count toward its rent
+        let profile_rent = if profile_exists {
+            0
+        } else {
+            rent.minimum_balance(space::PLAYER_PROFILE).saturating_sub(profile_info.lamports())
+        };
+        let token_account_rent = if ctx.accounts.player_token_account.data_is_empty() {
+            rent.minimum_balance(TokenAccount::LEN)
+        } else {
+            0
+        };
+        let cost = profile_rent.checked_add(token_account_rent)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(cost > 0 || !profile_exists, ErrorCode::NothingToSponsor);
+        // The pool itself has to stay rent exempt
+        require!(
+            ctx.accounts.fee_pool.lamports() >= cost.saturating_add(rent.minimum_balance(0)),
+            ErrorCode::InsufficientFeePool
+        );
+
+        let pool_seeds = &[b"fee_pool".as_ref(), &[ctx.bumps.fee_pool]];
+        if !profile_exists {
+            let profile_seeds = &[b"player_profile".as_ref(), player.as_ref(), &[ctx.bumps.player_profile]];
+            create_pda_account(
+                &ctx.accounts.fee_pool.to_account_info(),
+                &profile_info,
+                &ctx.accounts.system_program.to_account_info(),
+                space::PLAYER_PROFILE,
+                &crate::ID,
+                &[&pool_seeds[..], &profile_seeds[..]],
+            )?;
+            let profile = PlayerProfile::new(player, ctx.bumps.player_profile, current_timestamp(ctx.remaining_accounts)?);
+            profile.try_serialize(&mut &mut profile_info.try_borrow_mut_data()?[..])?;
+        }
+
+        if token_account_rent > 0 {
+            anchor_spl::associated_token::create(CpiContext::new_with_signer(
+                ctx.accounts.associated_token_program.to_account_info(),
+                anchor_spl::associated_token::Create {
+                    payer: ctx.accounts.fee_pool.to_account_info(),
+                    associated_token: ctx.accounts.player_token_account.to_account_info(),
+                    authority: ctx.accounts.player.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                &[&pool_seeds[..]],
+            ))?;
+        }
+
+        let mut data = profile_info.try_borrow_mut_data()?;
+        let mut player_profile = PlayerProfile::try_deserialize(&mut &data[..])?;
+        player_profile.sponsored_lamports = player_profile.sponsored_lamports.checked_add(cost)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(
+            player_profile.sponsored_lamports <= ctx.accounts.game_state.fee_sponsor_cap_lamports,
+            ErrorCode::SponsorCapExceeded
+        );
+        player_profile.try_serialize(&mut &mut data[..])?;
+
+        msg!("Fee pool sponsored {} lamports of account rent for {}", cost, player);
+        Ok(())
+    }
+
+    // Create the rent treasury that fronts account rent for race receipts, new profiles and
+    // prize hold escrows (admin only)
+    pub fn initialize_rent_treasury(
+        ctx: Context<InitializeRentTreasury>,
+    ) -> Result<()> {
+        let rent_treasury = &mut ctx.accounts.rent_treasury;
+        rent_treasury.fronted = [0; RentSubsystem::COUNT];
+        rent_treasury.recovered = [0; RentSubsystem::COUNT];
+        rent_treasury.accounts_fronted = [0; RentSubsystem::COUNT];
+        rent_treasury.bump = ctx.bumps.rent_treasury;
+
+        msg!("Rent treasury initialized");
+        Ok(())
+    }
+
+    // Top up the rent treasury with lamports
+    pub fn fund_rent_treasury(
+        ctx: Context<FundRentTreasury>,
+        amount: u64,
+    ) -> Result<()> {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.funder.to_account_info(),
+                    to: ctx.accounts.rent_treasury.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        msg!("Rent treasury funded with {} lamports", amount);
+        Ok(())
+    }
+
+    // Close a settled race receipt and return its rent to whoever paid it: the rent treasury
+    // when it fronted the receipt, otherwise the player (admin only). The race id can be
+    // receipted again afterwards, so only prune races the oracle will not resubmit.
+    pub fn close_race_receipt(
+        ctx: Context<CloseRaceReceipt>,
+        race_id: u64,
+    ) -> Result<()> {
+        let race_receipt = &ctx.accounts.race_receipt;
+        require!(
+            matches!(race_receipt.status, AwardStatus::Paid | AwardStatus::Voided),
+            ErrorCode::AwardNotSettled
+        );
+
+        let lamports = race_receipt.to_account_info().lamports();
+        if race_receipt.rent_fronted {
+            let rent_treasury = ctx.accounts.rent_treasury.as_mut()
+                .ok_or(ErrorCode::RentTreasuryRequired)?;
+            rent_treasury.record_recovered(RentSubsystem::RaceReceipts, lamports)?;
+            ctx.accounts.race_receipt.close(rent_treasury.to_account_info())?;
+        } else {
+            ctx.accounts.race_receipt.close(ctx.accounts.player.to_account_info())?;
+        }
+
+        msg!("Closed race {} receipt, returning {} lamports", race_id, lamports);
+        Ok(())
+    }
+
+    // Apply yesterday's challenge completion counts to today's reward multipliers. Permissionless;
+    // the first challenge award of a day does the same, so this only matters on quiet days.
+    pub fn recompute_challenge_rewards(
+        ctx: Context<RecomputeChallengeRewards>,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let balance = &mut ctx.accounts.game_state.challenge_balance;
+        require!(balance.roll(now), ErrorCode::ChallengeDayNotOver);
+
+        emit!(ChallengeRewardsRecomputed {
+            day: balance.day,
+            multiplier_bps: balance.multiplier_bps,
+            timestamp: now,
+        });
+
+        msg!("Challenge multipliers for day {}: {:?}", balance.day, balance.multiplier_bps);
+        Ok(())
+    }
+
+    // Configure challenge reward balancing; a zero target disables it (admin or rate manager)
+    pub fn update_challenge_balance(
+        ctx: Context<UpdateRates>,
+        target_completions: u32,
+        step_bps: u16,
+        min_multiplier_bps: u16,
+        max_multiplier_bps: u16,
+    ) -> Result<()> {
+        require!(
+            min_multiplier_bps > 0 && min_multiplier_bps <= max_multiplier_bps,
+            ErrorCode::InvalidChallengeBalance
+        );
+        let balance = &mut ctx.accounts.game_state.challenge_balance;
+        balance.target_completions = target_completions;
+        balance.step_bps = step_bps;
+        balance.min_multiplier_bps = min_multiplier_bps;
+        balance.max_multiplier_bps = max_multiplier_bps;
+        for multiplier in balance.multiplier_bps.iter_mut() {
+            *multiplier = (*multiplier).clamp(min_multiplier_bps, max_multiplier_bps);
+        }
+
+        msg!("Challenge balancing targets {} completions per day", target_completions);
+        Ok(())
+    }
+
+    // Compare vault balances against what they owe: held awards and vesting balances for the
+    // main vault, and drawn but unclaimed prizes for the jackpot vault when it is passed.
+    // Permissionless; the result is emitted either way and a pass is recorded for awards
+    // that require a recent check.
+    pub fn check_solvency(
+        ctx: Context<CheckSolvency>,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let game_state = &ctx.accounts.game_state;
+        let vault_liabilities = game_state.vault_liabilities();
+        let mut solvent = ctx.accounts.vault.amount >= vault_liabilities;
+
+        let (jackpot_balance, jackpot_liabilities) = match (&ctx.accounts.jackpot, &ctx.accounts.jackpot_vault) {
+            (Some(jackpot), Some(jackpot_vault)) => {
+                require!(jackpot_vault.key() == game_state.jackpot.vault, ErrorCode::InvalidJackpotVault);
+                (jackpot_vault.amount, jackpot.reserved_prizes)
+            }
+            _ => (0, 0),
+        };
+        solvent &= jackpot_balance >= jackpot_liabilities;
+
+        if solvent {
+            ctx.accounts.game_state.last_solvent_at = now;
+        }
+
+        emit!(Solvency {
+            vault_balance: ctx.accounts.vault.amount,
+            vault_liabilities,
+            jackpot_balance,
+            jackpot_liabilities,
+            solvent,
+            timestamp: now,
+        });
+
+        msg!("Solvency check {}", if solvent { "passed" } else { "FAILED" });
+        Ok(())
+    }
+
+    // Require award instructions to follow a passing solvency check no older than
+    // `max_age_secs`; 0 removes the requirement (admin only)
+    pub fn set_solvency_max_age(
+        ctx: Context<UpdateGameState>,
+        max_age_secs: i64,
+    ) -> Result<()> {
+        require!(max_age_secs >= 0, ErrorCode::InvalidSolvencyMaxAge);
+        ctx.accounts.game_state.solvency_max_age_secs = max_age_secs;
+
+        msg!("Solvency max age set to {}s", max_age_secs);
+        Ok(())
+    }
+
+    // Record that the game server is alive (oracle only)
+    pub fn heartbeat(
+        ctx: Context<Heartbeat>,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        ctx.accounts.game_state.last_heartbeat_at = now;
+
+        log_msg!("Oracle heartbeat at {}", now);
+        Ok(())
+    }
+
+    // Set how long the oracle may go without a heartbeat before payouts need an authority
+    // co-signature; 0 disables the failsafe (admin only)
+    pub fn set_heartbeat_window(
+        ctx: Context<UpdateGameState>,
+        window_secs: i64,
+    ) -> Result<()> {
+        require!(window_secs >= 0, ErrorCode::InvalidHeartbeatWindow);
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.heartbeat_window_secs = window_secs;
+        // Start the first window now rather than treating the oracle as already stale
+        game_state.last_heartbeat_at = current_timestamp(ctx.remaining_accounts)?;
+
+        msg!("Heartbeat window set to {}s", window_secs);
+        Ok(())
+    }
+
+    // Pause or resume all payouts (admin only). Resuming restarts the watchdog's current window,
+    // and once an expected upgrade authority is set it also requires the program data account
+    // to show that authority still holds the upgrade key.
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        if !paused && game_state.expected_upgrade_authority != Pubkey::default() {
+            let program_data = ctx.accounts.program_data.as_ref()
+                .ok_or(ErrorCode::UpgradeAuthorityMismatch)?;
+            require!(
+                program_data.upgrade_authority_address == Some(game_state.expected_upgrade_authority),
+                ErrorCode::UpgradeAuthorityMismatch
+            );
+        }
+        game_state.paused = paused;
+        if !paused {
+            game_state.watchdog_state.window_start_slot = Clock::get()?.slot;
+            game_state.watchdog_state.window_distributed = 0;
+        }
+
+        msg!("Payouts {}", if paused { "paused" } else { "resumed" });
+        Ok(())
+    }
+
+    // Compare the program's upgrade authority against the expected multisig/DAO.
+    // Permissionless; a mismatch (including a frozen or reassigned authority) pauses payouts,
+    // which then cannot be resumed until the authority matches again.
+    pub fn verify_upgrade_authority(
+        ctx: Context<VerifyUpgradeAuthority>,
+    ) -> Result<()> {
+        let game_state = &mut ctx.accounts.game_state;
+        let expected = game_state.expected_upgrade_authority;
+        require!(expected != Pubkey::default(), ErrorCode::FeatureDisabled);
+
+        let actual = ctx.accounts.program_data.upgrade_authority_address;
+        let matched = actual == Some(expected);
+        if !matched {
+            game_state.paused = true;
+        }
+
+        emit!(UpgradeAuthorityVerified {
+            expected,
+            actual,
+            deployed_slot: ctx.accounts.program_data.slot,
+            matched,
+            timestamp: current_timestamp(ctx.remaining_accounts)?,
+        });
+
+        msg!("Upgrade authority {}", if matched { "verified" } else { "MISMATCH, payouts paused" });
+        Ok(())
+    }
+
+    // Set the multisig/DAO expected to hold the program's upgrade authority; the default key
+    // turns the check off (admin only)
+    pub fn set_expected_upgrade_authority(
+        ctx: Context<UpdateGameState>,
+        expected_upgrade_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.game_state.expected_upgrade_authority = expected_upgrade_authority;
+
+        msg!("Expected upgrade authority set to {}", expected_upgrade_authority);
+        Ok(())
+    }
+
+    // Configure the distribution watchdog (admin only)
+    pub fn update_watchdog_config(
+        ctx: Context<UpdateGameState>,
+        watchdog: WatchdogConfig,
+    ) -> Result<()> {
+        require!(watchdog.window_slots > 0, ErrorCode::InvalidWatchdogConfig);
+        ctx.accounts.game_state.watchdog = watchdog;
+
+        msg!("Watchdog set to {} bps over {} slot windows", watchdog.anomaly_multiplier_bps, watchdog.window_slots);
+        Ok(())
+    }
+
+    // Configure the race award dispute window, reviewer and challenge bond (admin only)
+    pub fn update_dispute_config(
+        ctx: Context<UpdateGameState>,
+        dispute: DisputeConfig,
+    ) -> Result<()> {
+        require!(dispute.window_secs >= 0, ErrorCode::InvalidDisputeConfig);
+        ctx.accounts.game_state.dispute = dispute;
+
+        msg!("Dispute window set to {} seconds", dispute.window_secs);
+        Ok(())
+    }
+
+    // Flag a pending race award before its dispute window closes. The reviewer flags for
+    // free; other challengers post the configured bond into the vault.
+    pub fn challenge_award(
+        ctx: Context<ChallengeAward>,
+        race_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let race_receipt = &ctx.accounts.race_receipt;
+        require!(race_receipt.status == AwardStatus::Pending, ErrorCode::AwardNotPending);
+        require!(now < race_receipt.release_at, ErrorCode::DisputeWindowClosed);
+
+        let challenger = ctx.accounts.challenger.key();
+        let bond = if challenger == ctx.accounts.game_state.dispute.reviewer {
+            0
+        } else {
+            ctx.accounts.game_state.dispute.challenge_bond
+        };
+        if bond > 0 {
+            let challenger_token_account = ctx.accounts.challenger_token_account.as_ref()
+                .ok_or(ErrorCode::InvalidPlayerTokenAccount)?;
+            let cpi_accounts = Transfer {
+                from: challenger_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.challenger.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            transfer(CpiContext::new(cpi_program, cpi_accounts), bond)?;
+        }
+
+        let race_receipt = &mut ctx.accounts.race_receipt;
+        race_receipt.status = AwardStatus::Disputed;
+        race_receipt.challenger = challenger;
+        race_receipt.challenge_bond = bond;
+
+        emit!(AwardChallenged {
+            player: race_receipt.player,
+            race_id,
+            challenger,
+            bond,
+            timestamp: now,
+        });
+
+        msg!("Race {} award for {} challenged", race_id, race_receipt.player);
+        Ok(())
+    }
+
+    // Settle a challenged award after re-verification (oracle only). An upheld challenge voids
+    // the award and returns the bond; a rejected one pays the award and slashes the bond.
+    pub fn resolve_award_dispute(
+        ctx: Context<ResolveAwardDispute>,
+        race_id: u64,
+        challenge_upheld: bool,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        require!(ctx.accounts.race_receipt.status == AwardStatus::Disputed, ErrorCode::AwardNotDisputed);
+        let amount = ctx.accounts.race_receipt.amount;
+        let bond = ctx.accounts.race_receipt.challenge_bond;
+
+        if challenge_upheld {
+            if bond > 0 {
+                let challenger_token_account = ctx.accounts.challenger_token_account.as_ref()
+                    .ok_or(ErrorCode::InvalidPlayerTokenAccount)?;
+                transfer_tokens_from_vault(
+                    ctx.accounts.game_state.to_account_info(),
+                    ctx.accounts.vault.to_account_info(),
+                    challenger_token_account.to_account_info(),
+                    ctx.accounts.token_program.to_account_info(),
+                    bond,
+                    ctx.accounts.game_state.bump,
+                )?;
+            }
+            let player_profile = &mut ctx.accounts.player_profile;
+            player_profile.lifetime_earnings = player_profile.lifetime_earnings.saturating_sub(amount);
+            let game_state = &mut ctx.accounts.game_state;
+            game_state.total_pending_awards = game_state.total_pending_awards.saturating_sub(amount);
+            ctx.accounts.race_receipt.status = AwardStatus::Voided;
+        } else {
+            pay_race_receipt(
+                &mut ctx.accounts.game_state,
+                &mut ctx.accounts.race_receipt,
+                &ctx.accounts.vault,
+                &ctx.accounts.player_token_account,
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.memo_program.as_ref(),
+                now,
+            )?;
+        }
+
+        emit!(AwardDisputeResolved {
+            player: ctx.accounts.race_receipt.player,
+            race_id,
+            challenge_upheld,
+            timestamp: now,
+        });
+
+        msg!("Race {} dispute resolved, challenge {}", race_id, if challenge_upheld { "upheld" } else { "rejected" });
+        Ok(())
+    }
+
+    // Pay out a pending race award once its dispute window has closed (permissionless)
+    pub fn release_award(
+        ctx: Context<ReleaseAward>,
+        race_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let race_receipt = &ctx.accounts.race_receipt;
+        require!(race_receipt.status == AwardStatus::Pending, ErrorCode::AwardNotPending);
+        require!(now >= race_receipt.release_at, ErrorCode::DisputeWindowOpen);
+
+        pay_race_receipt(
+            &mut ctx.accounts.game_state,
+            &mut ctx.accounts.race_receipt,
+            &ctx.accounts.vault,
+            &ctx.accounts.player_token_account,
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.memo_program.as_ref(),
+            now,
+        )?;
+
+        msg!("Released race {} award of {} $SPEEDY", race_id, ctx.accounts.race_receipt.amount);
+        Ok(())
+    }
+
+    // Vote for another participant of a race as its MVP. Both the voter and the candidate prove
+    // participation with their race receipts; the vote PDA allows one vote per voter and race.
+    pub fn cast_mvp_vote(
+        ctx: Context<CastMvpVote>,
+        race_id: u64,
+    ) -> Result<()> {
+        let mvp = ctx.accounts.game_state.mvp;
+        require!(mvp.bonus > 0, ErrorCode::InvalidMvpVote);
+        let voter = ctx.accounts.voter.key();
+        let candidate = ctx.accounts.candidate.key();
+        require!(voter != candidate, ErrorCode::InvalidMvpVote);
+        require!(
+            ctx.accounts.voter_receipt.status != AwardStatus::Voided
+                && ctx.accounts.candidate_receipt.status != AwardStatus::Voided,
+            ErrorCode::InvalidMvpVote
+        );
+
+        // The window opens with the voter's race award
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let mvp_ballot = &mut ctx.accounts.mvp_ballot;
+        if mvp_ballot.closes_at == 0 {
+            mvp_ballot.race_id = race_id;
+            mvp_ballot.closes_at = ctx.accounts.voter_receipt.awarded_at.checked_add(mvp.window_secs)
+                .ok_or(ErrorCode::InvalidRewardAmount)?;
+            mvp_ballot.tallies = Vec::new();
+            mvp_ballot.total_votes = 0;
+            mvp_ballot.settled = false;
+            mvp_ballot.bump = ctx.bumps.mvp_ballot;
+        }
+        require!(now < mvp_ballot.closes_at, ErrorCode::MvpVotingClosed);
+        mvp_ballot.record_vote(candidate)?;
+
+        let mvp_vote = &mut ctx.accounts.mvp_vote;
+        mvp_vote.race_id = race_id;
+        mvp_vote.voter = voter;
+        mvp_vote.candidate = candidate;
+        mvp_vote.voted_at = now;
+        mvp_vote.bump = ctx.bumps.mvp_vote;
+
+        msg!("MVP vote in race {} for {}", race_id, candidate);
+        Ok(())
+    }
+
+    // Pay the MVP bonus to the most voted participant once voting has closed (permissionless).
+    // Without enough votes the ballot is settled with no bonus.
+    pub fn settle_mvp(
+        ctx: Context<SettleMvp>,
+        race_id: u64,
+    ) -> Result<()> {
+        let now = current_timestamp(ctx.remaining_accounts)?;
+        let mvp_ballot = &ctx.accounts.mvp_ballot;
+        require!(!mvp_ballot.settled, ErrorCode::MvpAlreadySettled);
+        require!(now >= mvp_ballot.closes_at, ErrorCode::MvpVotingOpen);
+        let leader = mvp_ballot.leader().ok_or(ErrorCode::InvalidMvpVote)?;
+        require_keys_eq!(ctx.accounts.winner.key(), leader.candidate, ErrorCode::NotMvpWinner);
+
+        let mvp = ctx.accounts.game_state.mvp;
+        let bonus = if leader.votes >= mvp.min_votes { mvp.bonus } else { 0 };
+        if bonus > 0 {
+            // Check if vault has sufficient balance
+            ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, bonus, 0)?;
+
+            transfer_tokens_from_vault(
+                ctx.accounts.game_state.to_account_info(),
+                ctx.accounts.vault.to_account_info(),
+                ctx.accounts.winner_token_account.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                bonus,
+                ctx.accounts.game_state.bump,
+            )?;
+            ctx.accounts.game_state.record_distribution(bonus)?;
+        }
+        ctx.accounts.mvp_ballot.settled = true;
+
+        emit!(MvpAwarded {
+            race_id,
+            winner: leader.candidate,
+            votes: leader.votes,
+            total_votes: ctx.accounts.mvp_ballot.total_votes,
+            bonus,
+            timestamp: now,
+        });
+
+        log_msg!("Race {} MVP {} with {} votes, bonus {} $SPEEDY", race_id, leader.candidate, leader.votes, bonus);
+        Ok(())
+    }
+
+    // Set the MVP bonus, voting window and minimum votes (admin only); a zero bonus disables voting
+    pub fn update_mvp_config(
+        ctx: Context<UpdateGameState>,
+        mvp: MvpConfig,
+    ) -> Result<()> {
+        require!(mvp.window_secs > 0, ErrorCode::InvalidRewardAmount);
+        ctx.accounts.game_state.mvp = mvp;
+
+        msg!("MVP bonus {} $SPEEDY, {}s window, {} votes minimum", mvp.bonus, mvp.window_secs, mvp.min_votes);
+        Ok(())
+    }
+
+    // Configure the new-wallet probation window (admin only). A zero duration disables probation.
+    pub fn update_probation_config(
+        ctx: Context<UpdateGameState>,
+        probation: ProbationConfig,
+    ) -> Result<()> {
+        require!(
+            probation.duration_secs >= 0 && probation.reward_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidProbationConfig
+        );
+
+        ctx.accounts.game_state.probation = probation;
+        msg!("Probation config updated: {}s at {} bps", probation.duration_secs, probation.reward_bps);
+        Ok(())
+    }
+
+    // Set the trusted attestation verifier (admin only). The default pubkey disables attestation checks.
+    pub fn set_attestation_verifier(
+        ctx: Context<UpdateGameState>,
+        verifier: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.game_state.attestation_verifier = verifier;
+        msg!("Attestation verifier set to {}", verifier);
+        Ok(())
+    }
+
+    // Bind a verifier-issued attestation hash (device or identity check) to a player wallet.
+    // The PDA is keyed by the hash, so one attestation can only ever back a single wallet.
+    pub fn record_attestation(
+        ctx: Context<RecordAttestation>,
+        attestation_hash: [u8; 32],
+    ) -> Result<()> {
+        let game_state = &ctx.accounts.game_state;
+        require!(
+            game_state.attestation_verifier != Pubkey::default(),
+            ErrorCode::AttestationNotEnabled
+        );
+
+        let timestamp = current_timestamp(ctx.remaining_accounts)?;
+        let attestation = &mut ctx.accounts.attestation;
+        attestation.player = ctx.accounts.player.key();
+        attestation.attestation_hash = attestation_hash;
+        attestation.verifier = ctx.accounts.verifier.key();
+        attestation.attested_at = timestamp;
+        attestation.welcome_bonus_claimed = false;
+        attestation.bump = ctx.bumps.attestation;
+
+        emit!(PlayerAttested {
+            player: attestation.player,
+            attestation_hash,
+            verifier: attestation.verifier,
+            timestamp,
+        });
+
+        msg!("Attestation recorded for player {}", attestation.player);
+        Ok(())
+    }
+}
+
+// Account Sizes
+//
+// Allocated size of every program account, discriminator included, for rent estimation.
+pub mod space {
+    use super::*;
+
+    pub const DISCRIMINATOR: usize = 8;
+
+    pub const GAME_STATE: usize = DISCRIMINATOR + GameState::LEN;
+    // Game state allocated by the original release: authority through is_initialized, with
+    // the discriminator counted twice. Migrated by `migrate_game_state`.
+    pub const GAME_STATE_V1: usize = DISCRIMINATOR + 8 + 32 + 32 + 32 + 8 + TokenRates::LEN + 1 + 1;
+    pub const PLAYER_PROFILE: usize = DISCRIMINATOR + PlayerProfile::LEN;
+    pub const ATTESTATION: usize = DISCRIMINATOR + Attestation::LEN;
+    pub const TOURNAMENT: usize = DISCRIMINATOR + Tournament::LEN;
+    pub const TOURNAMENT_ENTRY: usize = DISCRIMINATOR + TournamentEntry::LEN;
+    pub const LEAGUE: usize = DISCRIMINATOR + League::LEN;
+    pub const PREDICTION_MARKET: usize = DISCRIMINATOR + PredictionMarket::LEN;
+    pub const PREDICTION_STAKE: usize = DISCRIMINATOR + PredictionStake::LEN;
+    pub const JACKPOT: usize = DISCRIMINATOR + Jackpot::LEN;
+    pub const JACKPOT_ENTRY: usize = DISCRIMINATOR + JackpotEntry::LEN;
+    pub const JACKPOT_DRAW: usize = DISCRIMINATOR + JackpotDraw::LEN;
+    pub const QUEUE_TICKET: usize = DISCRIMINATOR + QueueTicket::LEN;
+    pub const GRANT: usize = DISCRIMINATOR + Grant::LEN;
+    pub const WEEKLY_STANDINGS: usize = DISCRIMINATOR + WeeklyStandings::LEN;
+    pub const STANDINGS_CLAIM: usize = DISCRIMINATOR + StandingsClaim::LEN;
+    pub const PAYMENT_STREAM: usize = DISCRIMINATOR + PaymentStream::LEN;
+    pub const BUYBACK_CONFIG: usize = DISCRIMINATOR + BuybackConfig::LEN;
+    pub const TOKEN_SALE: usize = DISCRIMINATOR + TokenSale::LEN;
+    pub const SALE_PURCHASE: usize = DISCRIMINATOR + SalePurchase::LEN;
+    pub const USED_VOUCHER: usize = DISCRIMINATOR + UsedVoucher::LEN;
+    pub const CONFIG_EXPORT: usize = DISCRIMINATOR + ConfigExport::LEN;
+    pub const CLAWBACK: usize = DISCRIMINATOR + Clawback::LEN;
+    pub const WAITLIST_ENTRY: usize = DISCRIMINATOR + WaitlistEntry::LEN;
+    pub const WEEKLY_REPORT: usize = DISCRIMINATOR + WeeklyReport::LEN;
+    pub const TEST_CLOCK: usize = DISCRIMINATOR + TestClock::LEN;
+    pub const REGISTRY_PAGE: usize = DISCRIMINATOR + RegistryPage::LEN;
+    pub const RACE_RECEIPT: usize = DISCRIMINATOR + RaceReceipt::LEN;
+    pub const PROPOSAL: usize = DISCRIMINATOR + Proposal::LEN;
+    pub const PARTNER_GRANT: usize = DISCRIMINATOR + PartnerGrant::LEN;
+    pub const HONEYCOMB_CONFIG: usize = DISCRIMINATOR + HoneycombConfig::LEN;
+    pub const MISSION_CLAIM: usize = DISCRIMINATOR + MissionClaim::LEN;
+    pub const BRIDGE_CONFIG: usize = DISCRIMINATOR + BridgeConfig::LEN;
+    pub const BRIDGE_CLAIM: usize = DISCRIMINATOR + BridgeClaim::LEN;
+    pub const ONRAMP_PROVIDER: usize = DISCRIMINATOR + OnrampProvider::LEN;
+    pub const ONRAMP_ORDER: usize = DISCRIMINATOR + OnrampOrder::LEN;
+    pub const REGION_POOL: usize = DISCRIMINATOR + RegionPool::LEN;
+    pub const CAR_DROP: usize = DISCRIMINATOR + CarDrop::LEN;
+    pub const PREORDER: usize = DISCRIMINATOR + Preorder::LEN;
+    pub const DUTCH_AUCTION: usize = DISCRIMINATOR + DutchAuction::LEN;
+    pub const AUCTION_PURCHASE: usize = DISCRIMINATOR + AuctionPurchase::LEN;
+    pub const REDEMPTION_OFFER: usize = DISCRIMINATOR + RedemptionOffer::LEN;
+    pub const REDEMPTION_RECEIPT: usize = DISCRIMINATOR + RedemptionReceipt::LEN;
+    pub const DONATION_STATS: usize = DISCRIMINATOR + DonationStats::LEN;
+    pub const LEADERBOARD: usize = DISCRIMINATOR + Leaderboard::LEN;
+    pub const PLAYER_BEST: usize = DISCRIMINATOR + PlayerBest::LEN;
+    pub const TICKET_CONFIG: usize = DISCRIMINATOR + TicketConfig::LEN;
+    pub const PROMO_CODE: usize = DISCRIMINATOR + PromoCode::LEN;
+    pub const PROMO_REDEMPTION: usize = DISCRIMINATOR + PromoRedemption::LEN;
+    pub const INVENTORY: usize = DISCRIMINATOR + Inventory::LEN;
+    pub const COSMETIC_LISTING: usize = DISCRIMINATOR + CosmeticListing::LEN;
+    pub const PRIZE_HOLD: usize = DISCRIMINATOR + PrizeHold::LEN;
+    pub const RENT_TREASURY: usize = DISCRIMINATOR + RentTreasury::LEN;
+    pub const MVP_BALLOT: usize = DISCRIMINATOR + MvpBallot::LEN;
+    pub const MVP_VOTE: usize = DISCRIMINATOR + MvpVote::LEN;
+    pub const WORLD_RECORD: usize = DISCRIMINATOR + WorldRecord::LEN;
+    pub const TRACK: usize = DISCRIMINATOR + Track::LEN;
+    pub const TRACK_SUBMISSION: usize = DISCRIMINATOR + TrackSubmission::LEN;
+    pub const CURATION_VOTE: usize = DISCRIMINATOR + CurationVote::LEN;
+    pub const RATE_HISTORY: usize = DISCRIMINATOR + RateHistory::LEN;
+    pub const PLAYER_INDEX: usize = DISCRIMINATOR + PlayerIndex::LEN;
+    pub const YEARLY_STATEMENT: usize = DISCRIMINATOR + YearlyStatement::LEN;
+    pub const ROLE_ASSIGNMENT: usize = DISCRIMINATOR + RoleAssignment::LEN;
+
+    // (account name, allocated size) for every account type
+    pub const REGISTRY: &[(&str, usize)] = &[
+        ("GameState", GAME_STATE),
+        ("PlayerProfile", PLAYER_PROFILE),
+        ("Attestation", ATTESTATION),
+        ("Tournament", TOURNAMENT),
+        ("TournamentEntry", TOURNAMENT_ENTRY),
+        ("League", LEAGUE),
+        ("PredictionMarket", PREDICTION_MARKET),
+        ("PredictionStake", PREDICTION_STAKE),
+        ("Jackpot", JACKPOT),
+        ("JackpotEntry", JACKPOT_ENTRY),
+        ("JackpotDraw", JACKPOT_DRAW),
+        ("QueueTicket", QUEUE_TICKET),
+        ("Grant", GRANT),
+        ("WeeklyStandings", WEEKLY_STANDINGS),
+        ("StandingsClaim", STANDINGS_CLAIM),
+        ("PaymentStream", PAYMENT_STREAM),
+        ("BuybackConfig", BUYBACK_CONFIG),
+        ("TokenSale", TOKEN_SALE),
+        ("SalePurchase", SALE_PURCHASE),
+        ("UsedVoucher", USED_VOUCHER),
+        ("ConfigExport", CONFIG_EXPORT),
+        ("Clawback", CLAWBACK),
+        ("WaitlistEntry", WAITLIST_ENTRY),
+        ("WeeklyReport", WEEKLY_REPORT),
+        ("TestClock", TEST_CLOCK),
+        ("RegistryPage", REGISTRY_PAGE),
+        ("RaceReceipt", RACE_RECEIPT),
+        ("Proposal", PROPOSAL),
+        ("PartnerGrant", PARTNER_GRANT),
+        ("HoneycombConfig", HONEYCOMB_CONFIG),
+        ("MissionClaim", MISSION_CLAIM),
+        ("BridgeConfig", BRIDGE_CONFIG),
+        ("BridgeClaim", BRIDGE_CLAIM),
+        ("OnrampProvider", ONRAMP_PROVIDER),
+        ("OnrampOrder", ONRAMP_ORDER),
+        ("RegionPool", REGION_POOL),
+        ("CarDrop", CAR_DROP),
+        ("Preorder", PREORDER),
+        ("DutchAuction", DUTCH_AUCTION),
+        ("AuctionPurchase", AUCTION_PURCHASE),
+        ("RedemptionOffer", REDEMPTION_OFFER),
+        ("RedemptionReceipt", REDEMPTION_RECEIPT),
+        ("DonationStats", DONATION_STATS),
+        ("Leaderboard", LEADERBOARD),
+        ("PlayerBest", PLAYER_BEST),
+        ("TicketConfig", TICKET_CONFIG),
+        ("PromoCode", PROMO_CODE),
+        ("PromoRedemption", PROMO_REDEMPTION),
+        ("Inventory", INVENTORY),
+        ("CosmeticListing", COSMETIC_LISTING),
+        ("PrizeHold", PRIZE_HOLD),
+        ("RentTreasury", RENT_TREASURY),
+        ("MvpBallot", MVP_BALLOT),
+        ("MvpVote", MVP_VOTE),
+        ("WorldRecord", WORLD_RECORD),
+        ("Track", TRACK),
+        ("TrackSubmission", TRACK_SUBMISSION),
+        ("CurationVote", CURATION_VOTE),
+        ("RateHistory", RATE_HISTORY),
+        ("PlayerIndex", PLAYER_INDEX),
+        ("YearlyStatement", YEARLY_STATEMENT),
+        ("RoleAssignment", ROLE_ASSIGNMENT),
+    ];
+
+    // Lamports needed to keep an account of `size` bytes rent exempt at the default rent
+    pub fn rent_exempt_lamports(size: usize) -> u64 {
+        Rent::default().minimum_balance(size)
+    }
+}
+
+// Shared Reward Logic
+//
+// Pure reward math and voucher encoding shared with the off-chain game server. Nothing in
+// this module depends on Anchor or the Solana runtime, so the server can compile it on its
+// own and its reward previews match what the program pays byte for byte.
+pub mod speedy_core {
+    pub const BPS_DENOMINATOR: u16 = 10_000;
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct RaceRates {
+        pub race_completion: u64,
+        pub race_win: u64,
+        pub distance_per_100m: u64,
+        pub obstacle_avoided: u64,
+        pub bonus_collected: u64,
+    }
+
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct RacePerformance {
+        pub completed: bool,
+        pub won: bool,
+        pub distance: u64,
+        pub obstacles_avoided: u64,
+        pub bonus_boxes_collected: u64,
+    }
+
+    // Total race reward: completion + win + distance (per 100m) + obstacles + bonus boxes
+    pub fn race_reward(rates: &RaceRates, race: &RacePerformance) -> Option<u64> {
+        let mut total = 0u64;
+        if race.completed {
+            total = total.checked_add(rates.race_completion)?;
+        }
+        if race.won {
+            total = total.checked_add(rates.race_win)?;
+        }
+        total = total.checked_add((race.distance / 100).checked_mul(rates.distance_per_100m)?)?;
+        total = total.checked_add(race.obstacles_avoided.checked_mul(rates.obstacle_avoided)?)?;
+        total.checked_add(race.bonus_boxes_collected.checked_mul(rates.bonus_collected)?)
+    }
+
+    // Score-based reward: `score * per_1000_points / 1000`, capped at `max_reward`
+    pub fn score_reward(score: u64, per_1000_points: u64, max_reward: u64) -> Option<u64> {
+        let reward = (score as u128).checked_mul(per_1000_points as u128)? / 1000;
+        Some(u64::try_from(reward).unwrap_or(u64::MAX).min(max_reward))
+    }
+
+    // Battle-royale reward: survival minutes and eliminations, scaled by a podium multiplier
+    // for 1st-3rd place (`placement` is 1-based; other placements use 1x)
+    pub fn elimination_reward(
+        per_survival_minute: u64,
+        per_elimination: u64,
+        podium_multiplier_bps: &[u16; 3],
+        survival_secs: u64,
+        eliminations: u64,
+        placement: u16,
+    ) -> Option<u64> {
+        let base = (survival_secs / 60).checked_mul(per_survival_minute)?
+            .checked_add(eliminations.checked_mul(per_elimination)?)?;
+        let multiplier_bps = match placement {
+            1..=3 => podium_multiplier_bps[(placement - 1) as usize],
+            _ => BPS_DENOMINATOR,
+        };
+        let scaled = (base as u128).checked_mul(multiplier_bps as u128)? / BPS_DENOMINATOR as u128;
+        u64::try_from(scaled).ok()
+    }
+
+    // Number of fixtures in a single round-robin over `participants`
+    pub fn fixture_count(participants: usize) -> usize {
+        participants * participants.saturating_sub(1) / 2
+    }
+
+    // Position of the unordered pair (a, b) among a round-robin's fixtures
+    pub fn fixture_index(participants: usize, a: usize, b: usize) -> usize {
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        low * participants - low * (low + 1) / 2 + (high - low - 1)
+    }
+
+    // Parimutuel payout for a winning stake after the house rake
+    pub fn prediction_payout(
+        stake: u64,
+        winning_pool: u64,
+        total_pool: u64,
+        rake_bps: u16,
+    ) -> Option<u64> {
+        let net_pool = total_pool.checked_sub(apply_bps(total_pool, rake_bps)?)?;
+        let payout = (stake as u128)
+            .checked_mul(net_pool as u128)?
+            .checked_div(winning_pool as u128)?;
+        u64::try_from(payout).ok()
+    }
+
+    pub const SECONDS_PER_WEEK: i64 = 7 * 24 * 60 * 60;
+
+    // Gregorian calendar year (UTC) containing the unix timestamp
+    pub fn calendar_year(now: i64) -> u16 {
+        // Days are shifted to an era starting on 0000-03-01 so leap days fall at the end of a year
+        let days = now.div_euclid(24 * 60 * 60) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let march_based_month = (5 * day_of_year + 2) / 153;
+        let year = era * 400 + year_of_era + i64::from(march_based_month >= 10);
+        u16::try_from(year.max(0)).unwrap_or(u16::MAX)
+    }
+
+    // Week number since the unix epoch, used for weekly activity checks
+    pub fn week_index(now: i64) -> u64 {
+        u64::try_from(now.max(0) / SECONDS_PER_WEEK).unwrap_or(0)
+    }
+
+    // Pick a winner among `entry_count` entries from 32 bytes of randomness
+    pub fn draw_winner_index(randomness: &[u8; 32], entry_count: u32) -> Option<u32> {
+        let mut seed = [0u8; 8];
+        seed.copy_from_slice(&randomness[..8]);
+        let index = u64::from_le_bytes(seed).checked_rem(entry_count as u64)?;
+        u32::try_from(index).ok()
+    }
+
+    pub const RACES_PER_LEVEL: u64 = 10;
+
+    // Player level derived from completed races, starting at level 1
+    pub fn player_level(races_completed: u64) -> u32 {
+        u32::try_from(races_completed / RACES_PER_LEVEL).unwrap_or(u32::MAX).saturating_add(1)
+    }
+
+    pub const INITIAL_RATING: u32 = 1200;
+    pub const MIN_RATING: u32 = 100;
+    pub const RATING_K_FACTOR: i64 = 32;
+
+    // Expected score (bps) against an opponent `opponent_rating - rating` points stronger,
+    // i.e. 1 / (1 + 10^(diff / 400)), linearly interpolated between 100-point steps
+    pub fn expected_score_bps(rating: u32, opponent_rating: u32) -> u16 {
+        const TABLE: [i64; 17] = [
+            9901, 9825, 9693, 9468, 9091, 8490, 7597, 6401, 5000,
+            3599, 2403, 1510, 909, 532, 307, 175, 99,
+        ];
+        let diff = (opponent_rating as i64 - rating as i64).clamp(-800, 800) + 800;
+        let index = (diff / 100) as usize;
+        if index == TABLE.len() - 1 {
+            return TABLE[index] as u16;
+        }
+        let fraction = diff % 100;
+        (TABLE[index] + (TABLE[index + 1] - TABLE[index]) * fraction / 100) as u16
+    }
+
+    // New rating after a result scored `score_bps` (0 = loss, 5000 = draw, 10000 = win)
+    pub fn update_rating(rating: u32, opponent_rating: u32, score_bps: u16) -> u32 {
+        let expected = expected_score_bps(rating, opponent_rating) as i64;
+        let delta = RATING_K_FACTOR * (score_bps as i64 - expected) / BPS_DENOMINATOR as i64;
+        (rating as i64 + delta).clamp(MIN_RATING as i64, u32::MAX as i64) as u32
+    }
+
+    // Reward multiplier (bps) for a player's rating: catch-up boost below the low threshold,
+    // slight reduction above the high threshold, 1x otherwise
+    pub fn handicap_multiplier_bps(
+        rating: u32,
+        low_rating_threshold: u32,
+        catch_up_bps: u16,
+        high_rating_threshold: u32,
+        top_rated_bps: u16,
+    ) -> u16 {
+        if rating < low_rating_threshold {
+            catch_up_bps
+        } else if rating > high_rating_threshold {
+            top_rated_bps
+        } else {
+            BPS_DENOMINATOR
+        }
+    }
+
+    pub fn staking_reward(hourly_rate: u64, hours_staked: u64) -> Option<u64> {
+        hourly_rate.checked_mul(hours_staked)
+    }
+
+    // `amount * bps / 10_000`, rounded down
+    pub fn apply_bps(amount: u64, bps: u16) -> Option<u64> {
+        let scaled = (amount as u128).checked_mul(bps as u128)? / BPS_DENOMINATOR as u128;
+        u64::try_from(scaled).ok()
+    }
+
+    // Burn share that rises linearly from `floor_bps` at `low_supply` circulating tokens
+    // to `ceiling_bps` at `high_supply`, clamped outside that range
+    pub fn burn_share_bps(
+        circulating: u64,
+        floor_bps: u16,
+        ceiling_bps: u16,
+        low_supply: u64,
+        high_supply: u64,
+    ) -> Option<u16> {
+        if circulating >= high_supply {
+            return Some(ceiling_bps);
+        }
+        if circulating <= low_supply {
+            return Some(floor_bps);
+        }
+        let span = ceiling_bps.checked_sub(floor_bps)? as u128;
+        let step = span
+            .checked_mul((circulating - low_supply) as u128)?
+            .checked_div((high_supply - low_supply) as u128)?;
+        floor_bps.checked_add(u16::try_from(step).ok()?)
+    }
+
+    // Emission era: one per `every_distributed` tokens paid out and one per `every_slots` slots
+    // after `start_slot`, whichever is further along, capped at `max_era`. A zero interval
+    // disables that trigger.
+    pub fn halving_era(
+        total_distributed: u64,
+        every_distributed: u64,
+        slot: u64,
+        start_slot: u64,
+        every_slots: u64,
+        max_era: u8,
+    ) -> u8 {
+        let by_distribution = total_distributed.checked_div(every_distributed).unwrap_or(0);
+        let by_slot = slot.saturating_sub(start_slot).checked_div(every_slots).unwrap_or(0);
+        by_distribution.max(by_slot).min(max_era as u64) as u8
+    }
+
+    // Entry fee after a linear fill-rate premium (`fill_premium_bps` at a full bracket)
+    // and a flat discount inside the last `late_window_secs` before the deadline
+    pub fn dynamic_entry_fee(
+        base_fee: u64,
+        registered: u32,
+        max_entrants: u32,
+        fill_premium_bps: u16,
+        late_discount_bps: u16,
+        late_window_secs: i64,
+        secs_to_deadline: i64,
+    ) -> Option<u64> {
+        let premium = (fill_premium_bps as u64)
+            .checked_mul(registered as u64)?
+            .checked_div(max_entrants.max(1) as u64)?;
+        let multiplier_bps = u16::try_from((BPS_DENOMINATOR as u64).checked_add(premium)?).ok()?;
+        let fee = apply_bps(base_fee, multiplier_bps)?;
+        if late_window_secs > 0 && secs_to_deadline <= late_window_secs {
+            apply_bps(fee, BPS_DENOMINATOR.checked_sub(late_discount_bps)?)
+        } else {
+            Some(fee)
+        }
+    }
+
+    // Convert a raw token amount by `numerator / denominator`, adjusting for mint decimals
+    pub fn convert_token_amount(
+        amount: u64,
+        numerator: u64,
+        denominator: u64,
+        from_decimals: u8,
+        to_decimals: u8,
+    ) -> Option<u64> {
+        if denominator == 0 {
+            return None;
+        }
+        let mut scaled = (amount as u128).checked_mul(numerator as u128)?;
+        let mut divisor = denominator as u128;
+        if to_decimals >= from_decimals {
+            scaled = scaled.checked_mul(10u128.checked_pow((to_decimals - from_decimals) as u32)?)?;
+        } else {
+            divisor = divisor.checked_mul(10u128.checked_pow((from_decimals - to_decimals) as u32)?)?;
+        }
+        u64::try_from(scaled / divisor).ok()
+    }
+
+    // Convert USD cents to raw token units at `price * 10^exponent` USD per whole token
+    pub fn usd_cents_to_tokens(cents: u64, decimals: u8, price: i64, exponent: i32) -> Option<u64> {
+        if price <= 0 {
+            return None;
+        }
+        // raw = cents * 10^decimals / (100 * price * 10^exponent)
+        let mut numerator = (cents as u128).checked_mul(10u128.checked_pow(decimals as u32)?)?;
+        let mut denominator = (price as u128).checked_mul(100)?;
+        let exponent_scale = 10u128.checked_pow(exponent.unsigned_abs())?;
+        if exponent < 0 {
+            numerator = numerator.checked_mul(exponent_scale)?;
+        } else {
+            denominator = denominator.checked_mul(exponent_scale)?;
+        }
+        u64::try_from(numerator / denominator).ok()
+    }
+
+    // Lamport cost of buying `amount` raw units after `sold` units have been sold, with the
+    // price per whole token at `base + slope * whole_tokens_sold`, integrated over the purchase.
+    // Both terms round up, so dust buys cannot come out free and splitting a buy never saves.
+    pub fn sale_cost_lamports(
+        base_price_lamports: u64,
+        price_slope_lamports: u64,
+        sold: u64,
+        amount: u64,
+        decimals: u8,
+    ) -> Option<u64> {
+        let unit = 10u128.checked_pow(decimals as u32)?;
+        let amount = amount as u128;
+        let base_cost = (base_price_lamports as u128).checked_mul(amount)?.div_ceil(unit);
+
+        // slope * amount * (2 * sold + amount) / (2 * unit^2)
+        let curve_units = amount.checked_mul((sold as u128) * 2 + amount)?.div_ceil(2 * unit);
+        let curve_cost = (price_slope_lamports as u128).checked_mul(curve_units)?.div_ceil(unit);
+
+        u64::try_from(base_cost.checked_add(curve_cost)?).ok()
+    }
+
+    // Amount streamed by `now`, nothing before the cliff and nothing accrues after `end_time`
+    pub fn stream_vested_amount(
+        rate_per_second: u64,
+        start_time: i64,
+        cliff_time: i64,
+        end_time: i64,
+        now: i64,
+    ) -> Option<u64> {
+        if now < cliff_time {
+            return Some(0);
+        }
+        let elapsed = now.min(end_time).saturating_sub(start_time).max(0) as u64;
+        elapsed.checked_mul(rate_per_second)
+    }
+
+    // Leaderboard ordering over (score, submission slot, player key): the higher score ranks
+    // first, then the earlier slot, then the lexicographically smaller key. Distinct players
+    // never compare equal, so settlement from the same entries is always reproducible.
+    pub fn leaderboard_order(a: (u64, u64, &[u8; 32]), b: (u64, u64, &[u8; 32])) -> core::cmp::Ordering {
+        b.0.cmp(&a.0)
+            .then(a.1.cmp(&b.1))
+            .then(a.2.cmp(b.2))
+    }
+
+    // Canonical bytes of a reward voucher as signed by the oracle (Borsh layout of `RewardVoucher`)
+    pub fn voucher_message(
+        player: &[u8; 32],
+        reward_type: u8,
+        reference_id: u64,
+        amount: u64,
+        nonce: u64,
+        expires_at: i64,
+        oracle_epoch: u64,
+    ) -> Vec<u8> {
+        let mut message = Vec::with_capacity(32 + 1 + 8 * 5);
+        message.extend_from_slice(player);
+        message.push(reward_type);
+        message.extend_from_slice(&reference_id.to_le_bytes());
+        message.extend_from_slice(&amount.to_le_bytes());
+        message.extend_from_slice(&nonce.to_le_bytes());
+        message.extend_from_slice(&expires_at.to_le_bytes());
+        message.extend_from_slice(&oracle_epoch.to_le_bytes());
+        message
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use core::cmp::Ordering;
+
+        #[test]
+        fn race_reward_sums_every_component() {
+            let rates = RaceRates {
+                race_completion: 10,
+                race_win: 20,
+                distance_per_100m: 1,
+                obstacle_avoided: 2,
+                bonus_collected: 3,
+            };
+            let race = RacePerformance {
+                completed: true,
+                won: true,
+                distance: 1_050,
+                obstacles_avoided: 4,
+                bonus_boxes_collected: 2,
+            };
+            assert_eq!(race_reward(&rates, &race), Some(54));
+            assert_eq!(race_reward(&rates, &RacePerformance::default()), Some(0));
+
+            let rates = RaceRates { race_completion: u64::MAX, race_win: 1, ..RaceRates::default() };
+            assert_eq!(race_reward(&rates, &race), None);
+        }
+
+        #[test]
+        fn score_reward_is_capped() {
+            assert_eq!(score_reward(2_500, 1_000, 10_000), Some(2_500));
+            assert_eq!(score_reward(2_000_000, 1_000, 500), Some(500));
+            assert_eq!(score_reward(u64::MAX, u64::MAX, 7), Some(7));
+        }
+
+        #[test]
+        fn elimination_reward_applies_podium_multiplier() {
+            let podium = [20_000, 15_000, 12_500];
+            assert_eq!(elimination_reward(5, 10, &podium, 125, 3, 1), Some(80));
+            assert_eq!(elimination_reward(5, 10, &podium, 125, 3, 3), Some(50));
+            assert_eq!(elimination_reward(5, 10, &podium, 125, 3, 4), Some(40));
+            assert_eq!(elimination_reward(5, 10, &podium, 125, 3, 0), Some(40));
+        }
+
+        #[test]
+        fn fixture_count_and_index() {
+            assert_eq!(fixture_count(0), 0);
+            assert_eq!(fixture_count(1), 0);
+            assert_eq!(fixture_count(4), 6);
+            assert_eq!(fixture_index(4, 0, 1), 0);
+            assert_eq!(fixture_index(4, 2, 3), 5);
+            assert_eq!(fixture_index(4, 3, 1), fixture_index(4, 1, 3));
+        }
+
+        #[test]
+        fn prediction_payout_after_rake() {
+            assert_eq!(prediction_payout(100, 1_000, 4_000, 500), Some(380));
+            assert_eq!(prediction_payout(100, 0, 4_000, 500), None);
+        }
+
+        #[test]
+        fn calendar_year_handles_leap_days_and_year_ends() {
+            assert_eq!(calendar_year(0), 1970);
+            assert_eq!(calendar_year(-86_400), 1969);
+            assert_eq!(calendar_year(951_868_800), 2000);
+            assert_eq!(calendar_year(1_703_980_800), 2023);
+            assert_eq!(calendar_year(1_709_164_800), 2024);
+        }
+
+        #[test]
+        fn week_index_clamps_before_epoch() {
+            assert_eq!(week_index(-5), 0);
+            assert_eq!(week_index(SECONDS_PER_WEEK * 3 + 1), 3);
+        }
+
+        #[test]
+        fn draw_winner_index_uses_first_eight_bytes() {
+            let mut randomness = [0xffu8; 32];
+            randomness[..8].copy_from_slice(&10u64.to_le_bytes());
+            assert_eq!(draw_winner_index(&randomness, 3), Some(1));
+            assert_eq!(draw_winner_index(&randomness, 0), None);
+        }
+
+        #[test]
+        fn player_level_starts_at_one() {
+            assert_eq!(player_level(0), 1);
+            assert_eq!(player_level(RACES_PER_LEVEL - 1), 1);
+            assert_eq!(player_level(RACES_PER_LEVEL), 2);
+        }
+
+        #[test]
+        fn expected_score_interpolates_and_clamps() {
+            assert_eq!(expected_score_bps(1_200, 1_200), 5_000);
+            assert_eq!(expected_score_bps(1_200, 1_300), 3_599);
+            assert_eq!(expected_score_bps(1_200, 1_250), 4_300);
+            assert_eq!(expected_score_bps(1_200, 2_500), 99);
+            assert_eq!(expected_score_bps(2_500, 1_200), 9_901);
+        }
+
+        #[test]
+        fn update_rating_moves_by_k_factor_and_floors() {
+            assert_eq!(update_rating(INITIAL_RATING, INITIAL_RATING, 10_000), 1_216);
+            assert_eq!(update_rating(INITIAL_RATING, INITIAL_RATING, 0), 1_184);
+            assert_eq!(update_rating(INITIAL_RATING, INITIAL_RATING, 5_000), INITIAL_RATING);
+            assert_eq!(update_rating(MIN_RATING, MIN_RATING, 0), MIN_RATING);
+        }
+
+        #[test]
+        fn handicap_multiplier_by_rating_band() {
+            assert_eq!(handicap_multiplier_bps(900, 1_000, 12_500, 1_800, 9_500), 12_500);
+            assert_eq!(handicap_multiplier_bps(1_500, 1_000, 12_500, 1_800, 9_500), BPS_DENOMINATOR);
+            assert_eq!(handicap_multiplier_bps(1_900, 1_000, 12_500, 1_800, 9_500), 9_500);
+        }
+
+        #[test]
+        fn staking_and_bps_math() {
+            assert_eq!(staking_reward(5, 3), Some(15));
+            assert_eq!(staking_reward(u64::MAX, 2), None);
+            assert_eq!(apply_bps(1_000, 250), Some(25));
+            assert_eq!(apply_bps(999, 1), Some(0));
+            assert_eq!(apply_bps(u64::MAX, BPS_DENOMINATOR), Some(u64::MAX));
+        }
+
+        #[test]
+        fn burn_share_is_linear_between_supply_bounds() {
+            assert_eq!(burn_share_bps(50, 2_000, 8_000, 100, 200), Some(2_000));
+            assert_eq!(burn_share_bps(150, 2_000, 8_000, 100, 200), Some(5_000));
+            assert_eq!(burn_share_bps(250, 2_000, 8_000, 100, 200), Some(8_000));
+            assert_eq!(burn_share_bps(150, 8_000, 2_000, 100, 200), None);
+        }
+
+        #[test]
+        fn dynamic_entry_fee_premium_and_late_discount() {
+            assert_eq!(dynamic_entry_fee(1_000, 5, 10, 2_000, 0, 0, 100), Some(1_100));
+            assert_eq!(dynamic_entry_fee(1_000, 5, 10, 2_000, 1_000, 60, 30), Some(990));
+            assert_eq!(dynamic_entry_fee(1_000, 5, 10, 2_000, 1_000, 60, 61), Some(1_100));
+            assert_eq!(dynamic_entry_fee(1_000, 0, 0, 2_000, 0, 0, 100), Some(1_000));
+        }
+
+        #[test]
+        fn convert_token_amount_adjusts_decimals() {
+            assert_eq!(convert_token_amount(1_000_000, 1, 1, 6, 9), Some(1_000_000_000));
+            assert_eq!(convert_token_amount(1_000_000_000, 2, 1, 9, 6), Some(2_000_000));
+            assert_eq!(convert_token_amount(1, 1, 1, 9, 6), Some(0));
+            assert_eq!(convert_token_amount(1, 1, 0, 6, 6), None);
+            assert_eq!(convert_token_amount(u64::MAX, 1, 1, 0, 9), None);
+        }
+
+        #[test]
+        fn usd_cents_to_tokens_at_price() {
+            // $0.50 per token: a dollar buys two tokens
+            assert_eq!(usd_cents_to_tokens(100, 6, 50, -2), Some(2_000_000));
+            // $2 per token
+            assert_eq!(usd_cents_to_tokens(100, 6, 2, 0), Some(500_000));
+            assert_eq!(usd_cents_to_tokens(100, 6, 0, 0), None);
+            assert_eq!(usd_cents_to_tokens(100, 6, -1, 0), None);
+        }
+
+        #[test]
+        fn sale_cost_integrates_the_price_curve() {
+            assert_eq!(sale_cost_lamports(1_000, 0, 0, 1_000_000_000, 9), Some(1_000));
+            assert_eq!(sale_cost_lamports(1_000, 0, 0, 1, 9), Some(1));
+            // Price 100 * whole tokens sold, integrated over the first two tokens
+            assert_eq!(sale_cost_lamports(0, 100, 0, 2_000_000_000, 9), Some(200));
+            assert_eq!(sale_cost_lamports(0, 0, 0, 1_000_000_000, 9), Some(0));
+        }
+
+        #[test]
+        fn stream_vests_after_cliff_until_end() {
+            assert_eq!(stream_vested_amount(10, 100, 150, 200, 120), Some(0));
+            assert_eq!(stream_vested_amount(10, 100, 150, 200, 170), Some(700));
+            assert_eq!(stream_vested_amount(10, 100, 150, 200, 300), Some(1_000));
+        }
+
+        #[test]
+        fn leaderboard_order_breaks_ties() {
+            let (low, high) = ([1u8; 32], [2u8; 32]);
+            assert_eq!(leaderboard_order((10, 5, &high), (5, 1, &low)), Ordering::Less);
+            assert_eq!(leaderboard_order((10, 5, &low), (10, 1, &high)), Ordering::Greater);
+            assert_eq!(leaderboard_order((10, 1, &low), (10, 1, &high)), Ordering::Less);
+            assert_eq!(leaderboard_order((10, 1, &low), (10, 1, &low)), Ordering::Equal);
+        }
+
+        #[test]
+        fn voucher_message_layout() {
+            let player = [7u8; 32];
+            let message = voucher_message(&player, 3, 11, 12, 13, 14, 15);
+            assert_eq!(message.len(), 32 + 1 + 8 * 5);
+            assert_eq!(&message[..32], &player);
+            assert_eq!(message[32], 3);
+            assert_eq!(&message[33..41], &11u64.to_le_bytes());
+            assert_eq!(&message[65..], &15u64.to_le_bytes());
+        }
+
+        // Deterministic xorshift stream so the property checks cover the same inputs every run
+        fn samples(seed: u64, count: usize) -> impl Iterator<Item = u64> {
+            let mut state = seed;
+            (0..count).map(move |_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            })
+        }
+
+        #[test]
+        fn sale_cost_is_monotonic_and_never_cheaper_split() {
+            for value in samples(0x5eed, 2_000) {
+                let base = value % 1_000_000;
+                let slope = (value >> 20) % 1_000_000;
+                let sold = (value >> 8) % 1_000_000_000_000;
+                let amount = (value >> 24) % 10_000_000_000;
+                let cost = sale_cost_lamports(base, slope, sold, amount, 9).unwrap();
+                let more = sale_cost_lamports(base, slope, sold, amount + 1, 9).unwrap();
+                assert!(more >= cost);
+                let later = sale_cost_lamports(base, slope, sold + 1, amount, 9).unwrap();
+                assert!(later >= cost);
+
+                let first = amount / 3;
+                let split = sale_cost_lamports(base, slope, sold, first, 9).unwrap()
+                    + sale_cost_lamports(base, slope, sold + first, amount - first, 9).unwrap();
+                assert!(split >= cost);
+            }
+        }
+
+        #[test]
+        fn sale_cost_rounds_up() {
+            for value in samples(0xc0ffee, 2_000) {
+                let base = value % 1_000_000 + 1;
+                let amount = (value >> 20) % 10_000_000_000 + 1;
+                let cost = sale_cost_lamports(base, 0, 0, amount, 9).unwrap();
+                let exact = base as u128 * amount as u128;
+                assert!(cost as u128 * 1_000_000_000 >= exact);
+                assert!((cost as u128 - 1) * 1_000_000_000 < exact);
+            }
+        }
+
+        #[test]
+        fn burn_share_stays_within_floor_and_ceiling() {
+            for value in samples(0xb0b, 2_000) {
+                let floor = (value % BPS_DENOMINATOR as u64) as u16;
+                let ceiling = floor + ((value >> 16) % (BPS_DENOMINATOR - floor + 1) as u64) as u16;
+                let low = (value >> 24) % 1_000_000;
+                let high = low + (value >> 40) % 1_000_000;
+                let circulating = (value >> 12) % 3_000_000;
+                let share = burn_share_bps(circulating, floor, ceiling, low, high).unwrap();
+                assert!(share >= floor && share <= ceiling);
+                let next = burn_share_bps(circulating + 1, floor, ceiling, low, high).unwrap();
+                assert!(next >= share);
+            }
+        }
+
+        #[test]
+        fn fixture_index_is_a_bijection() {
+            for participants in 0..40 {
+                let mut seen = vec![false; fixture_count(participants)];
+                for a in 0..participants {
+                    for b in a + 1..participants {
+                        let index = fixture_index(participants, a, b);
+                        assert!(!seen[index]);
+                        seen[index] = true;
+                        assert_eq!(fixture_index(participants, b, a), index);
+                    }
+                }
+                assert!(seen.iter().all(|hit| *hit));
+            }
+        }
+
+        #[test]
+        fn halving_era_takes_the_later_trigger() {
+            assert_eq!(halving_era(0, 0, 0, 0, 0, 63), 0);
+            assert_eq!(halving_era(250, 100, 0, 0, 0, 63), 2);
+            assert_eq!(halving_era(0, 0, 1_050, 50, 500, 63), 2);
+            assert_eq!(halving_era(250, 100, 1_050, 50, 200, 63), 5);
+            assert_eq!(halving_era(10, 0, 10, 50, 200, 63), 0);
+            assert_eq!(halving_era(u64::MAX, 1, 0, 0, 0, 63), 63);
+        }
+
+        #[test]
+        fn halving_era_never_goes_back() {
+            for value in samples(0xe4a, 2_000) {
+                let every_distributed = value % 1_000;
+                let every_slots = (value >> 12) % 1_000;
+                let start_slot = (value >> 24) % 10_000;
+                let distributed = (value >> 8) % 1_000_000;
+                let slot = (value >> 32) % 1_000_000;
+                let era = halving_era(distributed, every_distributed, slot, start_slot, every_slots, 63);
+                assert!(era <= 63);
+                assert!(halving_era(distributed + 1, every_distributed, slot, start_slot, every_slots, 63) >= era);
+                assert!(halving_era(distributed, every_distributed, slot + 1, start_slot, every_slots, 63) >= era);
+            }
+        }
+    }
+}
+
+// WASM bindings for the web client, built with `--features wasm`. Reward previews, PDA
+// derivation and voucher hashing run the same code the program executes.
+#[cfg(feature = "wasm")]
+pub mod wasm {
+    use super::*;
+    use wasm_bindgen::prelude::*;
+
+    fn to_pubkey(bytes: &[u8]) -> std::result::Result<Pubkey, JsError> {
+        Pubkey::try_from(bytes).map_err(|_| JsError::new("expected a 32-byte public key"))
+    }
+
+    // `rates` = [race_completion, race_win, distance_per_100m, obstacle_avoided, bonus_collected]
+    #[wasm_bindgen(js_name = previewRaceReward)]
+    pub fn preview_race_reward(
+        rates: &[u64],
+        completed: bool,
+        won: bool,
+        distance: u64,
+        obstacles_avoided: u64,
+        bonus_boxes_collected: u64,
+    ) -> std::result::Result<u64, JsError> {
+        let [race_completion, race_win, distance_per_100m, obstacle_avoided, bonus_collected] =
+            <[u64; 5]>::try_from(rates).map_err(|_| JsError::new("expected 5 race rates"))?;
+        let rates = speedy_core::RaceRates {
+            race_completion,
+            race_win,
+            distance_per_100m,
+            obstacle_avoided,
+            bonus_collected,
+        };
+        let race = speedy_core::RacePerformance {
+            completed,
+            won,
+            distance,
+            obstacles_avoided,
+            bonus_boxes_collected,
+        };
+        speedy_core::race_reward(&rates, &race).ok_or_else(|| JsError::new("reward overflow"))
+    }
+
+    #[wasm_bindgen(js_name = previewStakingReward)]
+    pub fn preview_staking_reward(hourly_rate: u64, hours_staked: u64) -> std::result::Result<u64, JsError> {
+        speedy_core::staking_reward(hourly_rate, hours_staked).ok_or_else(|| JsError::new("reward overflow"))
+    }
+
+    #[wasm_bindgen(js_name = applyBps)]
+    pub fn apply_bps(amount: u64, bps: u16) -> std::result::Result<u64, JsError> {
+        speedy_core::apply_bps(amount, bps).ok_or_else(|| JsError::new("amount overflow"))
+    }
+
+    #[wasm_bindgen(js_name = usdCentsToTokens)]
+    pub fn usd_cents_to_tokens(
+        cents: u64,
+        decimals: u8,
+        price: i64,
+        exponent: i32,
+    ) -> std::result::Result<u64, JsError> {
+        speedy_core::usd_cents_to_tokens(cents, decimals, price, exponent)
+            .ok_or_else(|| JsError::new("invalid price or amount overflow"))
+    }
+
+    #[wasm_bindgen(js_name = voucherHash)]
+    pub fn voucher_hash(
+        player: &[u8],
+        reward_type: u8,
+        reference_id: u64,
+        amount: u64,
+        nonce: u64,
+        expires_at: i64,
+        oracle_epoch: u64,
+    ) -> std::result::Result<Vec<u8>, JsError> {
+        let player = to_pubkey(player)?.to_bytes();
+        let message = speedy_core::voucher_message(
+            &player,
+            reward_type,
+            reference_id,
+            amount,
+            nonce,
+            expires_at,
+            oracle_epoch,
+        );
+        Ok(hash(&message).to_bytes().to_vec())
+    }
+
+    // -1 when entry `a` ranks above `b`, 1 when below
+    #[wasm_bindgen(js_name = compareLeaderboardEntries)]
+    pub fn compare_leaderboard_entries(
+        a_score: u64,
+        a_slot: u64,
+        a_player: &[u8],
+        b_score: u64,
+        b_slot: u64,
+        b_player: &[u8],
+    ) -> std::result::Result<i8, JsError> {
+        let a_player = to_pubkey(a_player)?.to_bytes();
+        let b_player = to_pubkey(b_player)?.to_bytes();
+        Ok(speedy_core::leaderboard_order((a_score, a_slot, &a_player), (b_score, b_slot, &b_player)) as i8)
+    }
+
+    #[wasm_bindgen(js_name = leaderboardAddress)]
+    pub fn leaderboard_address(season: u64, track_id: u64) -> Vec<u8> {
+        Pubkey::find_program_address(&[b"leaderboard", &season.to_le_bytes(), &track_id.to_le_bytes()], &crate::ID).0.to_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = gameStateAddress)]
+    pub fn game_state_address() -> Vec<u8> {
+        Pubkey::find_program_address(&[b"game_state"], &crate::ID).0.to_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = raceReceiptAddress)]
+    pub fn race_receipt_address(player: &[u8], race_id: u64) -> std::result::Result<Vec<u8>, JsError> {
+        let player = to_pubkey(player)?;
+        Ok(Pubkey::find_program_address(&[b"race_receipt", player.as_ref(), &race_id.to_le_bytes()], &crate::ID).0.to_bytes().to_vec())
+    }
+
+    #[wasm_bindgen(js_name = registryPageAddress)]
+    pub fn registry_page_address(kind: u8, page: u64) -> Vec<u8> {
+        Pubkey::find_program_address(&[b"registry", &[kind], &page.to_le_bytes()], &crate::ID).0.to_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = playerProfileAddress)]
+    pub fn player_profile_address(player: &[u8]) -> std::result::Result<Vec<u8>, JsError> {
+        let player = to_pubkey(player)?;
+        Ok(Pubkey::find_program_address(&[b"player_profile", player.as_ref()], &crate::ID).0.to_bytes().to_vec())
+    }
+
+    #[wasm_bindgen(js_name = usedVoucherAddress)]
+    pub fn used_voucher_address(voucher_hash: &[u8]) -> Vec<u8> {
+        Pubkey::find_program_address(&[b"used_voucher", voucher_hash], &crate::ID).0.to_bytes().to_vec()
+    }
+
+    #[wasm_bindgen(js_name = attestationAddress)]
+    pub fn attestation_address(attestation_hash: &[u8]) -> Vec<u8> {
+        Pubkey::find_program_address(&[b"attestation", attestation_hash], &crate::ID).0.to_bytes().to_vec()
+    }
+}
+
+// Shared solana-program-test fixture for the `cu-bench` and `invariants` harnesses: a freshly
+// initialized game with a funded vault and an oracle, plus builders for the instructions they
+// drive. Runs the built .so, so run `cargo build-sbf` first. The
+// repo pins no manifest; the dev-dependencies are solana-program-test and solana-sdk 1.18,
+// tokio 1 with the "macros" feature and base64 0.21.
+#[cfg(all(test, any(feature = "cu-bench", feature = "invariants")))]
+#[allow(dead_code)] // Each harness uses part of the fixture
+mod program_harness {
+    use super::*;
+    use anchor_lang::solana_program::{
+        instruction::{AccountMeta, Instruction},
+        system_instruction,
+        sysvar,
+    };
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use anchor_spl::associated_token::{
+        get_associated_token_address,
+        spl_associated_token_account::{self, instruction::create_associated_token_account},
+    };
+    use anchor_spl::token::spl_token;
+    use base64::Engine as _;
+    use solana_program_test::{BanksTransactionResultWithMetadata, ProgramTest, ProgramTestContext};
+    use solana_sdk::{
+        signature::{Keypair, Signer as _},
+        transaction::Transaction,
+    };
+
+    pub const DECIMALS: u8 = 6;
+    pub const SUPPLY: u64 = 1_000_000_000 * 1_000_000; // 1B $SPEEDY minted to the authority
+    pub const VAULT_FUNDING: u64 = 10_000_000 * 1_000_000;
+    const PLAYER_LAMPORTS: u64 = 1_000_000_000; // Rent for the profile, token account and receipts
+
+    pub fn pda(seeds: &[&[u8]]) -> Pubkey {
+        Pubkey::find_program_address(seeds, &crate::ID).0
+    }
+
+    pub fn game_state_address() -> Pubkey {
+        pda(&[b"game_state"])
+    }
+
+    pub fn race_receipt_address(player: &Pubkey, race_id: u64) -> Pubkey {
+        pda(&[b"race_receipt", player.as_ref(), race_id.to_le_bytes().as_ref()])
+    }
+
+    pub fn race_stats(race_id: u64, won: bool, distance: u64) -> RaceStats {
+        RaceStats {
+            race_id,
+            completed: true,
+            won,
+            distance,
+            obstacles_avoided: 12,
+            bonus_boxes_collected: 3,
+            lap_time: 95_000,
+            score: 4_200,
+        }
+    }
+
+    // Decode every `T` event in a transaction's logs
+    pub fn events<T: AnchorDeserialize + Discriminator>(logs: &[String]) -> Vec<T> {
+        logs.iter()
+            .filter_map(|log| log.strip_prefix("Program data: "))
+            .filter_map(|data| base64::engine::general_purpose::STANDARD.decode(data).ok())
+            .filter(|data| data.starts_with(&T::DISCRIMINATOR))
+            .map(|data| T::try_from_slice(&data[8..]).unwrap())
+            .collect()
+    }
+
+    pub struct World {
+        pub context: ProgramTestContext,
+        pub oracle: Keypair,
+        pub mint: Pubkey,
+        pub vault: Pubkey,
+        pub authority_tokens: Pubkey, // Source of `fund_vault`
+    }
+
+    impl World {
+        // A game with the default rates and settings, `SUPPLY` minted to the authority and
+        // `VAULT_FUNDING` of it in the vault
+        pub async fn new() -> World {
+            let mut program_test = ProgramTest::new("speedy_token", crate::ID, None);
+            program_test.prefer_bpf(true);
+            let context = program_test.start_with_context().await;
+
+            let authority = context.payer.pubkey();
+            let mint = Keypair::new();
+            let mut world = World {
+                oracle: Keypair::new(),
+                mint: mint.pubkey(),
+                vault: get_associated_token_address(&game_state_address(), &mint.pubkey()),
+                authority_tokens: get_associated_token_address(&authority, &mint.pubkey()),
+                context,
+            };
+
+            let initialize = Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::InitializeToken {
+                    authority,
+                    token_mint: world.mint,
+                    game_state: game_state_address(),
+                    vault: world.vault,
+                    token_program: spl_token::ID,
+                    associated_token_program: spl_associated_token_account::ID,
+                    system_program: anchor_lang::system_program::ID,
+                    rent: sysvar::rent::ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::InitializeToken { _decimals: DECIMALS }.data(),
+            };
+            world.process(&[initialize], &[&mint]).await.result.unwrap();
+
+            let mint_supply = [
+                create_associated_token_account(&authority, &authority, &world.mint, &spl_token::ID),
+                spl_token::instruction::mint_to(&spl_token::ID, &world.mint, &world.authority_tokens, &authority, &[], SUPPLY)
+                    .unwrap(),
+            ];
+            world.process(&mint_supply, &[]).await.result.unwrap();
+
+            let set_oracle = world.admin_instruction(crate::instruction::SetOracle { oracle: world.oracle.pubkey() }.data());
+            let fund = world.fund_vault_instruction(VAULT_FUNDING);
+            world.process(&[set_oracle, fund], &[]).await.result.unwrap();
+            world
+        }
+
+        // Send `instructions` paid by the authority, on a fresh blockhash so identical retries
+        // are executed again instead of being deduplicated
+        pub async fn process(
+            &mut self,
+            instructions: &[Instruction],
+            signers: &[&Keypair],
+        ) -> BanksTransactionResultWithMetadata {
+            let transaction = self.transaction(instructions, signers).await;
+            self.context.banks_client.process_transaction_with_metadata(transaction).await.unwrap()
+        }
+
+        // Simulate `instructions` and return the compute units they used
+        pub async fn units_consumed(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> u64 {
+            let transaction = self.transaction(instructions, signers).await;
+            let simulation = self.context.banks_client.simulate_transaction(transaction).await.unwrap();
+            if let Some(Err(error)) = simulation.result {
+                panic!("instruction failed: {error}");
+            }
+            simulation.simulation_details.unwrap().units_consumed
+        }
+
+        async fn transaction(&mut self, instructions: &[Instruction], signers: &[&Keypair]) -> Transaction {
+            let blockhash = self.context.get_new_latest_blockhash().await.unwrap();
+            let mut all_signers = vec![&self.context.payer];
+            all_signers.extend_from_slice(signers);
+            Transaction::new_signed_with_payer(instructions, Some(&self.context.payer.pubkey()), &all_signers, blockhash)
+        }
+
+        // A player wallet with enough SOL to pay for the accounts awards create
+        pub async fn new_player(&mut self) -> Keypair {
+            let player = Keypair::new();
+            let fund = system_instruction::transfer(&self.context.payer.pubkey(), &player.pubkey(), PLAYER_LAMPORTS);
+            self.process(&[fund], &[]).await.result.unwrap();
+            player
+        }
+
+        pub async fn advance_clock(&mut self, secs: i64) {
+            let mut clock: Clock = self.context.banks_client.get_sysvar().await.unwrap();
+            clock.unix_timestamp += secs;
+            self.context.set_sysvar(&clock);
+        }
+
+        pub async fn game_state(&mut self) -> GameState {
+            let account = self.context.banks_client.get_account(game_state_address()).await.unwrap().unwrap();
+            GameState::try_deserialize(&mut account.data.as_slice()).unwrap()
+        }
+
+        pub async fn race_receipt(&mut self, player: &Pubkey, race_id: u64) -> Option<RaceReceipt> {
+            let account = self.context.banks_client.get_account(race_receipt_address(player, race_id)).await.unwrap()?;
+            Some(RaceReceipt::try_deserialize(&mut account.data.as_slice()).unwrap())
+        }
+
+        pub async fn token_balance(&mut self, address: Pubkey) -> u64 {
+            match self.context.banks_client.get_account(address).await.unwrap() {
+                Some(account) => TokenAccount::try_deserialize(&mut account.data.as_slice()).unwrap().amount,
+                None => 0,
+            }
+        }
+
+        // An UpdateGameState instruction signed by the authority
+        pub fn admin_instruction(&self, data: Vec<u8>) -> Instruction {
+            Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::UpdateGameState {
+                    authority: self.context.payer.pubkey(),
+                    game_state: game_state_address(),
+                    rate_history: None,
+                }
+                .to_account_metas(None),
+                data,
+            }
+        }
+
+        pub fn dispute_window_instruction(&self, window_secs: i64) -> Instruction {
+            let dispute = DisputeConfig { window_secs, reviewer: Pubkey::default(), challenge_bond: 0 };
+            self.admin_instruction(crate::instruction::UpdateDisputeConfig { dispute }.data())
+        }
+
+        pub fn fund_vault_instruction(&self, amount: u64) -> Instruction {
+            Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::FundVault {
+                    authority: self.context.payer.pubkey(),
+                    game_state: game_state_address(),
+                    role_assignment: None,
+                    authority_token_account: self.authority_tokens,
+                    vault: self.vault,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::FundVault { amount }.data(),
+            }
+        }
+
+        // `award_race_tokens`, or `settle_race` when `settle` is set, signed by the player and
+        // the oracle. Always commits a race receipt.
+        pub fn award_instruction(&self, player: &Pubkey, race_stats: RaceStats, settle: bool) -> Instruction {
+            let race_id = race_stats.race_id;
+            let data = if settle {
+                crate::instruction::SettleRace { game_mode: GameMode::Circuit(race_stats), replay_hash: None }.data()
+            } else {
+                crate::instruction::AwardRaceTokens { race_stats, replay_hash: None }.data()
+            };
+            Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::AwardTokens {
+                    player: *player,
+                    oracle: self.oracle.pubkey(),
+                    game_state: game_state_address(),
+                    role_assignment: None,
+                    token_mint: self.mint,
+                    vault: self.vault,
+                    player_token_account: get_associated_token_address(player, &self.mint),
+                    player_profile: pda(&[b"player_profile", player.as_ref()]),
+                    price_feed: None,
+                    race_receipt: Some(race_receipt_address(player, race_id)),
+                    track: None,
+                    track_owner_nft: None,
+                    track_owner_token_account: None,
+                    rent_treasury: None,
+                    cosigner: None,
+                    memo_program: None,
+                    token_program: spl_token::ID,
+                    associated_token_program: spl_associated_token_account::ID,
+                    system_program: anchor_lang::system_program::ID,
+                    rent: sysvar::rent::ID,
+                    player_index: pda(&[b"player_index", player.as_ref()]),
+                }
+                .to_account_metas(None),
+                data,
+            }
+        }
+
+        // Permissionless release of a held award
+        pub fn release_instruction(&self, player: &Pubkey, race_id: u64) -> Instruction {
+            Instruction {
+                program_id: crate::ID,
+                accounts: crate::accounts::ReleaseAward {
+                    game_state: game_state_address(),
+                    player: *player,
+                    race_receipt: race_receipt_address(player, race_id),
+                    vault: self.vault,
+                    player_token_account: get_associated_token_address(player, &self.mint),
+                    memo_program: None,
+                    token_program: spl_token::ID,
+                }
+                .to_account_metas(None),
+                data: crate::instruction::ReleaseAward { race_id }.data(),
+            }
+        }
+
+        // `claim_everything` signed by the player, sweeping the given races' receipts
+        pub fn claim_everything_instruction(&self, player: &Pubkey, race_ids: &[u64]) -> Instruction {
+            let mut accounts = crate::accounts::ClaimEverything {
+                player: *player,
+                game_state: game_state_address(),
+                player_profile: pda(&[b"player_profile", player.as_ref()]),
+                token_mint: self.mint,
+                vault: self.vault,
+                player_token_account: get_associated_token_address(player, &self.mint),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: anchor_lang::system_program::ID,
+                player_index: pda(&[b"player_index", player.as_ref()]),
+            }
+            .to_account_metas(None);
+            accounts.extend(race_ids.iter().map(|race_id| AccountMeta::new(race_receipt_address(player, *race_id), false)));
+            Instruction {
+                program_id: crate::ID,
+                accounts,
+                data: crate::instruction::ClaimEverything {}.data(),
+            }
+        }
+    }
+}
+
+// Randomized economy invariants, built with `--features invariants` on top of
+// `program_harness`. Seeded sequences of awards, settlements, dispute-window changes, releases,
+// claims and vault top-ups run across a few players, and every transaction is checked against:
+// - the vault losing exactly what the transaction's reward events say it paid out,
+// - total_distributed never decreasing,
+// - each (player, race) receipted at most once, and everything paid out accounted for by
+//   receipts marked paid, so no award is paid twice.
+#[cfg(all(test, feature = "invariants"))]
+mod invariants {
+    use super::program_harness::*;
+    use super::*;
+    use solana_sdk::signature::{Keypair, Signer as _};
+
+    const SEEDS: [u64; 4] = [1, 7, 42, 2024];
+    const STEPS: usize = 60;
+    const PLAYERS: usize = 3;
+    const RACE_IDS: u64 = 8; // Few race ids, so repeated awards come up often
+    const DISPUTE_WINDOW_SECS: i64 = 60;
+
+    // xorshift64, so a failing seed replays exactly
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, bound: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % bound
+        }
+    }
+
+    // Tokens a transaction's events say left the vault. TokenRewardV2 repeats TokenReward, and
+    // settle_race reports through RaceSettled instead.
+    fn emitted_payouts(logs: &[String]) -> u64 {
+        let rewards: u64 = events::<TokenReward>(logs).iter().map(|reward| reward.amount).sum();
+        let settled: u64 = events::<RaceSettled>(logs).iter()
+            .filter(|settled| settled.status == AwardStatus::Paid && !settled.auto_staked)
+            .map(|settled| settled.amount)
+            .sum();
+        let claimed: u64 = events::<EverythingClaimed>(logs).iter()
+            .map(|claim| claim.race_awards + claim.unstaked + claim.vested - claim.loan_repaid)
+            .sum();
+        rewards + settled + claimed
+    }
+
+    #[tokio::test]
+    async fn randomized_sequences() {
+        for seed in SEEDS {
+            run(seed).await;
+        }
+    }
+
+    async fn run(seed: u64) {
+        let mut rng = Rng(seed);
+        let mut world = World::new().await;
+        let oracle = world.oracle.insecure_clone();
+        let mut players: Vec<Keypair> = Vec::new();
+        for _ in 0..PLAYERS {
+            players.push(world.new_player().await);
+        }
+        let mut receipted: Vec<(Pubkey, u64)> = Vec::new();
+        let mut total_outflow = 0u64;
+
+        for step in 0..STEPS {
+            let context = format!("seed {seed}, step {step}");
+            let player = &players[rng.below(PLAYERS as u64) as usize];
+            let player_key = player.pubkey();
+            let race_id = rng.below(RACE_IDS);
+            let vault_before = world.token_balance(world.vault).await;
+            let distributed_before = world.game_state().await.total_distributed;
+
+            let mut funded = 0;
+            let mut awarded_race = None;
+            let (instruction, signers): (_, Vec<&Keypair>) = match rng.below(7) {
+                0..=2 => {
+                    let stats = race_stats(race_id, rng.below(2) == 0, rng.below(10_000));
+                    awarded_race = Some(race_id);
+                    (world.award_instruction(&player_key, stats, rng.below(2) == 0), vec![player, &oracle])
+                }
+                3 => {
+                    funded = 1 + rng.below(1_000 * 1_000_000);
+                    (world.fund_vault_instruction(funded), vec![])
+                }
+                4 => {
+                    let window = if rng.below(2) == 0 { 0 } else { DISPUTE_WINDOW_SECS };
+                    (world.dispute_window_instruction(window), vec![])
+                }
+                5 => {
+                    world.advance_clock(DISPUTE_WINDOW_SECS / 2).await;
+                    (world.release_instruction(&player_key, race_id), vec![])
+                }
+                _ => {
+                    world.advance_clock(DISPUTE_WINDOW_SECS / 2).await;
+                    let race_ids: Vec<u64> = receipted.iter()
+                        .filter(|(owner, _)| *owner == player_key)
+                        .map(|(_, race_id)| *race_id)
+                        .collect();
+                    (world.claim_everything_instruction(&player_key, &race_ids), vec![player])
+                }
+            };
+            let outcome = world.process(&[instruction], &signers).await;
+            let succeeded = outcome.result.is_ok();
+            let funded = if succeeded { funded } else { 0 };
+            let logs = outcome.metadata.map(|metadata| metadata.log_messages).unwrap_or_default();
+
+            // The vault only loses what the emitted rewards account for
+            let vault_after = world.token_balance(world.vault).await;
+            let outflow = (vault_before + funded).checked_sub(vault_after)
+                .unwrap_or_else(|| panic!("{context}: vault gained tokens nobody funded"));
+            if succeeded {
+                assert_eq!(outflow, emitted_payouts(&logs), "{context}: vault outflow differs from emitted rewards");
+            } else {
+                assert_eq!(outflow, 0, "{context}: failed transaction moved vault tokens");
+            }
+            total_outflow += outflow;
+
+            // total_distributed only grows
+            let distributed_after = world.game_state().await.total_distributed;
+            assert!(distributed_after >= distributed_before, "{context}: total_distributed decreased");
+
+            // One receipt per (player, race); a repeat award must fail
+            if let Some(race_id) = awarded_race {
+                let repeat = receipted.contains(&(player_key, race_id));
+                if repeat {
+                    assert!(!succeeded, "{context}: race {race_id} awarded twice");
+                } else if succeeded {
+                    let receipt = world.race_receipt(&player_key, race_id).await
+                        .unwrap_or_else(|| panic!("{context}: award without a receipt"));
+                    assert!(receipt.player == player_key && receipt.race_id == race_id, "{context}: receipt mismatch");
+                    receipted.push((player_key, race_id));
+                }
+            }
+
+            // Every token paid out belongs to exactly one receipt marked paid
+            let mut paid_receipts = 0u64;
+            for (owner, race_id) in receipted.iter() {
+                let receipt = world.race_receipt(owner, *race_id).await.unwrap();
+                if receipt.status == AwardStatus::Paid {
+                    paid_receipts += receipt.amount;
+                }
+            }
+            assert_eq!(total_outflow, paid_receipts, "{context}: payouts differ from paid receipts");
+        }
+    }
+}
+
+// Compute-unit benchmarks, built with `--features cu-bench` on top of `program_harness`. Each
+// case simulates one instruction against the built program and fails once it uses more than
+// its budget in `CU_BUDGETS`, so a formula or account-layout change that creeps toward the
+// compute limit is caught before deploy. The award cases run a player's first race, which
+// also creates the profile and the receipt, so they measure the most expensive path.
+#[cfg(all(test, feature = "cu-bench"))]
+mod cu_bench {
+    use super::program_harness::*;
+    use anchor_lang::solana_program::instruction::Instruction;
+    use anchor_lang::{InstructionData, ToAccountMetas};
+    use solana_sdk::signature::Signer as _;
+
+    // Default per-instruction compute limit. Clients send awards and settlements without a
+    // compute budget instruction, so settle_race in particular must fit under it.
+    const MAX_INSTRUCTION_CU: u64 = 200_000;
+
+    // Compute units each benchmarked instruction may use; raise one only with the change
+    // that needs it
+    const CU_BUDGETS: &[(&str, u64)] = &[
+        ("create_player_index", 15_000),
+        ("award_race_tokens", 150_000),
+        ("settle_race", 130_000),
+        ("settle_race_pending", 90_000),
+        ("release_award", 60_000),
+        ("claim_everything", 90_000),
+    ];
+
+    const DISPUTE_WINDOW_SECS: i64 = 60;
+    const CLAIMED_RACES: u64 = 4; // Receipts swept by the claim_everything case
+
+    fn assert_within_budget(name: &str, units: u64) {
+        let (_, budget) = CU_BUDGETS.iter()
+            .find(|(budgeted, _)| *budgeted == name)
+            .unwrap_or_else(|| panic!("no compute budget for {name}"));
+        assert!(units <= *budget, "{name} used {units} CU, budget is {budget}");
+    }
+
+    #[test]
+    fn budgets_fit_the_default_limit() {
+        for (name, budget) in CU_BUDGETS {
+            assert!(*budget <= MAX_INSTRUCTION_CU, "{name} budget {budget} exceeds {MAX_INSTRUCTION_CU} CU");
+        }
+    }
+
+    #[tokio::test]
+    async fn create_player_index() {
+        let mut world = World::new().await;
+        let player = world.context.payer.pubkey();
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts: crate::accounts::CreatePlayerIndex {
+                player,
+                player_index: pda(&[b"player_index", player.as_ref()]),
+                system_program: anchor_lang::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: crate::instruction::CreatePlayerIndex {}.data(),
+        };
+
+        let units = world.units_consumed(&[instruction], &[]).await;
+        assert_within_budget("create_player_index", units);
+    }
+
+    #[tokio::test]
+    async fn award_race_tokens() {
+        let mut world = World::new().await;
+        let player = world.new_player().await;
+        let oracle = world.oracle.insecure_clone();
+        let award = world.award_instruction(&player.pubkey(), race_stats(1, true, 5_000), false);
+
+        let units = world.units_consumed(&[award], &[&player, &oracle]).await;
+        assert_within_budget("award_race_tokens", units);
+    }
+
+    #[tokio::test]
+    async fn settle_race() {
+        let mut world = World::new().await;
+        let player = world.new_player().await;
+        let oracle = world.oracle.insecure_clone();
+        let settle = world.award_instruction(&player.pubkey(), race_stats(1, true, 5_000), true);
+
+        let units = world.units_consumed(&[settle], &[&player, &oracle]).await;
+        assert_within_budget("settle_race", units);
+    }
+
+    #[tokio::test]
+    async fn settle_race_pending() {
+        let mut world = World::new().await;
+        let player = world.new_player().await;
+        let oracle = world.oracle.insecure_clone();
+        let dispute = world.dispute_window_instruction(DISPUTE_WINDOW_SECS);
+        world.process(&[dispute], &[]).await.result.unwrap();
+        let settle = world.award_instruction(&player.pubkey(), race_stats(1, true, 5_000), true);
+
+        let units = world.units_consumed(&[settle], &[&player, &oracle]).await;
+        assert_within_budget("settle_race_pending", units);
+    }
+
+    #[tokio::test]
+    async fn release_award() {
+        let mut world = World::new().await;
+        let player = world.new_player().await;
+        let oracle = world.oracle.insecure_clone();
+        let dispute = world.dispute_window_instruction(DISPUTE_WINDOW_SECS);
+        world.process(&[dispute], &[]).await.result.unwrap();
+        let settle = world.award_instruction(&player.pubkey(), race_stats(1, true, 5_000), true);
+        world.process(&[settle], &[&player, &oracle]).await.result.unwrap();
+        world.advance_clock(DISPUTE_WINDOW_SECS).await;
+
+        let release = world.release_instruction(&player.pubkey(), 1);
+        let units = world.units_consumed(&[release], &[]).await;
+        assert_within_budget("release_award", units);
+    }
+
+    #[tokio::test]
+    async fn claim_everything() {
+        let mut world = World::new().await;
+        let player = world.new_player().await;
+        let oracle = world.oracle.insecure_clone();
+        let dispute = world.dispute_window_instruction(DISPUTE_WINDOW_SECS);
+        world.process(&[dispute], &[]).await.result.unwrap();
+        let race_ids: Vec<u64> = (1..=CLAIMED_RACES).collect();
+        for race_id in race_ids.iter() {
+            let settle = world.award_instruction(&player.pubkey(), race_stats(*race_id, true, 5_000), true);
+            world.process(&[settle], &[&player, &oracle]).await.result.unwrap();
+        }
+        world.advance_clock(DISPUTE_WINDOW_SECS).await;
+
+        let claim = world.claim_everything_instruction(&player.pubkey(), &race_ids);
+        let units = world.units_consumed(&[claim], &[&player]).await;
+        assert_within_budget("claim_everything", units);
+    }
+}
+
+// List a newly created account on the current registry page of its kind
+fn append_to_registry(
+    game_state: &mut GameState,
+    registry_page: &mut RegistryPage,
+    registry_page_bump: u8,
+    kind: RegistryKind,
+    address: Pubkey,
+) -> Result<()> {
+    let count = game_state.registry_counts[kind as usize];
+    if registry_page.entries.is_empty() {
+        registry_page.kind = kind;
+        registry_page.page = game_state.registry_page_index(kind);
+        registry_page.bump = registry_page_bump;
+    }
+    registry_page.entries.push(address);
+    game_state.registry_counts[kind as usize] = count.checked_add(1)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    Ok(())
+}
+
+// Fail unless `account` is a program account with the given discriminator and allocated size
+fn verify_account_layout(account: &AccountInfo, discriminator: [u8; 8], expected_len: usize) -> Result<()> {
+    let data = account.try_borrow_data()?;
+    require!(
+        account.owner == &crate::ID && data.len() == expected_len && data.starts_with(&discriminator),
+        ErrorCode::LayoutMismatch
+    );
+    Ok(())
+}
+
+// Current unix time. Builds with the `test-clock` feature read it from the TestClock PDA
+// when that account is passed among the remaining accounts, so integration tests and devnet
+// previews can drive time deterministically. Instructions that consume their remaining
+// accounts positionally (league finalization, buybacks) always use the sysvar.
+#[cfg(feature = "test-clock")]
+fn current_timestamp(remaining_accounts: &[AccountInfo]) -> Result<i64> {
+    let (test_clock_key, _) = Pubkey::find_program_address(&[b"test_clock"], &crate::ID);
+    match remaining_accounts.iter().find(|account| account.key == &test_clock_key) {
+        Some(account) => Ok(TestClock::try_deserialize(&mut &account.try_borrow_data()?[..])?.unix_timestamp),
+        None => Ok(Clock::get()?.unix_timestamp),
+    }
+}
+
+#[cfg(not(feature = "test-clock"))]
+fn current_timestamp(_remaining_accounts: &[AccountInfo]) -> Result<i64> {
+    Ok(Clock::get()?.unix_timestamp)
+}
+
+// Tip the caller of a permissionless crank from the vault. Skipped without failing the crank
+// when no tip account is given, tips are disabled, payouts are paused, the crank kind is on
+// cooldown or the vault can't cover it on top of what it owes players.
+fn pay_crank_tip<'info>(
+    game_state: &mut Account<'info, GameState>,
+    kind: CrankKind,
+    vault: &Account<'info, TokenAccount>,
+    tip_account: Option<&Account<'info, TokenAccount>>,
+    token_program: AccountInfo<'info>,
+    now: i64,
+) -> Result<()> {
+    let Some(tip_account) = tip_account else {
+        return Ok(());
+    };
+    let tip = game_state.crank_tip;
+    let last_tip_at = game_state.last_crank_tip_at[kind as usize];
+    if tip.amount == 0
+        || game_state.paused
+        || now < last_tip_at.saturating_add(tip.cooldown_secs)
+        || !game_state.vault_covers(vault.amount, tip.amount, 0)
+    {
+        return Ok(());
+    }
+
+    game_state.last_crank_tip_at[kind as usize] = now;
+    game_state.record_distribution(tip.amount)?;
+    transfer_tokens_from_vault(
+        game_state.to_account_info(),
+        vault.to_account_info(),
+        tip_account.to_account_info(),
+        token_program,
+        tip.amount,
+        game_state.bump,
+    )?;
+
+    emit!(CrankTipPaid {
+        kind,
+        recipient: tip_account.key(),
+        amount: tip.amount,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Shared race payout path: strategy reward, USD conversion, probation, transfer and event
+// `settle` swaps the per-payout memo and reward events for one compact RaceSettled event
+fn process_race_award(
+    ctx: Context<AwardTokens>,
+    strategy: &dyn RewardStrategy,
+    replay_hash: Option<[u8; 32]>,
+    settle: bool,
+) -> Result<()> {
+    let game_state = &ctx.accounts.game_state;
+    let now = current_timestamp(ctx.remaining_accounts)?;
+    game_state.require_live_oracle(ctx.accounts.cosigner.as_ref(), now)?;
+    game_state.require_recent_solvency(now)?;
+
+    // Newly created profiles on probation receive a reduced share; the remainder stays in the vault
+    let player_profile = &mut ctx.accounts.player_profile;
+    if init_player_profile_if_needed(player_profile, ctx.accounts.player.key(), ctx.bumps.player_profile, now) {
+        front_rent(ctx.accounts.rent_treasury.as_mut(), &ctx.accounts.player.to_account_info(), RentSubsystem::PlayerProfiles, space::PLAYER_PROFILE)?;
+    }
+
+    let total_reward = compute_race_award(
+        game_state,
+        strategy,
+        player_profile,
+        ctx.accounts.price_feed.as_ref(),
+        ctx.accounts.token_mint.decimals,
+        now,
+    )?;
+    let mut total_reward = apply_probation(player_profile, &game_state.probation, total_reward, RewardType::RaceCompletion, now)?;
+    game_state.require_cosign(ctx.accounts.cosigner.as_ref(), total_reward)?;
+
+    // Check if vault has sufficient balance
+    ctx.accounts.game_state.require_vault_covers(ctx.accounts.vault.amount, total_reward, 0)?;
+
+    // Inside a dispute window the award is only recorded; `release_award` pays it later
+    let dispute_window = game_state.dispute.window_secs;
+    if dispute_window > 0 {
+        let race_receipt = ctx.accounts.race_receipt.as_ref()
+            .ok_or(ErrorCode::InvalidRaceReceipt)?;
+        record_race_receipt(
+            race_receipt,
+            &ctx.accounts.player,
+            ctx.accounts.rent_treasury.as_mut(),
+            &ctx.accounts.system_program,
+            RaceReceipt {
+                player: ctx.accounts.player.key(),
+                race_id: strategy.race_id(),
+                amount: total_reward,
+                replay_hash,
+                awarded_at: now,
+                status: AwardStatus::Pending,
+                release_at: now.checked_add(dispute_window).ok_or(ErrorCode::InvalidRewardAmount)?,
+                challenger: Pubkey::default(),
+                challenge_bond: 0,
+                rent_fronted: false,
+                bump: 0,
+            },
+        )?;
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.total_pending_awards = game_state.total_pending_awards.checked_add(total_reward)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        if settle {
+            emit_race_settled(player_profile, strategy.race_id(), total_reward, AwardStatus::Pending, false, now);
+        }
+        log_msg!("Award of {} $SPEEDY pending until the dispute window closes", total_reward);
+        return Ok(());
+    }
+
+    // A community track's owner takes a cut of the award once it is final
+    if let Some(track) = ctx.accounts.track.as_mut() {
+        let cut = pay_track_owner_cut(
+            &mut ctx.accounts.game_state,
+            track,
+            ctx.accounts.track_owner_nft.as_ref(),
+            ctx.accounts.track_owner_token_account.as_ref(),
+            &ctx.accounts.vault,
+            ctx.accounts.token_program.to_account_info(),
+            total_reward,
+        )?;
+        if cut > 0 {
+            total_reward -= cut;
+            ctx.accounts.vault.reload()?;
+            emit!(TrackOwnerPaid {
+                track_id: track.track_id,
+                owner: ctx.accounts.track_owner_token_account.as_ref().map(|account| account.owner).unwrap_or_default(),
+                amount: cut,
+                timestamp: now,
+            });
+        }
+    }
+
+    // Auto-staked awards stay in the vault but are receipted like paid ones
+    let staked = player_profile.auto_stake;
+    if staked {
+        auto_stake_reward(&mut ctx.accounts.game_state, player_profile, ctx.accounts.vault.amount, total_reward, now)?;
+    } else {
+        // Transfer tokens from vault to player
+        transfer_tokens_from_vault(
+            ctx.accounts.game_state.to_account_info(),
+            ctx.accounts.vault.to_account_info(),
+            ctx.accounts.player_token_account.to_account_info(),
+            ctx.accounts.token_program.to_account_info(),
+            total_reward,
+            ctx.accounts.game_state.bump,
+        )?;
+
+        // Update game statistics - using checked arithmetic
+        let game_state = &mut ctx.accounts.game_state;
+        game_state.record_distribution(total_reward)?;
+
+        // Log transaction
+        if !settle {
+            emit_token_reward(
+                TokenReward {
+                    player: ctx.accounts.player.key(),
+                    amount: total_reward,
+                    reward_type: RewardType::RaceCompletion,
+                    race_id: strategy.race_id(),
+                    timestamp: now,
+                },
+                &ctx.accounts.vault.to_account_info(),
+                ctx.accounts.vault.amount,
+                &ctx.accounts.player_token_account.to_account_info(),
+                ctx.accounts.memo_program.as_ref(),
+                game_state.current_era()?,
+            )?;
+        }
+    }
+
+    match ctx.accounts.race_receipt.as_ref() {
+        Some(race_receipt) => record_race_receipt(
+            race_receipt,
+            &ctx.accounts.player,
+            ctx.accounts.rent_treasury.as_mut(),
+            &ctx.accounts.system_program,
+            RaceReceipt {
+                player: ctx.accounts.player.key(),
+                race_id: strategy.race_id(),
+                amount: total_reward,
+                replay_hash,
+                awarded_at: now,
+                status: AwardStatus::Paid,
+                release_at: now,
+                challenger: Pubkey::default(),
+                challenge_bond: 0,
+                rent_fronted: false,
+                bump: 0,
+            },
+        )?,
+        None => require!(replay_hash.is_none(), ErrorCode::InvalidRaceReceipt),
+    }
+
+    if settle {
+        emit_race_settled(&ctx.accounts.player_profile, strategy.race_id(), total_reward, AwardStatus::Paid, staked, now);
+    }
+    log_msg!("{} {} $SPEEDY tokens for {}", if staked { "Auto-staked" } else { "Awarded" }, total_reward, strategy.name());
+    Ok(())
+}
+
+fn emit_race_settled(
+    player_profile: &PlayerProfile,
+    race_id: u64,
+    amount: u64,
+    status: AwardStatus,
+    auto_staked: bool,
+    now: i64,
+) {
+    emit!(RaceSettled {
+        player: player_profile.player,
+        race_id,
+        amount,
+        status,
+        auto_staked,
+        races_completed: player_profile.races_completed,
+        rating: player_profile.rating,
+        timestamp: now,
+    });
+}
+
+// Lock an award plus the auto-stake bonus in the player's staked balance. Nothing leaves the
+// vault until `unstake_winnings`, so the staked total is counted as a vault liability.
+fn auto_stake_reward(
+    game_state: &mut GameState,
+    player_profile: &mut PlayerProfile,
+    vault_balance: u64,
+    reward_amount: u64,
+    now: i64,
+) -> Result<()> {
+    let bonus = (reward_amount as u128)
+        .checked_mul(game_state.auto_stake.bonus_bps as u128)
+        .and_then(|v| v.checked_div(BPS_DENOMINATOR as u128))
+        .and_then(|v| u64::try_from(v).ok())
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    let staked = reward_amount.checked_add(bonus).ok_or(ErrorCode::InvalidRewardAmount)?;
+
+    // Check if vault has sufficient balance
+    game_state.require_vault_covers(vault_balance, staked, 0)?;
+
+    player_profile.auto_staked = player_profile.auto_staked.checked_add(staked)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    player_profile.auto_stake_unlock_at = now.checked_add(game_state.auto_stake.lock_secs)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    game_state.total_auto_staked = game_state.total_auto_staked.checked_add(staked)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+    emit!(RewardAutoStaked {
+        player: player_profile.player,
+        amount: reward_amount,
+        bonus,
+        unlock_at: player_profile.auto_stake_unlock_at,
+        timestamp: now,
+    });
+    Ok(())
+}
+
+// Pay a track owner's cut of a race award from the vault and return the cut. The owner is
+// whoever holds the track's ownership NFT; tracks without one take no cut.
+fn pay_track_owner_cut<'info>(
+    game_state: &mut Account<'info, GameState>,
+    track: &mut Account<'info, Track>,
+    owner_nft: Option<&Account<'info, TokenAccount>>,
+    owner_token_account: Option<&Account<'info, TokenAccount>>,
+    vault: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<u64> {
+    if track.owner_mint == Pubkey::default() || track.owner_cut_bps == 0 {
+        return Ok(0);
+    }
+    let (Some(owner_nft), Some(owner_token_account)) = (owner_nft, owner_token_account) else {
+        return err!(ErrorCode::InvalidTrackOwner);
+    };
+    require!(
+        owner_nft.mint == track.owner_mint
+            && owner_nft.amount == 1
+            && owner_token_account.owner == owner_nft.owner
+            && owner_token_account.mint == game_state.token_mint,
+        ErrorCode::InvalidTrackOwner
+    );
+
+    let cut = speedy_core::apply_bps(amount, track.owner_cut_bps)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    if cut == 0 {
+        return Ok(0);
+    }
+    transfer_tokens_from_vault(
+        game_state.to_account_info(),
+        vault.to_account_info(),
+        owner_token_account.to_account_info(),
+        token_program,
+        cut,
+        game_state.bump,
+    )?;
+    game_state.record_distribution(cut)?;
+    track.total_owner_paid = track.total_owner_paid.checked_add(cut)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    track.races_paid = track.races_paid.saturating_add(1);
+    Ok(cut)
+}
+
+// Welcome bonus in tokens at the current rates. When an attestation verifier is configured,
+// each attestation pays out the welcome bonus once.
+fn welcome_bonus_amount(
+    game_state: &GameState,
+    attestation: Option<&mut Account<Attestation>>,
+    price_feed: Option<&UncheckedAccount>,
+    decimals: u8,
+    now: i64,
+) -> Result<u64> {
+    if game_state.attestation_verifier != Pubkey::default() {
+        let attestation = attestation.ok_or(ErrorCode::AttestationRequired)?;
+        require!(
+            !attestation.welcome_bonus_claimed,
+            ErrorCode::WelcomeBonusAlreadyClaimed
+        );
+        attestation.welcome_bonus_claimed = true;
+    }
+
+    // Convert USD-denominated rewards at the oracle price
+    usd_to_token_amount(game_state, price_feed, game_state.active_rates()?.welcome_bonus, decimals, now)
+}
+
+// Create a PDA of `size` bytes owned by `owner`. PDA addresses are predictable, so anyone can
+// send lamports to one before it is created, and `create_account` then fails for good. An
+// already funded address is topped up to rent exemption, then allocated and assigned instead.
+// `signer_seeds` must cover the new account and, when the payer is a PDA, the payer.
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    size: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(size);
+    let funded = account.lamports();
+    if funded == 0 {
+        return anchor_lang::system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            rent,
+            size as u64,
+            owner,
+        );
+    }
+
+    let top_up = rent.saturating_sub(funded);
+    if top_up > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+                signer_seeds,
+            ),
+            top_up,
+        )?;
+    }
+    anchor_lang::system_program::allocate(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Allocate { account_to_allocate: account.clone() },
+            signer_seeds,
+        ),
+        size as u64,
+    )?;
+    anchor_lang::system_program::assign(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            anchor_lang::system_program::Assign { account_to_assign: account.clone() },
+            signer_seeds,
+        ),
+        owner,
+    )
+}
+
+// Create the receipt PDA for a race award, paid by the player and fronted by the rent treasury
+// when one is passed. A race can only be receipted once, which also rejects repeat awards for
+// the same race id.
+fn record_race_receipt<'info>(
+    race_receipt: &UncheckedAccount<'info>,
+    payer: &Signer<'info>,
+    rent_treasury: Option<&mut Account<'info, RentTreasury>>,
+    system_program: &Program<'info, System>,
+    mut receipt: RaceReceipt,
+) -> Result<()> {
+    let race_id_bytes = receipt.race_id.to_le_bytes();
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"race_receipt", receipt.player.as_ref(), race_id_bytes.as_ref()],
+        &crate::ID,
+    );
+    require!(race_receipt.key() == expected, ErrorCode::InvalidRaceReceipt);
+    require!(race_receipt.data_is_empty(), ErrorCode::RaceAlreadyReceipted);
+
+    let receipt_seeds = &[b"race_receipt".as_ref(), receipt.player.as_ref(), race_id_bytes.as_ref(), &[bump]];
+    create_pda_account(
+        &payer.to_account_info(),
+        &race_receipt.to_account_info(),
+        &system_program.to_account_info(),
+        space::RACE_RECEIPT,
+        &crate::ID,
+        &[&receipt_seeds[..]],
+    )?;
+
+    receipt.rent_fronted = front_rent(rent_treasury, &payer.to_account_info(), RentSubsystem::RaceReceipts, space::RACE_RECEIPT)?;
+    receipt.bump = bump;
+    receipt.try_serialize(&mut &mut race_receipt.try_borrow_mut_data()?[..])
+}
+
+// Pay a held race award from the vault and mark its receipt paid
+fn pay_race_receipt<'info>(
+    game_state: &mut Account<'info, GameState>,
+    race_receipt: &mut Account<'info, RaceReceipt>,
+    vault: &Account<'info, TokenAccount>,
+    player_token_account: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    memo_program: Option<&UncheckedAccount<'info>>,
+    now: i64,
+) -> Result<()> {
+    let amount = race_receipt.amount;
+    // Check if vault has sufficient balance
+    game_state.require_vault_covers(vault.amount, amount, amount)?;
+
+    transfer_tokens_from_vault(
+        game_state.to_account_info(),
+        vault.to_account_info(),
+        player_token_account.to_account_info(),
+        token_program,
+        amount,
+        game_state.bump,
+    )?;
+
+    game_state.total_pending_awards = game_state.total_pending_awards.saturating_sub(amount);
+    game_state.record_distribution(amount)?;
+    race_receipt.status = AwardStatus::Paid;
+
+    emit_token_reward(
+        TokenReward {
+            player: race_receipt.player,
+            amount,
+            reward_type: RewardType::RaceCompletion,
+            race_id: race_receipt.race_id,
+            timestamp: now,
+        },
+        &vault.to_account_info(),
+        vault.amount,
+        &player_token_account.to_account_info(),
+        memo_program,
+        game_state.current_era()?,
+    )
+}
+
+// Reward math shared by race awards and previews, up to (not including) probation:
+// strategy reward, USD conversion, handicap multiplier and the profile's race counters
+fn compute_race_award(
+    game_state: &GameState,
+    strategy: &dyn RewardStrategy,
+    player_profile: &mut PlayerProfile,
+    price_feed: Option<&UncheckedAccount>,
+    decimals: u8,
+    now: i64,
+) -> Result<u64> {
+    // Calculate rewards based on race performance
+    let total_reward = strategy.reward(game_state)?;
+
+    // Convert USD-denominated rewards at the oracle price
+    let total_reward = usd_to_token_amount(game_state, price_feed, total_reward, decimals, now)?;
+
+    // Catch-up multiplier for low-rated players, slight reduction for top-rated ones
+    let handicap = &game_state.handicap;
+    let multiplier_bps = speedy_core::handicap_multiplier_bps(
+        player_profile.rating,
+        handicap.low_rating_threshold,
+        handicap.catch_up_bps,
+        handicap.high_rating_threshold,
+        handicap.top_rated_bps,
+    );
+    let total_reward = speedy_core::apply_bps(total_reward, multiplier_bps)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+    // Active buffs and debuffs
+    let total_reward = player_profile.apply_buffs(BuffTarget::Races, total_reward, now)?;
+
+    if strategy.completed() {
+        player_profile.races_completed = player_profile.races_completed.checked_add(1)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        player_profile.last_race_week = speedy_core::week_index(now);
+    }
+    Ok(total_reward)
+}
+
+// Move tokens from a game vault to a token account owned by an allowlisted cold wallet.
+// Awards still held for their dispute window stay in the vault.
+fn sweep_vault_tokens<'info>(
+    game_state: &Account<'info, GameState>,
+    vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    game_state.withdrawal_allowlist.require_allowed(&destination.owner, now)?;
+
+    // Check if vault has sufficient balance
+    if vault.key() == game_state.vault {
+        game_state.require_vault_covers(vault.amount, amount, 0)?;
+    } else {
+        require!(vault.amount >= amount, ErrorCode::InsufficientVaultBalance);
+    }
+
+    transfer_tokens_from_vault(
+        game_state.to_account_info(),
+        vault.to_account_info(),
+        destination.to_account_info(),
+        token_program,
+        amount,
+        game_state.bump,
+    )
+}
+
+// Reimburse `payer` from the rent treasury for the rent of an account of `size` bytes it created
+// in this instruction. Without a treasury, or when the treasury cannot cover the rent and stay
+// rent exempt, the payer keeps covering the rent. Returns whether the treasury paid.
+fn front_rent<'info>(
+    rent_treasury: Option<&mut Account<'info, RentTreasury>>,
+    payer: &AccountInfo<'info>,
+    subsystem: RentSubsystem,
+    size: usize,
+) -> Result<bool> {
+    let Some(rent_treasury) = rent_treasury else {
+        return Ok(false);
+    };
+    let rent = Rent::get()?;
+    let lamports = rent.minimum_balance(size);
+    let treasury_info = rent_treasury.to_account_info();
+    let available = treasury_info.lamports()
+        .saturating_sub(rent.minimum_balance(treasury_info.data_len()));
+    if lamports > available {
+        return Ok(false);
+    }
+
+    **treasury_info.try_borrow_mut_lamports()? -= lamports;
+    **payer.try_borrow_mut_lamports()? += lamports;
+    rent_treasury.record_fronted(subsystem, lamports)?;
+    Ok(true)
+}
+
+// Move every unsold token from the sale vault to an allowlisted cold wallet once the sale has
+// ended. Returns the amount moved.
+fn withdraw_unsold_tokens<'info>(
+    game_state: &GameState,
+    token_sale: &Account<'info, TokenSale>,
+    sale_vault: &Account<'info, TokenAccount>,
+    destination: &Account<'info, TokenAccount>,
+    token_program: AccountInfo<'info>,
+    now: i64,
+) -> Result<u64> {
+    require!(now >= token_sale.config.end_time, ErrorCode::SaleNotEnded);
+    require!(destination.mint == sale_vault.mint, ErrorCode::InvalidVault);
+    game_state.withdrawal_allowlist.require_allowed(&destination.owner, now)?;
+
+    let amount = sale_vault.amount;
+    let sale_seeds = &[b"token_sale".as_ref(), &[token_sale.bump]];
+    let signer = &[&sale_seeds[..]];
+    let cpi_accounts = Transfer {
+        from: sale_vault.to_account_info(),
+        to: destination.to_account_info(),
+        authority: token_sale.to_account_info(),
+    };
+    transfer(CpiContext::new_with_signer(token_program, cpi_accounts, signer), amount)?;
+    Ok(amount)
+}
+
+// Move raised SOL from the sale PDA to an allowlisted cold wallet, keeping the PDA rent-exempt
+fn withdraw_sale_lamports<'info>(
+    game_state: &GameState,
+    token_sale: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    lamports: u64,
+    now: i64,
+) -> Result<()> {
+    game_state.withdrawal_allowlist.require_allowed(destination.key, now)?;
+
+    let rent_exempt = Rent::get()?.minimum_balance(token_sale.data_len());
+    let available = token_sale.lamports().saturating_sub(rent_exempt);
+    require!(lamports <= available, ErrorCode::InsufficientBalance);
+
+    **token_sale.try_borrow_mut_lamports()? -= lamports;
+    **destination.try_borrow_mut_lamports()? += lamports;
+    Ok(())
+}
+
+// Helper function to transfer tokens from vault
+fn transfer_tokens_from_vault<'info>(
+    game_state: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    player_token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+    bump: u8,
+) -> Result<()> {
+    let authority_seeds = &[
+        b"game_state".as_ref(),
+        &[bump],
+    ];
+    let signer = &[&authority_seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: vault,
+        to: player_token_account,
+        authority: game_state,
+    };
+    let cpi_program = token_program;
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+    transfer(cpi_ctx, amount)?;
+    Ok(())
+}
+
+// Attach a `speedy:reward:<player>:<reward_type>:<id>` memo so custodians and exchanges that
+// key off memos can attribute the deposit
+fn attach_reward_memo(memo_program: &UncheckedAccount, reward: &TokenReward) -> Result<()> {
+    let memo = format!(
+        "speedy:reward:{}:{}:{}",
+        reward.player,
+        reward.reward_type as u8,
+        reward.race_id,
+    );
+    let memo_ix = Instruction {
+        program_id: MEMO_PROGRAM_ID,
+        accounts: vec![],
+        data: memo.into_bytes(),
+    };
+    invoke(&memo_ix, &[memo_program.to_account_info()])?;
+    Ok(())
+}
+
+// Current amount held by a token account, read from its data rather than a cached `Account`
+fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let data = token_account.try_borrow_data()?;
+    Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+}
+
+// Emit a reward in its original and v2 forms. The v2 event reads the balances back after the
+// transfer so off-chain accounting can reconcile emitted amounts with actual movements, and
+// records the emission era the reward was paid in.
+fn emit_token_reward(
+    reward: TokenReward,
+    vault: &AccountInfo,
+    vault_balance_before: u64,
+    player_token_account: &AccountInfo,
+    memo_program: Option<&UncheckedAccount>,
+    halving_era: u8,
+) -> Result<()> {
+    if let Some(memo_program) = memo_program {
+        attach_reward_memo(memo_program, &reward)?;
+    }
+    emit!(TokenRewardV2 {
+        player: reward.player,
+        amount: reward.amount,
+        reward_type: reward.reward_type,
+        race_id: reward.race_id,
+        timestamp: reward.timestamp,
+        vault_balance_before,
+        vault_balance_after: token_balance(vault)?,
+        player_balance_after: token_balance(player_token_account)?,
+        subject: RewardSubject::from_legacy(reward.reward_type, reward.race_id),
+        halving_era,
+    });
+    emit!(reward);
+    Ok(())
+}
+
+// Leaf committed for each ranked player in the weekly standings tree
+pub fn standings_leaf(player: &Pubkey, week: u64, rank: u32, amount: u64) -> [u8; 32] {
+    hashv(&[
+        &[0u8],
+        player.as_ref(),
+        &week.to_le_bytes(),
+        &rank.to_le_bytes(),
+        &amount.to_le_bytes(),
+    ]).to_bytes()
+}
+
+// Check a player against a tournament's entry gates
+fn check_tournament_eligibility(
+    eligibility: &TournamentEligibility,
+    player: &Pubkey,
+    game_oracle: &Pubkey,
+    oracle: Option<&Signer>,
+    player_profile: Option<&Account<PlayerProfile>>,
+    car_rarity: CarRarity,
+    allowlist_proof: &[[u8; 32]],
+) -> Result<()> {
+    if let Some(min_car_rarity) = eligibility.min_car_rarity {
+        let oracle = oracle.ok_or(ErrorCode::NotEligible)?;
+        require!(
+            oracle.key() == *game_oracle && car_rarity as u8 >= min_car_rarity as u8,
+            ErrorCode::NotEligible
+        );
+    }
+    if eligibility.min_level > 0 {
+        let races_completed = player_profile
+            .map(|profile| profile.races_completed)
+            .unwrap_or(0);
+        require!(
+            speedy_core::player_level(races_completed) >= eligibility.min_level,
+            ErrorCode::NotEligible
+        );
+    }
+    if let Some(allowlist_root) = eligibility.allowlist_root {
+        require!(
+            verify_merkle_proof(allowlist_proof, &allowlist_root, allowlist_leaf(player)),
+            ErrorCode::NotEligible
+        );
+    }
+    Ok(())
+}
+
+// Transfer out of a tournament's prize escrow, signed by the tournament PDA
+fn transfer_from_tournament_escrow<'info>(
+    tournament: &Account<'info, Tournament>,
+    prize_escrow: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let tournament_id_bytes = tournament.tournament_id.to_le_bytes();
+    let tournament_seeds = &[
+        b"tournament".as_ref(),
+        tournament_id_bytes.as_ref(),
+        &[tournament.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: prize_escrow,
+        to: destination,
+        authority: tournament.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(token_program, cpi_accounts, &[&tournament_seeds[..]]),
+        amount,
+    )
+}
+
+// Balances released by one `claim_everything` sweep
+struct ClaimSweep {
+    race_awards: u64,
+    unstaked: u64,
+    vested: u64, // Including the part that repaid entry fee loans
+    repaid: u64,
+}
+
+impl ClaimSweep {
+    // Amount owed to the player out of the vault
+    fn payout(&self) -> Result<u64> {
+        let payout = self.race_awards
+            .checked_add(self.unstaked)
+            .and_then(|total| total.checked_add(self.vested - self.repaid))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        require!(payout > 0 || self.repaid > 0, ErrorCode::NothingToClaim);
+        Ok(payout)
+    }
+
+    fn settle(&self, game_state: &mut GameState, payout: u64) -> Result<()> {
+        game_state.total_pending_awards = game_state.total_pending_awards.saturating_sub(self.race_awards);
+        game_state.total_auto_staked = game_state.total_auto_staked.saturating_sub(self.unstaked);
+        game_state.total_vesting = game_state.total_vesting.saturating_sub(self.vested);
+        game_state.total_fee_loans = game_state.total_fee_loans.saturating_sub(self.repaid);
+        game_state.record_distribution(payout)
+    }
+}
+
+// Release the player's pending race awards whose dispute window has closed (accounts that are
+// not race receipts are skipped), unlocked auto-staked winnings and vested rewards. Entry fee
+// loans are settled from the vested part first.
+fn sweep_claimable<'info>(
+    receipts: &'info [AccountInfo<'info>],
+    player_profile: &mut PlayerProfile,
+    now: i64,
+) -> Result<ClaimSweep> {
+    let mut race_awards = 0u64;
+    for receipt_info in receipts.iter() {
+        let is_receipt = receipt_info.try_borrow_data()?.starts_with(&RaceReceipt::DISCRIMINATOR);
+        if !is_receipt {
+            continue;
+        }
+        let mut race_receipt = Account::<RaceReceipt>::try_from(receipt_info)?;
+        require!(race_receipt.player == player_profile.player, ErrorCode::InvalidRaceReceipt);
+        if race_receipt.status != AwardStatus::Pending || now < race_receipt.release_at {
+            continue;
+        }
+        race_receipt.status = AwardStatus::Paid;
+        race_receipt.exit(&crate::ID)?;
+        race_awards = race_awards.checked_add(race_receipt.amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+    }
+
+    player_profile.prune_buffs(now);
+    let unstaked = if now >= player_profile.auto_stake_unlock_at { player_profile.auto_staked } else { 0 };
+    player_profile.auto_staked -= unstaked;
+
+    let vested = player_profile.vesting.take_claimable(now);
+    let repaid = vested.min(player_profile.fee_loan);
+    player_profile.fee_loan -= repaid;
+
+    Ok(ClaimSweep { race_awards, unstaked, vested, repaid })
+}
+
+// Record an opened position in the player's index, if the player has created one
+fn index_position(player_index: &AccountInfo, kind: PositionKind, address: Pubkey) -> Result<()> {
+    update_player_index(player_index, |index| index.add(kind, address))
+}
+
+fn unindex_position(player_index: &AccountInfo, address: &Pubkey) -> Result<()> {
+    update_player_index(player_index, |index| {
+        index.remove(address);
+        Ok(())
+    })
+}
+
+// Contexts take the index as its bare PDA address so it is always passed; an index the player
+// has not created yet is not owned by this program and is left alone
+fn update_player_index(
+    player_index: &AccountInfo,
+    update: impl FnOnce(&mut PlayerIndex) -> Result<()>,
+) -> Result<()> {
+    if player_index.owner != &crate::ID {
+        return Ok(());
+    }
+    let mut data = player_index.try_borrow_mut_data()?;
+    let mut index = PlayerIndex::try_deserialize(&mut &data[..])?;
+    update(&mut index)?;
+    index.try_serialize(&mut &mut data[..])
+}
+
+// Append the current rate tables to the rate history. Once the history exists every rate
+// change has to pass it, so the record has no gaps.
+fn record_rate_snapshot(
+    game_state: &GameState,
+    rate_history: Option<&mut Account<RateHistory>>,
+    now: i64,
+) -> Result<()> {
+    let Some(rate_history) = rate_history else {
+        require!(!game_state.rate_history_enabled, ErrorCode::RateHistoryRequired);
+        return Ok(());
+    };
+    rate_history.push(RateSnapshot {
+        token_rates: game_state.token_rates,
+        usd_rates: game_state.usd_rates,
+        usd_priced: game_state.usd_pricing.enabled,
+        effective_slot: Clock::get()?.slot,
+        effective_at: now,
+    });
+    Ok(())
+}
+
+// Set one token rate and emit the old and new value
+fn apply_rate_change(token_rates: &mut TokenRates, change: RateChange, now: i64) {
+    let rate = token_rates.field_mut(change.field);
+    let old_value = *rate;
+    *rate = change.value;
+
+    emit!(RateChanged {
+        field: change.field,
+        old_value,
+        new_value: change.value,
+        timestamp: now,
+    });
+}
+
+// Move tokens out of a track submission's stake escrow, signed by the submission PDA
+fn transfer_from_track_submission<'info>(
+    submission: &Account<'info, TrackSubmission>,
+    submission_escrow: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let track_id_bytes = submission.track_id.to_le_bytes();
+    let submission_seeds = &[
+        b"track_submission".as_ref(),
+        track_id_bytes.as_ref(),
+        &[submission.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: submission_escrow,
+        to: destination,
+        authority: submission.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(token_program, cpi_accounts, &[&submission_seeds[..]]),
+        amount,
+    )
+}
+
+// Move the whole held prize out of its hold escrow, signed by the hold PDA
+fn transfer_from_prize_hold<'info>(
+    prize_hold: &Account<'info, PrizeHold>,
+    hold_escrow: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<()> {
+    let tournament_id_bytes = prize_hold.tournament_id.to_le_bytes();
+    let hold_seeds = &[
+        b"prize_hold".as_ref(),
+        tournament_id_bytes.as_ref(),
+        prize_hold.player.as_ref(),
+        &[prize_hold.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: hold_escrow,
+        to: destination,
+        authority: prize_hold.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(token_program, cpi_accounts, &[&hold_seeds[..]]),
+        prize_hold.amount,
+    )
+}
+
+fn transfer_from_region_vault<'info>(
+    region_pool: &Account<'info, RegionPool>,
+    region_vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let region_seeds = &[
+        b"region_pool".as_ref(),
+        &[region_pool.region as u8],
+        &[region_pool.bump],
+    ];
+    let cpi_accounts = Transfer {
+        from: region_vault,
+        to: destination,
+        authority: region_pool.to_account_info(),
+    };
+    transfer(
+        CpiContext::new_with_signer(token_program, cpi_accounts, &[&region_seeds[..]]),
+        amount,
+    )
+}
+
+// Leaf committed for each wallet in a tournament allowlist tree
+pub fn allowlist_leaf(player: &Pubkey) -> [u8; 32] {
+    hashv(&[&[0u8], player.as_ref()]).to_bytes()
+}
+
+// Verify a merkle proof built with sorted sibling pairs; leaves and inner nodes are domain-separated
+pub fn verify_merkle_proof(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+    let mut node = leaf;
+    for sibling in proof.iter() {
+        node = if node <= *sibling {
+            hashv(&[&[1u8], &node, sibling]).to_bytes()
+        } else {
+            hashv(&[&[1u8], sibling, &node]).to_bytes()
+        };
+    }
+    node == *root
+}
+
+// Convert a reward computed from `usd_rates` (USD cents) into raw token units using the
+// oracle price. Returns the amount unchanged when USD pricing is disabled.
+fn usd_to_token_amount(
+    game_state: &GameState,
+    price_feed: Option<&UncheckedAccount>,
+    amount: u64,
+    decimals: u8,
+    now: i64,
+) -> Result<u64> {
+    let config = &game_state.usd_pricing;
+    if !config.enabled {
+        return Ok(amount);
+    }
+
+    let price_feed = price_feed.ok_or(ErrorCode::InvalidPriceFeed)?;
+    let price = load_pyth_price(price_feed, &config.price_feed_id)?;
+
+    require!(price.price > 0, ErrorCode::InvalidPriceFeed);
+    require!(
+        now.saturating_sub(price.publish_time) <= config.max_staleness_secs,
+        ErrorCode::StalePrice
+    );
+    let confidence_bps = (price.conf as u128) * BPS_DENOMINATOR as u128 / price.price as u128;
+    require!(
+        confidence_bps <= config.max_confidence_bps as u128,
+        ErrorCode::PriceConfidenceTooWide
+    );
+
+    speedy_core::usd_cents_to_tokens(amount, decimals, price.price, price.exponent)
+        .ok_or(ErrorCode::InvalidRewardAmount.into())
+}
+
+// Read a fully verified price from a Pyth receiver `PriceUpdateV2` account
+fn load_pyth_price(price_feed: &AccountInfo, feed_id: &[u8; 32]) -> Result<PythPriceMessage> {
+    require!(
+        price_feed.owner == &PYTH_RECEIVER_PROGRAM_ID,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let data = price_feed.try_borrow_data()?;
+    let discriminator = &hash(b"account:PriceUpdateV2").to_bytes()[..8];
+    require!(
+        data.len() > 8 && &data[..8] == discriminator,
+        ErrorCode::InvalidPriceFeed
+    );
+
+    let mut payload: &[u8] = &data[8..];
+    let update = PythPriceUpdate::deserialize(&mut payload)
+        .map_err(|_| ErrorCode::InvalidPriceFeed)?;
+
+    require!(
+        matches!(update.verification_level, PythVerificationLevel::Full),
+        ErrorCode::InvalidPriceFeed
+    );
+    require!(
+        &update.price_message.feed_id == feed_id,
+        ErrorCode::InvalidPriceFeed
+    );
+    Ok(update.price_message)
+}
+
+// Price in lamports for buying `amount` raw units from the sale after `sold` units have been sold
+fn sale_cost_lamports(
+    config: &SaleConfig,
+    sold: u64,
+    amount: u64,
+    decimals: u8,
+) -> Result<u64> {
+    speedy_core::sale_cost_lamports(
+        config.base_price_lamports,
+        config.price_slope_lamports,
+        sold,
+        amount,
+        decimals,
+    ).ok_or(ErrorCode::InvalidRewardAmount.into())
+}
+
+// Convert a raw token amount by `numerator / denominator`, adjusting for mint decimals
+fn convert_token_amount(
+    amount: u64,
+    numerator: u64,
+    denominator: u64,
+    from_decimals: u8,
+    to_decimals: u8,
+) -> Result<u64> {
+    require!(denominator > 0, ErrorCode::InvalidMigrationRate);
+    speedy_core::convert_token_amount(amount, numerator, denominator, from_decimals, to_decimals)
+        .ok_or(ErrorCode::InvalidRewardAmount.into())
+}
+
+// Fields of a Wormhole `PostedVAA` account the bridge reward claim relies on
+struct PostedVaa {
+    sequence: u64,
+    emitter_chain: u16,
+    emitter_address: [u8; 32],
+    payload: Vec<u8>,
+}
+
+impl PostedVaa {
+    // Layout: "vaa", version u8, consistency u8, vaa_time u32, signature_set Pubkey,
+    // submission_time u32, nonce u32, sequence u64, emitter_chain u16, emitter_address, payload
+    const SEQUENCE_OFFSET: usize = 3 + 1 + 1 + 4 + 32 + 4 + 4;
+
+    fn load(info: &AccountInfo) -> Result<Self> {
+        let data = info.try_borrow_data()?;
+        require!(data.len() >= Self::SEQUENCE_OFFSET + 8 + 2 + 32 + 4, ErrorCode::InvalidBridgeMessage);
+        // PostedMessage accounts ("msg") carry unverified outbound data and must not pass
+        require!(&data[..3] == b"vaa", ErrorCode::InvalidBridgeMessage);
+
+        let mut cursor = &data[Self::SEQUENCE_OFFSET..];
+        let sequence = u64::deserialize(&mut cursor)?;
+        let emitter_chain = u16::deserialize(&mut cursor)?;
+        let emitter_address = <[u8; 32]>::deserialize(&mut cursor)?;
+        let payload = Vec::<u8>::deserialize(&mut cursor)?;
+        Ok(Self { sequence, emitter_chain, emitter_address, payload })
+    }
+}
+
+// The BPF Loader Upgradeable program data account holding this program's upgrade authority
+fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[crate::ID.as_ref()], &bpf_loader_upgradeable::id()).0
+}
+
+// Require that this instruction is running as a direct CPI from `caller`. The top-level
+// instruction of the transaction is the program that invoked us when we sit one level deep.
+fn verify_cpi_caller(instructions_sysvar: &AccountInfo, caller: &Pubkey) -> Result<()> {
+    require!(
+        get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+        ErrorCode::InvalidCpiCaller
+    );
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let top_level_ix = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    require!(top_level_ix.program_id == *caller, ErrorCode::InvalidCpiCaller);
+    Ok(())
+}
+
+// Verify that the instruction immediately before this one is an ed25519 program
+// instruction checking `signer`'s signature over exactly `message`
+fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::MissingSignatureVerification);
+    let ed25519_ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+
+    require!(
+        ed25519_ix.program_id == ed25519_program::ID && ed25519_ix.accounts.is_empty(),
+        ErrorCode::MissingSignatureVerification
+    );
+
+    // Layout: [num_signatures: u8, padding: u8, offsets: 7 x u16, pubkey, signature, message]
+    let data = &ed25519_ix.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidSignatureVerification);
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let signature_ix_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_ix_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_ix_index = read_u16(14);
+
+    // All data must live inside the ed25519 instruction itself
+    require!(
+        signature_ix_index == u16::MAX
+            && public_key_ix_index == u16::MAX
+            && message_ix_index == u16::MAX,
+        ErrorCode::InvalidSignatureVerification
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(ErrorCode::InvalidSignatureVerification)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        ErrorCode::InvalidSignatureVerification
+    );
+    Ok(())
+}
+
+// Create the player's profile on first use (init_if_needed leaves it zeroed)
+fn init_player_profile_if_needed(
+    player_profile: &mut PlayerProfile,
+    player: Pubkey,
+    bump: u8,
+    now: i64,
+) -> bool {
+    if player_profile.player != Pubkey::default() {
+        return false;
+    }
+    *player_profile = PlayerProfile::new(player, bump, now);
+    true
+}
+
+// Apply new-wallet probation to a computed reward and return the amount to pay out now.
+// Rewards withheld during probation are added back once the player has left probation
+// and completed the configured number of races. The profile's balance adjustment is netted in
+// last, and the payout is counted towards the player's earnings for the calendar year.
+fn apply_probation(
+    player_profile: &mut PlayerProfile,
+    probation: &ProbationConfig,
+    reward: u64,
+    reward_type: RewardType,
+    now: i64,
+) -> Result<u64> {
+    let (payout, released) = settle_probation(player_profile, probation, reward, now)?;
+    if released > 0 {
+        emit!(ProbationRewardsReleased {
+            player: player_profile.player,
+            amount: released,
+            timestamp: now,
+        });
+    }
+
+    // Debt is recovered from the payout, credits are added to it
+    let (payout, applied) = settle_balance_adjustment(player_profile, payout)?;
+    if applied != 0 {
+        emit!(BalanceAdjustmentApplied {
+            player: player_profile.player,
+            applied,
+            remaining: player_profile.balance_adjustment,
+            timestamp: now,
+        });
+    }
+
+    player_profile.lifetime_earnings = player_profile.lifetime_earnings.checked_add(payout)
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+    player_profile.year_earnings_mut(speedy_core::calendar_year(now))
+        .record(reward_type, payout)?;
+    Ok(payout)
+}
+
+// Net the profile's signed balance adjustment into a payout. A negative adjustment takes up to
+// the whole payout; a positive one is paid out in full. Returns the payout and the amount applied.
+fn settle_balance_adjustment(player_profile: &mut PlayerProfile, payout: u64) -> Result<(u64, i64)> {
+    let adjustment = player_profile.balance_adjustment;
+    if adjustment < 0 {
+        let recovered = payout.min(adjustment.unsigned_abs());
+        player_profile.balance_adjustment += recovered as i64;
+        return Ok((payout - recovered, -(recovered as i64)));
+    }
+
+    player_profile.balance_adjustment = 0;
+    let payout = payout.checked_add(adjustment as u64).ok_or(ErrorCode::InvalidRewardAmount)?;
+    Ok((payout, adjustment))
+}
+
+// Probation bookkeeping without events; returns the payout and any withheld amount released
+fn settle_probation(
+    player_profile: &mut PlayerProfile,
+    probation: &ProbationConfig,
+    reward: u64,
+    now: i64,
+) -> Result<(u64, u64)> {
+    let probation_ends = player_profile.created_at.saturating_add(probation.duration_secs);
+
+    if now < probation_ends {
+        let payout = speedy_core::apply_bps(reward, probation.reward_bps)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        player_profile.withheld_rewards = player_profile.withheld_rewards
+            .checked_add(reward - payout)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        return Ok((payout, 0));
+    }
+
+    if player_profile.withheld_rewards > 0
+        && player_profile.races_completed >= probation.release_after_races
+    {
+        let released = player_profile.withheld_rewards;
+        player_profile.withheld_rewards = 0;
+        let payout = reward.checked_add(released).ok_or(ErrorCode::InvalidRewardAmount)?;
+        return Ok((payout, released));
+    }
+
+    Ok((reward, 0))
+}
+
+// Account Structures
+#[derive(Accounts)]
+#[instruction(decimals: u8)]
+pub struct InitializeToken<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = decimals,
+        mint::authority = authority, // Authority is the mint authority, not game_state
+    )]
+    pub token_mint: Account<'info, Mint>, // Classic SPL Token; see MigrateMint::new_mint
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::GAME_STATE,
+        seeds = [b"game_state"],
+        bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = game_state, // Vault is controlled by game_state PDA
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct FundVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AwardTokens<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Vouches for the reported result; players cannot award themselves
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    /// CHECK: Pyth price update account, validated in `load_pyth_price`; required only when USD pricing is enabled
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Race receipt PDA for (player, race id), created in `record_race_receipt`;
+    /// required when committing a replay hash
+    #[account(mut)]
+    pub race_receipt: Option<UncheckedAccount<'info>>,
+
+    // Community track the race was run on; its owner takes a cut of the award
+    #[account(mut)]
+    pub track: Option<Account<'info, Track>>,
+
+    // Track ownership NFT holding, required when the track has an owner
+    pub track_owner_nft: Option<Account<'info, TokenAccount>>,
+
+    // $SPEEDY account of the track NFT holder
+    #[account(mut)]
+    pub track_owner_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Reimburses the player for the receipt and a new profile when passed and funded
+    #[account(
+        mut,
+        seeds = [b"rent_treasury"],
+        bump = rent_treasury.bump,
+    )]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+
+    // Authority co-signature, required while the oracle heartbeat is stale or for payouts
+    // above the co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64, config: TournamentConfig)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        constraint = game_state.mint_for(config.entry_currency) == Some(entry_mint.key())
+            @ ErrorCode::InvalidTournamentConfig,
+    )]
+    pub entry_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::TOURNAMENT,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = entry_mint,
+        associated_token::authority = tournament,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::REGISTRY_PAGE,
+        seeds = [
+            b"registry".as_ref(),
+            &[RegistryKind::Tournament as u8],
+            game_state.registry_page_index(RegistryKind::Tournament).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct JoinTournament<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Required only for tournaments gated on car rarity
+    pub oracle: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::TOURNAMENT_ENTRY,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    // Required only for level-gated tournaments
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeTickets<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::TICKET_CONFIG,
+        seeds = [b"ticket_config"],
+        bump,
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"ticket_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = ticket_config,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTicketConfig<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+}
+
+#[derive(Accounts)]
+pub struct BuyTickets<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+
+    #[account(
+        mut,
+        address = ticket_config.ticket_mint,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = player,
+    )]
+    pub player_ticket_account: Account<'info, TokenAccount>,
+
+    // Required only to use a promo shop discount
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefundTickets<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+
+    #[account(
+        mut,
+        address = ticket_config.ticket_mint,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = player,
+    )]
+    pub player_ticket_account: Account<'info, TokenAccount>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct SpendTicket<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Required only for tournaments gated on car rarity
+    pub oracle: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::TOURNAMENT_ENTRY,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    // Required only for level-gated tournaments
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+    )]
+    pub ticket_config: Account<'info, TicketConfig>,
+
+    #[account(
+        mut,
+        address = ticket_config.ticket_mint,
+    )]
+    pub ticket_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = ticket_mint,
+        associated_token::authority = player,
+    )]
+    pub player_ticket_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Ticket entries are only accepted by tournaments whose entry currency is $SPEEDY
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+        constraint = prize_escrow.mint == game_state.token_mint @ ErrorCode::InvalidTournamentConfig,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct JoinTournamentOnCredit<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Required only for tournaments gated on car rarity
+    pub oracle: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::TOURNAMENT_ENTRY,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Loans only cover tournaments whose entry currency is $SPEEDY
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+        constraint = prize_escrow.mint == game_state.token_mint @ ErrorCode::InvalidTournamentConfig,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct WithdrawFromTournament<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = tournament_entry.bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct JoinTournamentWaitlist<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Required only for tournaments gated on car rarity
+    pub oracle: Option<Signer<'info>>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::WAITLIST_ENTRY,
+        seeds = [
+            b"tournament_waitlist",
+            tournament_id.to_le_bytes().as_ref(),
+            tournament.waitlist_tail.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+
+    // Required only for level-gated tournaments
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct PromoteWaitlisted<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    // Always the earliest remaining position
+    #[account(
+        mut,
+        close = cranker,
+        seeds = [
+            b"tournament_waitlist",
+            tournament_id.to_le_bytes().as_ref(),
+            tournament.waitlist_head.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = space::TOURNAMENT_ENTRY,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), waitlist_entry.player.as_ref()],
+        bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = waitlist_entry.player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Crank tip destination; no tip is paid when omitted
+    #[account(mut, token::mint = game_state.token_mint)]
+    pub tip_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", waitlist_entry.player.as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64, position: u64)]
+pub struct RefundWaitlistEntry<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        close = player,
+        has_one = player @ ErrorCode::Unauthorized,
+        seeds = [
+            b"tournament_waitlist",
+            tournament_id.to_le_bytes().as_ref(),
+            position.to_le_bytes().as_ref(),
+        ],
+        bump = waitlist_entry.bump,
+    )]
+    pub waitlist_entry: Account<'info, WaitlistEntry>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct SponsorTournament<'info> {
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        address = tournament.prize_source,
+    )]
+    pub prize_source: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = prize_source.mint,
+        token::authority = sponsor,
+    )]
+    pub sponsor_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct DisqualifyEntrant<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    /// CHECK: Disqualified entrant, receives the refund and the entry's rent
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = tournament_entry.bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct PayTournamentPrize<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    /// CHECK: Prize recipient, must be a registered entrant
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"tournament_entry", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = tournament_entry.bump,
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+
+    // Tournament escrow, or the game vault of the prize currency for mixed-currency tournaments
+    #[account(
+        mut,
+        address = tournament.prize_source,
+    )]
+    pub prize_source: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = prize_source.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Hold accounts, required only for prizes above the hold threshold
+    #[account(
+        init,
+        payer = oracle,
+        space = space::PRIZE_HOLD,
+        seeds = [b"prize_hold", tournament_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub prize_hold: Option<Account<'info, PrizeHold>>,
+
+    #[account(address = prize_source.mint)]
+    pub prize_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = oracle,
+        associated_token::mint = prize_mint,
+        associated_token::authority = prize_hold,
+    )]
+    pub hold_escrow: Option<Account<'info, TokenAccount>>,
+
+    // Reimburses the oracle for a new prize hold and its escrow when passed and funded
+    #[account(
+        mut,
+        seeds = [b"rent_treasury"],
+        bump = rent_treasury.bump,
+    )]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct ReleasePrizeHold<'info> {
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"prize_hold", tournament_id.to_le_bytes().as_ref(), prize_hold.player.as_ref()],
+        bump = prize_hold.bump,
+    )]
+    pub prize_hold: Account<'info, PrizeHold>,
+
+    #[account(
+        mut,
+        address = prize_hold.escrow,
+    )]
+    pub hold_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = hold_escrow.mint,
+        associated_token::authority = prize_hold.player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct ClawbackPrizeHold<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Moderator, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        seeds = [b"prize_hold", tournament_id.to_le_bytes().as_ref(), prize_hold.player.as_ref()],
+        bump = prize_hold.bump,
+    )]
+    pub prize_hold: Account<'info, PrizeHold>,
+
+    #[account(
+        mut,
+        address = prize_hold.escrow,
+    )]
+    pub hold_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = tournament.prize_source,
+    )]
+    pub prize_source: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct SweepTournamentFees<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = game_state.vault_for_mint(&prize_escrow.mint) == Some(treasury.key())
+            @ ErrorCode::InvalidTournamentConfig,
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct RolloverUnclaimed<'info> {
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"tournament", tournament_id.to_le_bytes().as_ref()],
+        bump = tournament.bump,
+    )]
+    pub tournament: Account<'info, Tournament>,
+
+    #[account(
+        mut,
+        address = tournament.prize_escrow,
+    )]
+    pub prize_escrow: Account<'info, TokenAccount>,
+
+    // Tournament receiving the balance, with its prize escrow
+    #[account(
+        seeds = [b"tournament", next_tournament.tournament_id.to_le_bytes().as_ref()],
+        bump = next_tournament.bump,
+    )]
+    pub next_tournament: Option<Account<'info, Tournament>>,
+
+    #[account(mut)]
+    pub next_prize_escrow: Option<Account<'info, TokenAccount>>,
+
+    // Game vault of the escrow's currency, used when no next tournament is passed
+    #[account(
+        mut,
+        constraint = game_state.vault_for_mint(&prize_escrow.mint) == Some(treasury.key())
+            @ ErrorCode::InvalidTournamentConfig,
+    )]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(league_id: u64)]
+pub struct CreateLeague<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::LEAGUE,
+        seeds = [b"league", league_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub league: Account<'info, League>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::REGISTRY_PAGE,
+        seeds = [
+            b"registry".as_ref(),
+            &[RegistryKind::League as u8],
+            game_state.registry_page_index(RegistryKind::League).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    pub previous_season: Option<Account<'info, League>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(league_id: u64)]
+pub struct ReportLeagueFixture<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"league", league_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+    )]
+    pub league: Account<'info, League>,
+}
+
+#[derive(Accounts)]
+#[instruction(league_id: u64)]
+pub struct FinalizeLeagueSeason<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"league", league_id.to_le_bytes().as_ref()],
+        bump = league.bump,
+    )]
+    pub league: Account<'info, League>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct CreatePredictionMarket<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::PREDICTION_MARKET,
+        seeds = [b"prediction_market", week.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub prediction_market: Account<'info, PredictionMarket>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::REGISTRY_PAGE,
+        seeds = [
+            b"registry".as_ref(),
+            &[RegistryKind::PredictionMarket as u8],
+            game_state.registry_page_index(RegistryKind::PredictionMarket).to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = prediction_market,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreatePlayerIndex<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::PLAYER_INDEX,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: Account<'info, PlayerIndex>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PrunePlayerIndex<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump = player_index.bump,
+    )]
+    pub player_index: Account<'info, PlayerIndex>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct PlacePrediction<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"prediction_market", week.to_le_bytes().as_ref()],
+        bump = prediction_market.bump,
+    )]
+    pub prediction_market: Account<'info, PredictionMarket>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PREDICTION_STAKE,
+        seeds = [b"prediction_stake", week.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub prediction_stake: Account<'info, PredictionStake>,
+
+    #[account(
+        mut,
+        address = prediction_market.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct SettlePredictionMarket<'info> {
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"prediction_market", week.to_le_bytes().as_ref()],
+        bump = prediction_market.bump,
+    )]
+    pub prediction_market: Account<'info, PredictionMarket>,
+
+    #[account(
+        seeds = [b"weekly_standings", week.to_le_bytes().as_ref()],
+        bump = weekly_standings.bump,
+    )]
+    pub weekly_standings: Account<'info, WeeklyStandings>,
+
+    #[account(
+        mut,
+        address = prediction_market.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct ClaimPrediction<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"prediction_market", week.to_le_bytes().as_ref()],
+        bump = prediction_market.bump,
+    )]
+    pub prediction_market: Account<'info, PredictionMarket>,
+
+    #[account(
+        mut,
+        seeds = [b"prediction_stake", week.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = prediction_stake.bump,
+    )]
+    pub prediction_stake: Account<'info, PredictionStake>,
+
+    #[account(
+        mut,
+        address = prediction_market.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct ProposeClawback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Moderator, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    // One pending clawback per player
+    #[account(
+        init,
+        payer = authority,
+        space = space::CLAWBACK,
+        seeds = [b"clawback", player.as_ref()],
+        bump,
+    )]
+    pub clawback: Account<'info, Clawback>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteClawback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Moderator, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"clawback", clawback.player.as_ref()],
+        bump = clawback.bump,
+    )]
+    pub clawback: Account<'info, Clawback>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", clawback.player.as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct CreditBalanceAdjustment<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Moderator, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[derive(Accounts)]
+pub struct CancelClawback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Moderator, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"clawback", clawback.player.as_ref()],
+        bump = clawback.bump,
+    )]
+    pub clawback: Account<'info, Clawback>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVipRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Created and initialized as a Token-2022 mint in the handler
+    #[account(
+        mut,
+        seeds = [b"vip_role_mint"],
+        bump,
+    )]
+    pub vip_role_mint: UncheckedAccount<'info>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVipRole<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vip_role.mint,
+    )]
+    pub vip_role_mint: InterfaceAccount<'info, token_interface::Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = vip_role_mint,
+        associated_token::authority = player,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub player_role_account: InterfaceAccount<'info, token_interface::TokenAccount>,
+
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(drop_id: u64)]
+pub struct CreateCarDrop<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::CAR_DROP,
+        seeds = [b"car_drop", drop_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub car_drop: Account<'info, CarDrop>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"car_mint", drop_id.to_le_bytes().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = car_drop,
+    )]
+    pub car_mint: Account<'info, Mint>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = car_drop,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(drop_id: u64)]
+pub struct PreorderCar<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"car_drop", drop_id.to_le_bytes().as_ref()],
+        bump = car_drop.bump,
+    )]
+    pub car_drop: Account<'info, CarDrop>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PREORDER,
+        seeds = [b"preorder", drop_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub preorder: Account<'info, Preorder>,
+
+    #[account(
+        mut,
+        address = car_drop.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = escrow.mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(drop_id: u64)]
+pub struct DeliverPreorder<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"car_drop", drop_id.to_le_bytes().as_ref()],
+        bump = car_drop.bump,
+    )]
+    pub car_drop: Account<'info, CarDrop>,
+
+    /// CHECK: Pre-order owner, receives the cars and the pre-order rent
+    #[account(mut, address = preorder.player)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"preorder", drop_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = preorder.bump,
+    )]
+    pub preorder: Account<'info, Preorder>,
+
+    #[account(
+        mut,
+        address = car_drop.car_mint,
+    )]
+    pub car_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = car_mint,
+        associated_token::authority = player,
+    )]
+    pub player_car_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = car_drop.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(drop_id: u64)]
+pub struct CancelCarDrop<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"car_drop", drop_id.to_le_bytes().as_ref()],
+        bump = car_drop.bump,
+    )]
+    pub car_drop: Account<'info, CarDrop>,
+}
+
+#[derive(Accounts)]
+#[instruction(drop_id: u64)]
+pub struct RefundPreorder<'info> {
+    #[account(
+        seeds = [b"car_drop", drop_id.to_le_bytes().as_ref()],
+        bump = car_drop.bump,
+    )]
+    pub car_drop: Account<'info, CarDrop>,
+
+    /// CHECK: Pre-order owner, receives the refund and the pre-order rent
+    #[account(mut, address = preorder.player)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"preorder", drop_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = preorder.bump,
+    )]
+    pub preorder: Account<'info, Preorder>,
+
+    #[account(
+        mut,
+        address = car_drop.escrow,
+    )]
+    pub escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = escrow.mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct CreateDutchAuction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::DUTCH_AUCTION,
+        seeds = [b"dutch_auction", auction_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"auction_mint", auction_id.to_le_bytes().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = auction,
+    )]
+    pub car_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(auction_id: u64)]
+pub struct BuyFromAuction<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"dutch_auction", auction_id.to_le_bytes().as_ref()],
+        bump = auction.bump,
+    )]
+    pub auction: Account<'info, DutchAuction>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::AUCTION_PURCHASE,
+        seeds = [b"auction_purchase", auction_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub purchase: Account<'info, AuctionPurchase>,
+
+    #[account(
+        mut,
+        address = auction.car_mint,
+    )]
+    pub car_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = car_mint,
+        associated_token::authority = player,
+    )]
+    pub player_car_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = game_state.token_mint,
+        token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct CreateRedemptionOffer<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::REDEMPTION_OFFER,
+        seeds = [b"redemption_offer", item_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub offer: Account<'info, RedemptionOffer>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct RedeemOffer<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption_offer", item_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, RedemptionOffer>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::REDEMPTION_RECEIPT,
+        seeds = [b"redemption", item_id.to_le_bytes().as_ref(), offer.redeemed.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub receipt: Account<'info, RedemptionReceipt>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(item_id: u64, index: u32)]
+pub struct MarkRedemptionShipped<'info> {
+    pub fulfiller: Signer<'info>,
+
+    #[account(
+        seeds = [b"redemption_offer", item_id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+        has_one = fulfiller @ ErrorCode::Unauthorized,
+    )]
+    pub offer: Account<'info, RedemptionOffer>,
+
+    #[account(
+        mut,
+        seeds = [b"redemption", item_id.to_le_bytes().as_ref(), index.to_le_bytes().as_ref()],
+        bump = receipt.bump,
+    )]
+    pub receipt: Account<'info, RedemptionReceipt>,
+}
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Required to resume once an expected upgrade authority is set
+    #[account(address = program_data_address())]
+    pub program_data: Option<Account<'info, ProgramData>>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyUpgradeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = program_data_address())]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+#[derive(Accounts)]
+pub struct CheckSolvency<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.vault)]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"jackpot"],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Option<Account<'info, Jackpot>>,
+
+    pub jackpot_vault: Option<Account<'info, TokenAccount>>,
+}
+
+#[derive(Accounts)]
+pub struct RecomputeChallengeRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct FinalizeWeek<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Plain init: a week can only be reported once and the report is never written again
+    #[account(
+        init,
+        payer = payer,
+        space = space::WEEKLY_REPORT,
+        seeds = [b"weekly_report", week.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub weekly_report: Account<'info, WeeklyReport>,
+
+    // Crank tip destination; no tip is paid when omitted
+    #[account(mut, token::mint = game_state.token_mint)]
+    pub tip_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetTestClock<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::TEST_CLOCK,
+        seeds = [b"test_clock"],
+        bump,
+    )]
+    pub test_clock: Account<'info, TestClock>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct ChallengeAward<'info> {
+    pub challenger: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player whose award is challenged
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"race_receipt", player.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = race_receipt.bump,
+    )]
+    pub race_receipt: Account<'info, RaceReceipt>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Required unless the challenger is the reviewer
+    #[account(
+        mut,
+        token::mint = game_state.token_mint,
+        token::authority = challenger,
+    )]
+    pub challenger_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct ResolveAwardDispute<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Player whose award is disputed
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"race_receipt", player.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = race_receipt.bump,
+    )]
+    pub race_receipt: Account<'info, RaceReceipt>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Receives the returned bond when a bonded challenge is upheld
+    #[account(
+        mut,
+        token::mint = game_state.token_mint,
+        token::authority = race_receipt.challenger,
+    )]
+    pub challenger_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct ReleaseAward<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player the award is paid to
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"race_receipt", player.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = race_receipt.bump,
+    )]
+    pub race_receipt: Account<'info, RaceReceipt>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct CastMvpVote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"race_receipt", voter.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = voter_receipt.bump,
+    )]
+    pub voter_receipt: Account<'info, RaceReceipt>,
+
+    /// CHECK: Participant voted for; participation is proven by their receipt
+    pub candidate: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"race_receipt", candidate.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = candidate_receipt.bump,
+    )]
+    pub candidate_receipt: Account<'info, RaceReceipt>,
+
+    #[account(
+        init_if_needed,
+        payer = voter,
+        space = space::MVP_BALLOT,
+        seeds = [b"mvp_ballot", race_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub mvp_ballot: Account<'info, MvpBallot>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = space::MVP_VOTE,
+        seeds = [b"mvp_vote", race_id.to_le_bytes().as_ref(), voter.key().as_ref()],
+        bump,
+    )]
+    pub mvp_vote: Account<'info, MvpVote>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct SettleMvp<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"mvp_ballot", race_id.to_le_bytes().as_ref()],
+        bump = mvp_ballot.bump,
+    )]
+    pub mvp_ballot: Account<'info, MvpBallot>,
+
+    /// CHECK: Must be the ballot leader, checked in `settle_mvp`
+    pub winner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = winner,
+    )]
+    pub winner_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AwardPartnerTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"partner_grant", partner_grant.partner_program.as_ref()],
+        bump = partner_grant.bump,
+    )]
+    pub partner_grant: Account<'info, PartnerGrant>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player receiving the award
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, used to identify the calling program
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(mission_id: u64, completion_id: [u8; 32])]
+pub struct AwardHoneycombMission<'info> {
+    // Signer supplied by the Honeycomb side; pays for the claim record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"honeycomb_config"],
+        bump = honeycomb_config.bump,
+    )]
+    pub honeycomb_config: Account<'info, HoneycombConfig>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = space::MISSION_CLAIM,
+        seeds = [b"mission_claim", completion_id.as_ref()],
+        bump,
+    )]
+    pub mission_claim: Account<'info, MissionClaim>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player receiving the award
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, used to identify the calling program
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimBridgeReward<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"bridge_config"],
+        bump = bridge_config.bump,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    /// CHECK: Posted VAA owned by the core bridge, parsed in `PostedVaa::load`
+    #[account(owner = bridge_config.wormhole_program @ ErrorCode::InvalidBridgeMessage)]
+    pub posted_vaa: UncheckedAccount<'info>,
+
+    /// CHECK: Claim PDA derived from the VAA's emitter and sequence; created in the handler
+    #[account(mut)]
+    pub bridge_claim: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player named in the VAA payload
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetBridgeConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::BRIDGE_CONFIG,
+        seeds = [b"bridge_config"],
+        bump,
+    )]
+    pub bridge_config: Account<'info, BridgeConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetHoneycombConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::HONEYCOMB_CONFIG,
+        seeds = [b"honeycomb_config"],
+        bump,
+    )]
+    pub honeycomb_config: Account<'info, HoneycombConfig>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(partner_program: Pubkey)]
+pub struct SetPartnerGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::PARTNER_GRANT,
+        seeds = [b"partner_grant", partner_program.as_ref()],
+        bump,
+    )]
+    pub partner_grant: Account<'info, PartnerGrant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(proposal_id: u64)]
+pub struct ProposeAdminAction<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::PROPOSAL,
+        seeds = [b"proposal", proposal_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Accounts after `proposal` are only needed by the actions that touch them
+#[derive(Accounts)]
+pub struct ExecuteAdminProposal<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"proposal", proposal.proposal_id.to_le_bytes().as_ref()],
+        bump = proposal.bump,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    // Required for rate proposals once the rate history has been initialized
+    #[account(
+        mut,
+        seeds = [b"rate_history"],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Account<'info, RateHistory>>,
+
+    #[account(mut)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub destination_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_sale"],
+        bump = token_sale.bump,
+    )]
+    pub token_sale: Option<Account<'info, TokenSale>>,
+
+    /// CHECK: Plain SOL destination; must match the proposal and be on the withdrawal allowlist
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct FundFeePool<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    // Lamport-only PDA; it stays owned by the system program so it can pay for account creation
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump,
+    )]
+    pub fee_pool: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SponsorPlayerAccounts<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Wallet being onboarded
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_pool"],
+        bump,
+    )]
+    pub fee_pool: SystemAccount<'info>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    /// CHECK: Player's $SPEEDY ATA, created here if missing
+    #[account(
+        mut,
+        address = anchor_spl::associated_token::get_associated_token_address(&player.key(), &token_mint.key()),
+    )]
+    pub player_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Player profile PDA, created here if missing
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRateHistory<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::RATE_HISTORY,
+        seeds = [b"rate_history"],
+        bump,
+    )]
+    pub rate_history: Account<'info, RateHistory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRentTreasury<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::RENT_TREASURY,
+        seeds = [b"rent_treasury"],
+        bump,
+    )]
+    pub rent_treasury: Account<'info, RentTreasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FundRentTreasury<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_treasury"],
+        bump = rent_treasury.bump,
+    )]
+    pub rent_treasury: Account<'info, RentTreasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(race_id: u64)]
+pub struct CloseRaceReceipt<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Player the receipt belongs to; receives the rent unless the treasury fronted it
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"race_receipt", player.key().as_ref(), race_id.to_le_bytes().as_ref()],
+        bump = race_receipt.bump,
+    )]
+    pub race_receipt: Account<'info, RaceReceipt>,
+
+    // Required when the treasury fronted the receipt's rent
+    #[account(
+        mut,
+        seeds = [b"rent_treasury"],
+        bump = rent_treasury.bump,
+    )]
+    pub rent_treasury: Option<Account<'info, RentTreasury>>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGameState<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Still in the original layout, so it is checked in `verify_account_layout` and
+    /// only deserialized after it has been grown
+    #[account(mut, seeds = [b"game_state"], bump)]
+    pub game_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct VerifyLayout<'info> {
+    /// CHECK: Layout checked in `verify_account_layout`
+    #[account(seeds = [b"game_state"], bump)]
+    pub game_state: UncheckedAccount<'info>,
+
+    /// CHECK: Layout checked in `verify_account_layout`
+    #[account(seeds = [b"buyback_config"], bump)]
+    pub buyback_config: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Layout checked in `verify_account_layout`
+    #[account(seeds = [b"jackpot"], bump)]
+    pub jackpot: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Layout checked in `verify_account_layout`
+    #[account(seeds = [b"token_sale"], bump)]
+    pub token_sale: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Layout checked in `verify_account_layout`
+    #[account(seeds = [b"config_export", [GameConfig::VERSION].as_ref()], bump)]
+    pub config_export: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct ExportConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // One buffer per config version, so a grown GameConfig never reuses a smaller export
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::CONFIG_EXPORT,
+        seeds = [b"config_export", [GameConfig::VERSION].as_ref()],
+        bump,
+    )]
+    pub config_export: Account<'info, ConfigExport>,
+
+    #[account(
+        seeds = [b"donation_stats"],
+        bump = donation_stats.bump,
+    )]
+    pub donation_stats: Option<Account<'info, DonationStats>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ImportConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Required once the rate history has been initialized
+    #[account(
+        mut,
+        seeds = [b"rate_history"],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Account<'info, RateHistory>>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::DONATION_STATS,
+        seeds = [b"donation_stats"],
+        bump,
+    )]
+    pub donation_stats: Account<'info, DonationStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeJackpot<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::JACKPOT,
+        seeds = [b"jackpot"],
+        bump,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = jackpot,
+    )]
+    pub jackpot_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(draw_id: u64)]
+pub struct EnterJackpotDraw<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::JACKPOT_ENTRY,
+        seeds = [b"jackpot_entry", draw_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub jackpot_entry: Account<'info, JackpotEntry>,
+
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(draw_id: u64)]
+pub struct DrawJackpot<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(address = game_state.jackpot.vault)]
+    pub jackpot_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = space::JACKPOT_DRAW,
+        seeds = [b"jackpot_draw", draw_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub jackpot_draw: Account<'info, JackpotDraw>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(draw_id: u64)]
+pub struct ClaimJackpot<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.bump,
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    #[account(
+        mut,
+        address = game_state.jackpot.vault,
+    )]
+    pub jackpot_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot_draw", draw_id.to_le_bytes().as_ref()],
+        bump = jackpot_draw.bump,
+    )]
+    pub jackpot_draw: Account<'info, JackpotDraw>,
+
+    #[account(
+        seeds = [b"jackpot_entry", draw_id.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump = jackpot_entry.bump,
+    )]
+    pub jackpot_entry: Account<'info, JackpotEntry>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct EnterRankedQueue<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // One open ticket per player
+    #[account(
+        init,
+        payer = player,
+        space = space::QUEUE_TICKET,
+        seeds = [b"queue_ticket", player.key().as_ref()],
+        bump,
+    )]
+    pub queue_ticket: Account<'info, QueueTicket>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveQueueTicket<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Ticket owner, receives the ticket rent
+    #[account(mut, address = queue_ticket.player)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"queue_ticket", player.key().as_ref()],
+        bump = queue_ticket.bump,
+    )]
+    pub queue_ticket: Account<'info, QueueTicket>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RefundExpiredQueueTicket<'info> {
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    /// CHECK: Ticket owner, receives the ticket rent
+    #[account(mut, address = queue_ticket.player)]
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"queue_ticket", player.key().as_ref()],
+        bump = queue_ticket.bump,
+    )]
+    pub queue_ticket: Account<'info, QueueTicket>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Crank tip destination; no tip is paid when omitted
+    #[account(mut, token::mint = game_state.token_mint)]
+    pub tip_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReportMatchResult<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    /// CHECK: Player whose rating is updated; only used to derive the profile PDA
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PreviewAward<'info> {
+    /// CHECK: Player the award would be paid to; nothing is written
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(address = game_state.vault)]
+    pub vault: Account<'info, TokenAccount>,
+
+    // Omitted for players without a profile yet
+    #[account(
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    /// CHECK: Pyth price update account, validated in `load_pyth_price`; required only when USD pricing is enabled
+    pub price_feed: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct AwardRelayTokens<'info> {
+    #[account(mut)]
+    pub captain: Signer<'info>,
+
+    // Vouches for the reported result and the team split
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Pyth price update account, validated in `load_pyth_price`; required only when USD pricing is enabled
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    // Authority co-signature, required while the oracle heartbeat is stale or for payouts
+    // above the co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(voucher: RewardVoucher, region: Region)]
+pub struct RedeemRegionalVoucher<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"region_pool", [region as u8].as_ref()],
+        bump = region_pool.bump,
+    )]
+    pub region_pool: Account<'info, RegionPool>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = region_pool.vault,
+    )]
+    pub region_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    // Fails to initialize if this exact voucher was already redeemed
+    #[account(
+        init,
+        payer = player,
+        space = space::USED_VOUCHER,
+        seeds = [b"used_voucher", voucher.regional_hash(region).as_ref()],
+        bump,
+    )]
+    pub used_voucher: Account<'info, UsedVoucher>,
+
+    /// CHECK: Instructions sysvar, used to inspect the ed25519 verification instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    // Authority co-signature, required while the oracle heartbeat is stale or for payouts
+    // above the co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(region: Region)]
+pub struct InitializeRegionPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::REGION_POOL,
+        seeds = [b"region_pool", [region as u8].as_ref()],
+        bump,
+    )]
+    pub region_pool: Account<'info, RegionPool>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = region_pool, // Shard is controlled by the region pool PDA
+    )]
+    pub region_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(region: Region)]
+pub struct UpdateRegionPool<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::RateManager, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"region_pool", [region as u8].as_ref()],
+        bump = region_pool.bump,
+    )]
+    pub region_pool: Account<'info, RegionPool>,
+}
+
+#[derive(Accounts)]
+#[instruction(voucher: RewardVoucher)]
+pub struct RedeemRewardVoucher<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    // Fails to initialize if this exact voucher was already redeemed
+    #[account(
+        init,
+        payer = player,
+        space = space::USED_VOUCHER,
+        seeds = [b"used_voucher", voucher.hash().as_ref()],
+        bump,
+    )]
+    pub used_voucher: Account<'info, UsedVoucher>,
+
+    /// CHECK: Instructions sysvar, used to inspect the ed25519 verification instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    // Authority co-signature, required while the oracle heartbeat is stale or for payouts
+    // above the co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Required once the rate history has been initialized
+    #[account(
+        mut,
+        seeds = [b"rate_history"],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Account<'info, RateHistory>>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub old_vault: Account<'info, TokenAccount>,
+
+    // Allowlisted cold wallet token account; required when the old vault still holds tokens
+    #[account(mut)]
+    pub old_vault_destination: Option<Account<'info, TokenAccount>>,
+
+    // Classic SPL Token mint, like the original: every game flow moves $SPEEDY with the token
+    // program's `transfer`, so Token-2022 mints (transfer hooks, transfer fees) can't be used
+    pub new_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = new_mint,
+        associated_token::authority = game_state,
+    )]
+    pub new_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SwapLegacyTokens<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        address = game_state.legacy_mint,
+    )]
+    pub legacy_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = legacy_mint,
+        associated_token::authority = player,
+    )]
+    pub player_legacy_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateTokenSale<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::TOKEN_SALE,
+        seeds = [b"token_sale"],
+        bump,
+    )]
+    pub token_sale: Account<'info, TokenSale>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = token_sale,
+    )]
+    pub sale_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BuySaleTokens<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"token_sale"],
+        bump = token_sale.bump,
+    )]
+    pub token_sale: Account<'info, TokenSale>,
+
+    #[account(
+        mut,
+        address = token_sale.sale_vault,
+    )]
+    pub sale_vault: Account<'info, TokenAccount>,
+
+    #[account(address = sale_vault.mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = space::SALE_PURCHASE,
+        seeds = [b"sale_purchase", buyer.key().as_ref()],
+        bump,
+    )]
+    pub sale_purchase: Account<'info, SalePurchase>,
+
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        associated_token::mint = token_mint,
+        associated_token::authority = buyer,
+    )]
+    pub buyer_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSaleProceeds<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"token_sale"],
+        bump = token_sale.bump,
+    )]
+    pub token_sale: Account<'info, TokenSale>,
+
+    /// CHECK: Plain SOL destination; must be on the withdrawal allowlist
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnsoldSaleTokens<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        seeds = [b"token_sale"],
+        bump = token_sale.bump,
+    )]
+    pub token_sale: Account<'info, TokenSale>,
+
+    #[account(
+        mut,
+        address = token_sale.sale_vault,
+    )]
+    pub sale_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = sale_vault.mint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(voucher: OnrampVoucher)]
+pub struct RedeemOnrampVoucher<'info> {
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"onramp_provider", onramp_provider.provider.as_ref()],
+        bump = onramp_provider.bump,
+    )]
+    pub onramp_provider: Account<'info, OnrampProvider>,
+
+    // Fails to initialize if this order was already redeemed
+    #[account(
+        init,
+        payer = wallet,
+        space = space::ONRAMP_ORDER,
+        seeds = [b"onramp_order", onramp_provider.provider.as_ref(), voucher.order_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub onramp_order: Account<'info, OnrampOrder>,
+
+    #[account(
+        mut,
+        seeds = [b"token_sale"],
+        bump = token_sale.bump,
+    )]
+    pub token_sale: Account<'info, TokenSale>,
+
+    #[account(
+        mut,
+        address = token_sale.sale_vault,
+    )]
+    pub sale_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = sale_vault.mint,
+        token::authority = wallet,
+    )]
+    pub wallet_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Instructions sysvar, used to inspect the ed25519 verification instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(provider: Pubkey)]
+pub struct SetOnrampProvider<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::ONRAMP_PROVIDER,
+        seeds = [b"onramp_provider", provider.as_ref()],
+        bump,
+    )]
+    pub onramp_provider: Account<'info, OnrampProvider>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepToColdStorage<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        constraint = game_state.vault_for_mint(&vault.mint) == Some(vault.key()) @ ErrorCode::InvalidVault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vault.mint,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(grant_id: u64)]
+pub struct CreateGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::GRANT,
+        seeds = [b"grant", grant_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub grant: Account<'info, Grant>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(grant_id: u64)]
+pub struct ReleaseGrantMilestone<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"grant", grant_id.to_le_bytes().as_ref()],
+        bump = grant.bump,
+    )]
+    pub grant: Account<'info, Grant>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Grant recipient, only used as the ATA owner
+    #[account(address = grant.recipient)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(grant_id: u64)]
+pub struct CancelGrant<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"grant", grant_id.to_le_bytes().as_ref()],
+        bump = grant.bump,
+    )]
+    pub grant: Account<'info, Grant>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct CommitWeeklyStandings<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = space::WEEKLY_STANDINGS,
+        seeds = [b"weekly_standings", week.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub weekly_standings: Account<'info, WeeklyStandings>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(week: u64)]
+pub struct ClaimStandingsPrize<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"weekly_standings", week.to_le_bytes().as_ref()],
+        bump = weekly_standings.bump,
+    )]
+    pub weekly_standings: Account<'info, WeeklyStandings>,
+
+    // One claim per player per week
+    #[account(
+        init,
+        payer = player,
+        space = space::STANDINGS_CLAIM,
+        seeds = [b"standings_claim", week.to_le_bytes().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub standings_claim: Account<'info, StandingsClaim>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(season: u64, track_id: u64, player: Pubkey)]
+pub struct SubmitScore<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::LEADERBOARD,
+        seeds = [b"leaderboard", season.to_le_bytes().as_ref(), track_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::PLAYER_BEST,
+        seeds = [b"player_best", season.to_le_bytes().as_ref(), track_id.to_le_bytes().as_ref(), player.as_ref()],
+        bump,
+    )]
+    pub player_best: Account<'info, PlayerBest>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct RegisterTrack<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::TRACK,
+        seeds = [b"track", track_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub track: Account<'info, Track>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct UpdateTrack<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"track", track_id.to_le_bytes().as_ref()],
+        bump = track.bump,
+    )]
+    pub track: Account<'info, Track>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct SubmitTrack<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = space::TRACK_SUBMISSION,
+        seeds = [b"track_submission", track_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub track_submission: Account<'info, TrackSubmission>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = track_submission,
+    )]
+    pub submission_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", creator.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct VoteOnTrack<'info> {
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"track_submission", track_id.to_le_bytes().as_ref()],
+        bump = track_submission.bump,
+    )]
+    pub track_submission: Account<'info, TrackSubmission>,
+
+    #[account(
+        mut,
+        address = track_submission.escrow,
+    )]
+    pub submission_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = curator,
+        space = space::CURATION_VOTE,
+        seeds = [b"curation_vote", track_id.to_le_bytes().as_ref(), curator.key().as_ref()],
+        bump,
+    )]
+    pub curation_vote: Account<'info, CurationVote>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = curator,
+    )]
+    pub curator_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", curator.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct FinalizeTrackSubmission<'info> {
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"track_submission", track_id.to_le_bytes().as_ref()],
+        bump = track_submission.bump,
+    )]
+    pub track_submission: Account<'info, TrackSubmission>,
+
+    #[account(
+        mut,
+        address = track_submission.escrow,
+    )]
+    pub submission_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = track_submission.creator,
+    )]
+    pub creator_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", track_submission.creator.as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct WithdrawCurationStake<'info> {
+    #[account(mut)]
+    pub curator: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"track_submission", track_id.to_le_bytes().as_ref()],
+        bump = track_submission.bump,
+    )]
+    pub track_submission: Account<'info, TrackSubmission>,
+
+    #[account(
+        mut,
+        address = track_submission.escrow,
+    )]
+    pub submission_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = curator,
+        seeds = [b"curation_vote", track_id.to_le_bytes().as_ref(), curator.key().as_ref()],
+        bump = curation_vote.bump,
+    )]
+    pub curation_vote: Account<'info, CurationVote>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = curator,
+    )]
+    pub curator_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", curator.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct RegisterCommunityTrack<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        seeds = [b"track_submission", track_id.to_le_bytes().as_ref()],
+        bump = track_submission.bump,
+    )]
+    pub track_submission: Account<'info, TrackSubmission>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = space::TRACK,
+        seeds = [b"track", track_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub track: Account<'info, Track>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"track_owner_mint", track_id.to_le_bytes().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = game_state,
+    )]
+    pub track_owner_mint: Account<'info, Mint>,
+
+    /// CHECK: Submission creator receiving the ownership NFT
+    #[account(address = track_submission.creator)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = track_owner_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_nft_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct SubmitWorldRecord<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::WORLD_RECORD,
+        seeds = [b"world_record", track_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub world_record: Account<'info, WorldRecord>,
+
+    /// CHECK: New record holder
+    pub player: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Authority co-signature, required while the oracle heartbeat is stale or for bounties
+    // above the co-sign threshold
+    pub cosigner: Option<Signer<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(track_id: u64)]
+pub struct FundWorldRecordBounty<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"world_record", track_id.to_le_bytes().as_ref()],
+        bump = world_record.bump,
+    )]
+    pub world_record: Account<'info, WorldRecord>,
+
+    #[account(
+        mut,
+        associated_token::mint = game_state.token_mint,
+        associated_token::authority = funder,
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64, recipient: Pubkey)]
+pub struct CreatePaymentStream<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::PAYMENT_STREAM,
+        seeds = [b"payment_stream", stream_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", recipient.as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct ClaimPaymentStream<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_stream", stream_id.to_le_bytes().as_ref()],
+        bump = payment_stream.bump,
+        has_one = recipient @ ErrorCode::Unauthorized,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = token_mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", recipient.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedRewards<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(stream_id: u64)]
+pub struct CancelPaymentStream<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"payment_stream", stream_id.to_le_bytes().as_ref()],
+        bump = payment_stream.bump,
+    )]
+    pub payment_stream: Account<'info, PaymentStream>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStableVault<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(constraint = stable_mint.key() != game_state.token_mint @ ErrorCode::InvalidTournamentConfig)]
+    pub stable_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = stable_mint,
+        associated_token::authority = game_state,
+    )]
+    pub stable_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConfigureBuyback<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::BUYBACK_CONFIG,
+        seeds = [b"buyback_config"],
+        bump,
+    )]
+    pub buyback_config: Account<'info, BuybackConfig>,
+
+    pub stable_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = stable_mint,
+        associated_token::authority = game_state,
+    )]
+    pub stable_treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteBuyback<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::Treasurer, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"buyback_config"],
+        bump = buyback_config.bump,
+    )]
+    pub buyback_config: Account<'info, BuybackConfig>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = buyback_config.stable_treasury,
+    )]
+    pub stable_treasury: Account<'info, TokenAccount>,
+
+    /// CHECK: Must match the configured AMM program
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct AwardWelcomeBonus<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Required only when an attestation verifier is configured
+    #[account(
+        mut,
+        seeds = [b"attestation", attestation.attestation_hash.as_ref()],
+        bump = attestation.bump,
+        has_one = player @ ErrorCode::AttestationMismatch,
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// CHECK: Pyth price update account, validated in `load_pyth_price`; required only when USD pricing is enabled
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct OnboardPlayer<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+        constraint = player_token_account.delegate.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+        constraint = player_token_account.close_authority.is_none() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"starter_car_mint"],
+        bump,
+    )]
+    pub starter_car_mint: Account<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = starter_car_mint,
+        associated_token::authority = player,
+    )]
+    pub player_car_account: Account<'info, TokenAccount>,
+
+    // Required only when an attestation verifier is configured
+    #[account(
+        mut,
+        seeds = [b"attestation", attestation.attestation_hash.as_ref()],
+        bump = attestation.bump,
+        has_one = player @ ErrorCode::AttestationMismatch,
+    )]
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// CHECK: Pyth price update account, validated in `load_pyth_price`; required only when USD pricing is enabled
+    pub price_feed: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Memo program; when provided, each payout carries an attribution memo
+    #[account(address = MEMO_PROGRAM_ID)]
+    pub memo_program: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(code_hash: [u8; 32])]
+pub struct CreatePromoCode<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::PROMO_CODE,
+        seeds = [b"promo_code", code_hash.as_ref()],
+        bump,
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPromoCode<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"promo_code", promo_code.code_hash.as_ref()],
+        bump = promo_code.bump,
+    )]
+    pub promo_code: Account<'info, PromoCode>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PROMO_REDEMPTION,
+        seeds = [b"promo_redemption", promo_code.key().as_ref(), player.key().as_ref()],
+        bump,
+    )]
+    pub promo_redemption: Account<'info, PromoRedemption>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Required only for cosmetic-unlock codes
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::INVENTORY,
+        seeds = [b"inventory", player.key().as_ref()],
+        bump,
+    )]
+    pub inventory: Option<Account<'info, Inventory>>,
+
+    #[account(
+        seeds = [b"ticket_config"],
+        bump = ticket_config.bump,
+    )]
+    pub ticket_config: Option<Account<'info, TicketConfig>>,
+
+    #[account(
+        mut,
+        constraint = ticket_config.as_ref().is_some_and(|config| ticket_mint.key() == config.ticket_mint)
+            @ ErrorCode::InvalidTicketConfig,
+    )]
+    pub ticket_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = ticket_config.as_ref().is_some_and(|config| player_ticket_account.mint == config.ticket_mint)
+            @ ErrorCode::InvalidTicketConfig,
+        constraint = player_ticket_account.owner == player.key() @ ErrorCode::InvalidPlayerTokenAccount,
+    )]
+    pub player_ticket_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct GrantBuff<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PurchaseBuff<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct ListCosmetic<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::COSMETIC_LISTING,
+        seeds = [b"cosmetic_listing", item_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub listing: Account<'info, CosmeticListing>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(item_id: u64)]
+pub struct BuyCosmetic<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"cosmetic_listing", item_id.to_le_bytes().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, CosmeticListing>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::INVENTORY,
+        seeds = [b"inventory", player.key().as_ref()],
+        bump,
+    )]
+    pub inventory: Account<'info, Inventory>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct AwardCosmetic<'info> {
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init_if_needed,
+        payer = oracle,
+        space = space::INVENTORY,
+        seeds = [b"inventory", player.as_ref()],
+        bump,
+    )]
+    pub inventory: Account<'info, Inventory>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EquipCosmetic<'info> {
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"inventory", player.key().as_ref()],
+        bump = inventory.bump,
+    )]
+    pub inventory: Account<'info, Inventory>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeStarterCar<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"starter_car_mint"],
+        bump,
+        mint::decimals = 0,
+        mint::authority = game_state,
+    )]
+    pub starter_car_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(attestation_hash: [u8; 32])]
+pub struct RecordAttestation<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = verifier.key() == game_state.attestation_verifier @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = player,
+        space = space::ATTESTATION,
+        seeds = [b"attestation", attestation_hash.as_ref()],
+        bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SpendTokens<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    // Required when a jackpot share of spends is configured
+    #[account(mut)]
+    pub jackpot_vault: Option<Account<'info, TokenAccount>>,
+
+    // Charity round-up accounts; required when the player has opted in
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Option<Account<'info, PlayerProfile>>,
+
+    #[account(
+        mut,
+        seeds = [b"donation_stats"],
+        bump = donation_stats.bump,
+    )]
+    pub donation_stats: Option<Account<'info, DonationStats>>,
+
+    #[account(mut, token::mint = token_mint)]
+    pub charity_token_account: Option<Account<'info, TokenAccount>>,
+
+    // Required when the burn curve keeps part of the spend
+    #[account(mut, address = game_state.vault @ ErrorCode::InvalidVault)]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SetDonationBps<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAutoStake<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimEverything<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimEverythingAsStable<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        seeds = [b"buyback_config"],
+        bump = buyback_config.bump,
+    )]
+    pub buyback_config: Account<'info, BuybackConfig>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(address = buyback_config.stable_mint)]
+    pub stable_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = stable_mint,
+        associated_token::authority = player,
+    )]
+    pub player_stable_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Must match the configured AMM program
+    #[account(executable)]
+    pub amm_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+
+    /// CHECK: The player's PlayerIndex PDA, kept in sync once it exists
+    #[account(
+        mut,
+        seeds = [b"player_index", player.key().as_ref()],
+        bump,
+    )]
+    pub player_index: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey, year: u16)]
+pub struct CloseYearlyStatement<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = space::YEARLY_STATEMENT,
+        seeds = [b"yearly_statement", player.as_ref(), year.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub yearly_statement: Account<'info, YearlyStatement>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeWinnings<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(address = game_state.token_mint)]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        address = game_state.vault,
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCharityCampaign<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = space::DONATION_STATS,
+        seeds = [b"donation_stats"],
+        bump,
+    )]
+    pub donation_stats: Account<'info, DonationStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConvertToCredits<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        address = game_state.token_mint,
+    )]
+    pub token_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = player,
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = space::PLAYER_PROFILE,
+        seeds = [b"player_profile", player.key().as_ref()],
+        bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct DeductCredits<'info> {
+    pub oracle: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&oracle.key(), Role::Oracle, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        mut,
+        seeds = [b"player_profile", player.as_ref()],
+        bump = player_profile.bump,
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateGameState<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Required once the rate history has been initialized
+    #[account(
+        mut,
+        seeds = [b"rate_history"],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Account<'info, RateHistory>>,
+}
+
+// Rate-setting instructions, open to the authority and rate managers
+#[derive(Accounts)]
+pub struct UpdateRates<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        constraint = game_state.authorizes(&authority.key(), Role::RateManager, role_assignment.as_deref()) @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    // Needed when the signer acts through a role assignment
+    pub role_assignment: Option<Account<'info, RoleAssignment>>,
+
+    // Required once the rate history has been initialized
+    #[account(
+        mut,
+        seeds = [b"rate_history"],
+        bump = rate_history.bump,
+    )]
+    pub rate_history: Option<Account<'info, RateHistory>>,
+}
+
+#[derive(Accounts)]
+#[instruction(new_oracle: Pubkey, new_co_authority: Pubkey, new_treasurer: Pubkey)]
+pub struct RotateKeys<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // Required once game_state.co_authority is set
+    pub co_authority: Option<Signer<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        close = authority,
+        constraint = old_treasurer_role.role == Role::Treasurer @ ErrorCode::InvalidKeyRotation,
+    )]
+    pub old_treasurer_role: Option<Account<'info, RoleAssignment>>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::ROLE_ASSIGNMENT,
+        seeds = [b"role", [Role::Treasurer as u8].as_ref(), new_treasurer.as_ref()],
+        bump,
+    )]
+    pub new_treasurer_role: Option<Account<'info, RoleAssignment>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct AssignRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = space::ROLE_ASSIGNMENT,
+        seeds = [b"role", [role as u8].as_ref(), holder.as_ref()],
+        bump,
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(role: Role, holder: Pubkey)]
+pub struct RevokeRole<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"game_state"],
+        bump = game_state.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub game_state: Account<'info, GameState>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"role", [role as u8].as_ref(), holder.as_ref()],
+        bump = role_assignment.bump,
+    )]
+    pub role_assignment: Account<'info, RoleAssignment>,
+}
+
+// Data Structures
+#[account]
+pub struct GameState {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub vault: Pubkey,        // Vault that holds tokens for rewards
+    pub total_distributed: u64,
+    pub token_rates: TokenRates,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub attestation_verifier: Pubkey, // Default pubkey = attestation not required
+    pub probation: ProbationConfig,
+    pub oracle: Pubkey, // Signs reward vouchers; default pubkey = vouchers disabled
+    pub voucher_clock_tolerance_secs: i64, // Grace period after a voucher's expires_at
+    pub legacy_mint: Pubkey,                // Previous mint swappable via swap_legacy_tokens
+    pub migration_numerator: u64,
+    pub migration_denominator: u64,
+    pub usd_pricing: UsdPricingConfig,
+    pub usd_rates: TokenRates, // Reward rates in USD cents, used when usd_pricing is enabled
+    pub drift_rates: DriftRates,
+    pub elimination_rates: EliminationRates,
+    pub handicap: HandicapConfig,
+    pub ranked_queue: RankedQueueConfig,
+    pub jackpot: JackpotConfig,
+    pub clawback_timelock_secs: i64, // Delay between proposing and executing a clawback
+    pub vip_role: VipRoleConfig,
+    pub total_burned: u64, // $SPEEDY burned through spends and buybacks
+    pub total_staked: u64, // $SPEEDY currently held as ranked queue stakes
+    pub stable_mint: Pubkey,  // Stablecoin paid from the side vault; default pubkey = no side vault
+    pub stable_vault: Pubkey, // Stablecoin ATA owned by game_state, funds stable prize pools
+    pub fee_sponsor_cap_lamports: u64, // Rent the fee pool covers per player; 0 = sponsorship disabled
+    pub crank_tip: CrankTipConfig,
+    pub last_crank_tip_at: [i64; CrankKind::COUNT], // Indexed by `CrankKind`
+    pub features: FeatureGate,
+    pub registry_counts: [u64; RegistryKind::COUNT], // Accounts listed per `RegistryKind`
+    pub dispute: DisputeConfig,
+    pub total_pending_awards: u64, // Race awards held in the vault until their dispute window closes
+    pub paused: bool, // Blocks every payout that counts toward total_distributed
+    pub watchdog: WatchdogConfig,
+    pub watchdog_state: WatchdogState,
+    pub withdrawal_allowlist: WithdrawalAllowlist,
+    pub last_heartbeat_at: i64,
+    pub heartbeat_window_secs: i64, // 0 = failsafe disabled
+    pub vesting: VestingConfig,
+    pub total_vesting: u64, // Vested-award balances still held in the vault
+    pub challenge_balance: ChallengeBalance,
+    pub last_solvent_at: i64,        // Time of the last passing `check_solvency`
+    pub solvency_max_age_secs: i64,  // Awards require a passing check this recent; 0 = not required
+    pub expected_upgrade_authority: Pubkey, // Multisig/DAO that must hold the upgrade authority; default = unchecked
+    pub auto_stake: AutoStakeConfig,
+    pub total_auto_staked: u64, // Auto-staked winnings and bonuses still held in the vault
+    pub buff_shop: BuffShopItem,
+    pub prize_hold: PrizeHoldConfig,
+    pub cosign_threshold: u64,
+    pub total_fee_loans: u64,   // Entry fees fronted by the vault against vesting balances
+    pub mvp: MvpConfig,
+    pub world_record_bounty: u64, // Bounty each track's record is topped back up to once claimed
+    pub curation: CurationConfig,
+    pub rate_history_enabled: bool, // Rate changes must append to the RateHistory account
+    pub co_authority: Pubkey, // Must co-sign key rotations; default pubkey = not required
+    pub oracle_epoch: u64,    // Signed into vouchers; vouchers from an older epoch are rejected
+    pub halving: HalvingConfig,
+    pub halving_era: u8, // Rates are divided by 2^halving_era; advanced by record_distribution
+    pub burn_curve: BurnCurve,
+    pub proposals_required: bool, // Rate and treasury changes only go through admin proposals
+    pub total_returned: u64, // Spent tokens routed to the vault or jackpot instead of burned
+}
+
+impl GameState {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + TokenRates::LEN + 1 + 1 + 32 + ProbationConfig::LEN + 32 + 8 + 32 + 8 + 8
+        + UsdPricingConfig::LEN + TokenRates::LEN + DriftRates::LEN
+        + EliminationRates::LEN + HandicapConfig::LEN
+        + RankedQueueConfig::LEN + JackpotConfig::LEN + 8
+        + VipRoleConfig::LEN + 8 + 8 + 32 + 32 + 8
+        + CrankTipConfig::LEN + 8 * CrankKind::COUNT + FeatureGate::LEN
+        + 8 * RegistryKind::COUNT + DisputeConfig::LEN + 8
+        + 1 + WatchdogConfig::LEN + WatchdogState::LEN + WithdrawalAllowlist::LEN
+        + 8 + 8 + VestingConfig::LEN + 8 + ChallengeBalance::LEN
+        + 8 + 8 + 32 + AutoStakeConfig::LEN + 8 + BuffShopItem::LEN
+        + PrizeHoldConfig::LEN + 8 + 8 + MvpConfig::LEN + 8
+        + CurationConfig::LEN + 1 + 32 + 8 + HalvingConfig::LEN + 1
+        + BurnCurve::LEN + 1 + 8;
+
+    // Defaults for every field after the original layout (authority through is_initialized).
+    // Shared by initialize_token and migrate_game_state so both produce the same state.
+    pub fn set_defaults(&mut self) {
+        self.attestation_verifier = Pubkey::default();
+        self.oracle = Pubkey::default();
+        self.voucher_clock_tolerance_secs = 60;
+        self.legacy_mint = Pubkey::default();
+        self.migration_numerator = 0;
+        self.migration_denominator = 0;
+        self.usd_pricing = UsdPricingConfig {
+            enabled: false,
+            price_feed_id: [0u8; 32],
+            max_staleness_secs: 60,
+            max_confidence_bps: 200, // Reject prices with >2% confidence interval
+        };
+        self.probation = ProbationConfig {
+            duration_secs: 0,           // Disabled until configured
+            reward_bps: 5_000,          // 50% of computed rewards
+            release_after_races: 10,
+        };
+
+        self.drift_rates = DriftRates {
+            per_1000_points: 1_000_000, // 1 token per 1000 points
+            max_reward: 200_000_000,    // 200 tokens
+        };
+
+        self.elimination_rates = EliminationRates {
+            per_survival_minute: 5_000_000,                // 5 tokens
+            per_elimination: 10_000_000,                   // 10 tokens
+            podium_multiplier_bps: [20_000, 15_000, 12_500], // 2x, 1.5x, 1.25x
+        };
+
+        self.handicap = HandicapConfig {
+            low_rating_threshold: 1000,
+            catch_up_bps: 12_500,       // +25% below 1000
+            high_rating_threshold: 1800,
+            top_rated_bps: 9_500,       // -5% above 1800
+        };
+
+        self.ranked_queue = RankedQueueConfig {
+            stake_amount: 5_000_000,    // 5 tokens
+            ticket_timeout_secs: 3_600, // 1 hour
+        };
+        self.jackpot = JackpotConfig {
+            burn_share_bps: 0,                          // Disabled until the jackpot is initialized
+            draw_interval_secs: speedy_core::SECONDS_PER_WEEK,
+            vault: Pubkey::default(),
+        };
+        self.clawback_timelock_secs = 48 * 60 * 60;
+        self.vip_role = VipRoleConfig {
+            mint: Pubkey::default(), // Created by initialize_vip_role
+            min_lifetime_earnings: 0,
+            min_token_balance: 0,
+        };
+        self.total_burned = 0;
+        self.total_staked = 0;
+        self.stable_mint = Pubkey::default(); // Set by initialize_stable_vault
+        self.stable_vault = Pubkey::default();
+        self.fee_sponsor_cap_lamports = 0;
+        self.crank_tip = CrankTipConfig {
+            amount: 0,                  // Tips disabled until configured
+            cooldown_secs: 60 * 60,
+        };
+        self.last_crank_tip_at = [0; CrankKind::COUNT];
+        self.features = FeatureGate(FeatureGate::ALL);
+        self.registry_counts = [0; RegistryKind::COUNT];
+        self.dispute = DisputeConfig {
+            window_secs: 0, // Awards pay out immediately
+            reviewer: Pubkey::default(),
+            challenge_bond: 0,
+        };
+        self.total_pending_awards = 0;
+        self.paused = false;
+        self.watchdog = WatchdogConfig {
+            window_slots: 9_000,             // ~1 hour
+            anomaly_multiplier_bps: 50_000,  // Pause at 5x the average window
+            min_history_windows: 24,
+        };
+        self.watchdog_state = WatchdogState::default();
+        self.withdrawal_allowlist = WithdrawalAllowlist::default();
+        self.last_heartbeat_at = 0;
+        self.heartbeat_window_secs = 0;
+        self.vesting = VestingConfig { threshold: 0, duration_secs: 7 * 24 * 60 * 60 };
+        self.total_vesting = 0;
+        self.last_solvent_at = 0;
+        self.solvency_max_age_secs = 0;
+        self.expected_upgrade_authority = Pubkey::default();
+        self.auto_stake = AutoStakeConfig { bonus_bps: 200, lock_secs: 30 * 24 * 60 * 60 };
+        self.total_auto_staked = 0;
+        self.buff_shop = BuffShopItem {
+            target: BuffTarget::AllRewards,
+            multiplier_bps: BPS_DENOMINATOR,
+            duration_secs: 0,
+            price: 0,
+        };
+        self.prize_hold = PrizeHoldConfig { threshold: 0, hold_secs: 3 * 24 * 60 * 60 };
+        self.cosign_threshold = 0;
+        self.total_fee_loans = 0;
+        self.mvp = MvpConfig { bonus: 0, window_secs: 15 * 60, min_votes: 2 };
+        self.world_record_bounty = 0;
+        self.curation = CurationConfig {
+            min_stake: 0,
+            voting_secs: 3 * 24 * 60 * 60,
+            slash_bps: 2_500,
+            creator_cut_bps: 200,
+        };
+        self.rate_history_enabled = false;
+        self.co_authority = Pubkey::default(); // Set by rotate_keys
+        self.oracle_epoch = 0;
+        self.halving = HalvingConfig { every_distributed: 0, every_slots: 0, start_slot: 0 };
+        self.halving_era = 0;
+        self.burn_curve = BurnCurve {
+            floor_bps: BPS_DENOMINATOR,
+            ceiling_bps: BPS_DENOMINATOR,
+            low_supply: 0,
+            high_supply: 0,
+        };
+        self.proposals_required = false;
+        self.total_returned = 0;
+        self.challenge_balance = ChallengeBalance {
+            target_completions: 0,
+            step_bps: 1_000,
+            min_multiplier_bps: 5_000,
+            max_multiplier_bps: 20_000,
+            day: 0,
+            completions: [0; ChallengeBalance::DIFFICULTIES],
+            multiplier_bps: [BPS_DENOMINATOR; ChallengeBalance::DIFFICULTIES],
+        };
+
+        // USD-pegged rates (in USD cents), used only when usd_pricing is enabled
+        self.usd_rates = TokenRates {
+            race_completion: 10,
+            race_win: 5,
+            distance_per_100m: 0,
+            obstacle_avoided: 0,
+            bonus_collected: 0,
+            daily_challenge_easy: 5,
+            daily_challenge_medium: 10,
+            daily_challenge_hard: 20,
+            tournament_participation: 10,
+            tournament_winner: 100,
+            welcome_bonus: 10,
+            staking_per_hour_common: 0,
+            staking_per_hour_rare: 0,
+            staking_per_hour_epic: 1,
+            staking_per_hour_legendary: 2,
+        };
+    }
+
+    // Count a payout toward total_distributed and the watchdog's rolling window. Fails while
+    // paused; the payout that pushes the current window past the anomaly threshold still
+    // completes, but pauses every payout after it until the authority unpauses.
+    pub fn record_distribution(&mut self, amount: u64) -> Result<()> {
+        require!(!self.paused, ErrorCode::Paused);
+        self.total_distributed = self.total_distributed.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+
+        let slot = Clock::get()?.slot;
+        let era = self.halving.era(self.total_distributed, slot);
+        if era > self.halving_era {
+            self.halving_era = era;
+            emit!(HalvingEraStarted {
+                era,
+                total_distributed: self.total_distributed,
+                slot,
+            });
+        }
+
+        let config = self.watchdog;
+        let state = &mut self.watchdog_state;
+        if slot >= state.window_start_slot.saturating_add(config.window_slots) {
+            // Idle gaps count as a single window so quiet periods don't drag the average down
+            if state.windows_observed > 0 || state.window_distributed > 0 {
+                let observed = state.windows_observed.min(config.min_history_windows as u32) as u128;
+                state.average_per_window = ((state.average_per_window as u128 * observed
+                    + state.window_distributed as u128) / (observed + 1)) as u64;
+                state.windows_observed = state.windows_observed.saturating_add(1);
+            }
+            state.window_start_slot = slot;
+            state.window_distributed = 0;
+        }
+        state.window_distributed = state.window_distributed.saturating_add(amount);
+
+        let threshold = (state.average_per_window as u128 * config.anomaly_multiplier_bps as u128
+            / BPS_DENOMINATOR as u128) as u64;
+        if config.anomaly_multiplier_bps > 0
+            && state.windows_observed >= config.min_history_windows as u32
+            && state.window_distributed > threshold
+        {
+            self.paused = true;
+            emit!(WatchdogTripped {
+                window_distributed: state.window_distributed,
+                average_per_window: state.average_per_window,
+                slot,
+            });
+        }
+        Ok(())
+    }
+
+    // Payouts the oracle signs for (direct and relay awards, world-record bounties and reward
+    // vouchers) need an authority co-signature once the oracle has missed its heartbeat
+    // window, so an abandoned or hijacked backend cannot keep paying out alone
+    pub fn require_live_oracle(&self, cosigner: Option<&Signer>, now: i64) -> Result<()> {
+        if self.heartbeat_window_secs == 0
+            || now <= self.last_heartbeat_at.saturating_add(self.heartbeat_window_secs)
+        {
+            return Ok(());
+        }
+        require!(
+            cosigner.map(|signer| signer.key()) == Some(self.authority),
+            ErrorCode::OracleHeartbeatStale
+        );
+        Ok(())
+    }
+
+    // Payouts above the co-sign threshold need the authority to sign alongside the oracle,
+    // so only routine small awards clear on the oracle's signature alone. Every payout that
+    // checks this is oracle-signed; a stream of awards kept just under the threshold is still
+    // counted by the distribution watchdog, which pauses payouts past its window limit.
+    pub fn require_cosign(&self, cosigner: Option<&Signer>, amount: u64) -> Result<()> {
+        if self.cosign_threshold == 0 || amount <= self.cosign_threshold {
+            return Ok(());
+        }
+        require!(
+            cosigner.map(|signer| signer.key()) == Some(self.authority),
+            ErrorCode::CosignatureRequired
+        );
+        Ok(())
+    }
+
+    // Whether `signer` may act in `role`: the key that owns the duty (the oracle for Oracle,
+    // the authority otherwise) or the holder of a matching role assignment. Oracle assignments
+    // only count in the epoch they were granted, so rotate_keys and bump_oracle_epoch revoke
+    // them along with the old oracle key.
+    pub fn authorizes(&self, signer: &Pubkey, role: Role, assignment: Option<&RoleAssignment>) -> bool {
+        let owner = if role == Role::Oracle { self.oracle } else { self.authority };
+        *signer == owner
+            || assignment.is_some_and(|assignment| {
+                assignment.role == role
+                    && assignment.holder == *signer
+                    && (role != Role::Oracle || assignment.oracle_epoch == self.oracle_epoch)
+            })
+    }
+
+    // Tokens the main vault owes to players: held race awards, vesting balances, auto-staked
+    // winnings and ranked queue stakes. Vesting already spent on entry fee loans has left the
+    // vault.
+    pub fn vault_liabilities(&self) -> u64 {
+        self.total_pending_awards
+            .saturating_add(self.total_vesting.saturating_sub(self.total_fee_loans))
+            .saturating_add(self.total_auto_staked)
+            .saturating_add(self.total_staked)
+    }
+
+    // Payouts from the main vault may only spend what is not already owed to players.
+    // `settled` is the part of `amount` that pays off one of those liabilities.
+    pub fn vault_covers(&self, vault_balance: u64, amount: u64, settled: u64) -> bool {
+        let reserved = self.vault_liabilities().saturating_sub(settled);
+        vault_balance.saturating_sub(reserved) >= amount
+    }
+
+    pub fn require_vault_covers(&self, vault_balance: u64, amount: u64, settled: u64) -> Result<()> {
+        require!(
+            self.vault_covers(vault_balance, amount, settled),
+            ErrorCode::InsufficientVaultBalance
+        );
+        Ok(())
+    }
+
+    pub fn require_recent_solvency(&self, now: i64) -> Result<()> {
+        require!(
+            self.solvency_max_age_secs == 0
+                || now <= self.last_solvent_at.saturating_add(self.solvency_max_age_secs),
+            ErrorCode::SolvencyCheckStale
+        );
+        Ok(())
+    }
+
+    // Direct rate and treasury instructions are closed once proposals are required
+    pub fn require_direct_admin(&self) -> Result<()> {
+        require!(!self.proposals_required, ErrorCode::ProposalRequired);
+        Ok(())
+    }
+
+    // Registry page the next account of `kind` is listed on
+    pub fn registry_page_index(&self, kind: RegistryKind) -> u64 {
+        self.registry_counts[kind as usize] / RegistryPage::MAX_ENTRIES as u64
+    }
+
+    // Reject the calling instruction while its subsystem is switched off
+    pub fn require_feature(&self, feature: u64) -> Result<()> {
+        require!(self.features.is_enabled(feature), ErrorCode::FeatureDisabled);
+        Ok(())
+    }
+
+    // Mint used for a pool currency, if that currency is set up
+    pub fn mint_for(&self, currency: PoolCurrency) -> Option<Pubkey> {
+        match currency {
+            PoolCurrency::Speedy => Some(self.token_mint),
+            PoolCurrency::Stable if self.stable_mint != Pubkey::default() => Some(self.stable_mint),
+            PoolCurrency::Stable => None,
+        }
+    }
+
+    // Game-owned vault holding `mint`, if the program keeps one
+    pub fn vault_for_mint(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.token_mint {
+            Some(self.vault)
+        } else if *mint == self.stable_mint && self.stable_mint != Pubkey::default() {
+            Some(self.stable_vault)
+        } else {
+            None
+        }
+    }
+
+    // Environment-independent economy settings; keys, mints and vaults are left out
+    pub fn config(&self, charity_wallet: Pubkey) -> GameConfig {
+        GameConfig {
+            token_rates: self.token_rates,
+            probation: self.probation,
+            voucher_clock_tolerance_secs: self.voucher_clock_tolerance_secs,
+            usd_pricing: self.usd_pricing,
+            usd_rates: self.usd_rates,
+            drift_rates: self.drift_rates,
+            elimination_rates: self.elimination_rates,
+            handicap: self.handicap,
+            ranked_queue: self.ranked_queue,
+            jackpot_burn_share_bps: self.jackpot.burn_share_bps,
+            jackpot_draw_interval_secs: self.jackpot.draw_interval_secs,
+            features: self.features,
+            burn_curve: self.burn_curve,
+            halving: self.halving,
+            dispute: self.dispute,
+            watchdog: self.watchdog,
+            crank_tip: self.crank_tip,
+            prize_hold: self.prize_hold,
+            cosign_threshold: self.cosign_threshold,
+            vesting: self.vesting,
+            mvp: self.mvp,
+            curation: self.curation,
+            charity_wallet,
+        }
+    }
+
+    pub fn apply_config(&mut self, config: &GameConfig) {
+        self.token_rates = config.token_rates;
+        self.probation = config.probation;
+        self.voucher_clock_tolerance_secs = config.voucher_clock_tolerance_secs;
+        self.usd_pricing = config.usd_pricing;
+        self.usd_rates = config.usd_rates;
+        self.drift_rates = config.drift_rates;
+        self.elimination_rates = config.elimination_rates;
+        self.handicap = config.handicap;
+        self.ranked_queue = config.ranked_queue;
+        self.jackpot.burn_share_bps = config.jackpot_burn_share_bps;
+        self.jackpot.draw_interval_secs = config.jackpot_draw_interval_secs;
+        self.features = config.features;
+        self.burn_curve = config.burn_curve;
+        self.halving = config.halving;
+        self.dispute = config.dispute;
+        self.watchdog = config.watchdog;
+        self.crank_tip = config.crank_tip;
+        self.prize_hold = config.prize_hold;
+        self.cosign_threshold = config.cosign_threshold;
+        self.vesting = config.vesting;
+        self.mvp = config.mvp;
+        self.curation = config.curation;
+    }
+
+    // Rates used by the reward math: USD cents when USD pricing is enabled, raw tokens otherwise,
+    // halved once per emission era
+    pub fn active_rates(&self) -> Result<TokenRates> {
+        let rates = if self.usd_pricing.enabled { &self.usd_rates } else { &self.token_rates };
+        let era = self.current_era()?;
+        rates.try_map(|rate| Ok(rate >> era))
+    }
+
+    pub fn halve(&self, amount: u64) -> Result<u64> {
+        Ok(amount >> self.current_era()?)
+    }
+
+    // Era in effect now. A slot-based halving applies from its slot on, even before a payout
+    // advances `halving_era` through record_distribution.
+    pub fn current_era(&self) -> Result<u8> {
+        let era = self.halving.era(self.total_distributed, Clock::get()?.slot);
+        Ok(era.max(self.halving_era))
+    }
+
+    // Tokens paid out and not yet burned or spent back into the vault or jackpot
+    pub fn circulating_supply(&self) -> u64 {
+        self.total_distributed
+            .saturating_sub(self.total_burned)
+            .saturating_sub(self.total_returned)
+    }
+
+    // Share of a spend to burn at the current circulating supply; the rest goes to the vault
+    pub fn burn_bps(&self) -> Result<u16> {
+        let curve = &self.burn_curve;
+        speedy_core::burn_share_bps(
+            self.circulating_supply(),
+            curve.floor_bps,
+            curve.ceiling_bps,
+            curve.low_supply,
+            curve.high_supply,
+        )
+        .ok_or(ErrorCode::InvalidBurnCurve.into())
+    }
+
+    // Convert every token-denominated setting for a mint migration. Prices, rewards and
+    // thresholds keep their whole-token value; supply totals and the thresholds compared with
+    // them move at the swap rate so the burn curve and halvings see the same supply. Drift and
+    // elimination rates are left alone while they are priced in USD cents. The jackpot vault
+    // stays on the old mint, so spends stop feeding it.
+    pub fn rescale_for_mint(
+        &mut self,
+        numerator: u64,
+        denominator: u64,
+        from_decimals: u8,
+        to_decimals: u8,
+    ) -> Result<()> {
+        let unit = |amount| convert_token_amount(amount, 1, 1, from_decimals, to_decimals);
+        let supply = |amount| convert_token_amount(amount, numerator, denominator, from_decimals, to_decimals);
+
+        self.token_rates = self.token_rates.try_map(unit)?;
+        if !self.usd_pricing.enabled {
+            self.drift_rates.per_1000_points = unit(self.drift_rates.per_1000_points)?;
+            self.drift_rates.max_reward = unit(self.drift_rates.max_reward)?;
+            self.elimination_rates.per_survival_minute = unit(self.elimination_rates.per_survival_minute)?;
+            self.elimination_rates.per_elimination = unit(self.elimination_rates.per_elimination)?;
+        }
+        self.ranked_queue.stake_amount = unit(self.ranked_queue.stake_amount)?;
+        self.vip_role.min_lifetime_earnings = unit(self.vip_role.min_lifetime_earnings)?;
+        self.vip_role.min_token_balance = unit(self.vip_role.min_token_balance)?;
+        self.crank_tip.amount = unit(self.crank_tip.amount)?;
+        self.dispute.challenge_bond = unit(self.dispute.challenge_bond)?;
+        self.vesting.threshold = unit(self.vesting.threshold)?;
+        self.buff_shop.price = unit(self.buff_shop.price)?;
+        self.prize_hold.threshold = unit(self.prize_hold.threshold)?;
+        self.cosign_threshold = unit(self.cosign_threshold)?;
+        self.mvp.bonus = unit(self.mvp.bonus)?;
+        self.world_record_bounty = unit(self.world_record_bounty)?;
+        self.curation.min_stake = unit(self.curation.min_stake)?;
+        self.watchdog_state.window_distributed = unit(self.watchdog_state.window_distributed)?;
+        self.watchdog_state.average_per_window = unit(self.watchdog_state.average_per_window)?;
+        self.jackpot.burn_share_bps = 0;
+
+        self.total_distributed = supply(self.total_distributed)?;
+        self.total_burned = supply(self.total_burned)?;
+        self.total_returned = supply(self.total_returned)?;
+        self.halving.every_distributed = supply(self.halving.every_distributed)?;
+        self.burn_curve.low_supply = supply(self.burn_curve.low_supply)?;
+        self.burn_curve.high_supply = supply(self.burn_curve.high_supply)?;
+        Ok(())
+    }
+}
+
+// Canonical, Borsh-serialized economy config used to clone environments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct GameConfig {
+    pub token_rates: TokenRates,
+    pub probation: ProbationConfig,
+    pub voucher_clock_tolerance_secs: i64,
+    pub usd_pricing: UsdPricingConfig,
+    pub usd_rates: TokenRates,
+    pub drift_rates: DriftRates,
+    pub elimination_rates: EliminationRates,
+    pub handicap: HandicapConfig,
+    pub ranked_queue: RankedQueueConfig,
+    pub jackpot_burn_share_bps: u16,
+    pub jackpot_draw_interval_secs: i64,
+    pub features: FeatureGate,
+    pub burn_curve: BurnCurve,
+    pub halving: HalvingConfig, // Set once: only imported while the target has no schedule
+    pub dispute: DisputeConfig,
+    pub watchdog: WatchdogConfig,
+    pub crank_tip: CrankTipConfig,
+    pub prize_hold: PrizeHoldConfig,
+    pub cosign_threshold: u64,
+    pub vesting: VestingConfig,
+    pub mvp: MvpConfig,
+    pub curation: CurationConfig,
+    pub charity_wallet: Pubkey, // From DonationStats; default pubkey = no charity campaign
+}
+
+impl GameConfig {
+    pub const VERSION: u8 = 3;
+    pub const LEN: usize = TokenRates::LEN + ProbationConfig::LEN + 8 + UsdPricingConfig::LEN
+        + TokenRates::LEN + DriftRates::LEN + EliminationRates::LEN + HandicapConfig::LEN
+        + RankedQueueConfig::LEN + 2 + 8 + FeatureGate::LEN
+        + BurnCurve::LEN + HalvingConfig::LEN + DisputeConfig::LEN + WatchdogConfig::LEN
+        + CrankTipConfig::LEN + PrizeHoldConfig::LEN + 8 + VestingConfig::LEN + MvpConfig::LEN
+        + CurationConfig::LEN + 32;
+
+    pub fn hash(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.try_to_vec()?).to_bytes())
+    }
+
+    // Same checks the individual admin updates apply
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.probation.duration_secs >= 0 && self.probation.reward_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidProbationConfig
+        );
+        require!(self.voucher_clock_tolerance_secs >= 0, ErrorCode::InvalidClockTolerance);
+        require!(
+            self.usd_pricing.max_confidence_bps <= BPS_DENOMINATOR,
+            ErrorCode::InvalidPriceFeed
+        );
+        require!(
+            self.handicap.low_rating_threshold <= self.handicap.high_rating_threshold,
+            ErrorCode::InvalidHandicapConfig
+        );
+        require!(self.ranked_queue.ticket_timeout_secs > 0, ErrorCode::InvalidRankedQueueConfig);
+        require!(
+            self.jackpot_burn_share_bps <= BPS_DENOMINATOR && self.jackpot_draw_interval_secs > 0,
+            ErrorCode::InvalidJackpotConfig
+        );
+        require!(self.features.0 & !FeatureGate::ALL == 0, ErrorCode::InvalidFeature);
+        require!(
+            self.burn_curve.floor_bps <= self.burn_curve.ceiling_bps
+                && self.burn_curve.ceiling_bps <= BPS_DENOMINATOR
+                && self.burn_curve.low_supply <= self.burn_curve.high_supply,
+            ErrorCode::InvalidBurnCurve
+        );
+        require!(self.dispute.window_secs >= 0, ErrorCode::InvalidDisputeConfig);
+        require!(self.watchdog.window_slots > 0, ErrorCode::InvalidWatchdogConfig);
+        require!(
+            self.crank_tip.cooldown_secs >= 0 && self.mvp.window_secs > 0,
+            ErrorCode::InvalidRewardAmount
+        );
+        require!(self.prize_hold.hold_secs > 0, ErrorCode::InvalidPrizeHoldConfig);
+        require!(self.vesting.duration_secs > 0, ErrorCode::InvalidVestingConfig);
+        require!(
+            self.curation.voting_secs > 0
+                && self.curation.slash_bps <= BPS_DENOMINATOR
+                && self.curation.creator_cut_bps <= Track::MAX_OWNER_CUT_BPS,
+            ErrorCode::InvalidTrackSubmission
+        );
+        Ok(())
+    }
+}
+
+// Drift/stunt mode rates, denominated like the active rate table (USD cents in USD mode)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DriftRates {
+    pub per_1000_points: u64,
+    pub max_reward: u64,
+}
+
+impl DriftRates {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RankedQueueConfig {
+    pub stake_amount: u64,        // Refundable stake escrowed in the vault per ticket
+    pub ticket_timeout_secs: i64, // Tickets without a reported match refund after this
+}
+
+impl RankedQueueConfig {
+    pub const LEN: usize = 8 + 8;
+}
+
+// Subsystem switches for gradual rollout. A cleared bit rejects the subsystem's entry points
+// and payouts; refunds and withdrawals stay open so funds are never trapped.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FeatureGate(pub u64);
+
+impl FeatureGate {
+    pub const LEN: usize = 8;
+
+    pub const TOURNAMENTS: u64 = 1 << 0;
+    pub const LEAGUES: u64 = 1 << 1;
+    pub const JACKPOT: u64 = 1 << 2;
+    pub const PREDICTION_MARKETS: u64 = 1 << 3;
+    pub const RANKED_QUEUE: u64 = 1 << 4;
+    pub const VIP_ROLE: u64 = 1 << 5;
+    pub const FEE_SPONSORSHIP: u64 = 1 << 6;
+    pub const BRIDGE: u64 = 1 << 7;
+    pub const BUYBACK: u64 = 1 << 8;
+    pub const TICKETS: u64 = 1 << 9;
+    pub const CAR_DROPS: u64 = 1 << 10;
+    pub const AUCTIONS: u64 = 1 << 11;
+    pub const REDEMPTIONS: u64 = 1 << 12;
+    pub const PAYMENT_STREAMS: u64 = 1 << 13;
+    pub const GRANTS: u64 = 1 << 14; // Ecosystem grant milestones and partner awards
+    pub const ALL: u64 = (1 << 15) - 1;
+
+    pub fn is_enabled(&self, feature: u64) -> bool {
+        self.0 & feature == feature
+    }
+}
+
+// Self-balancing daily challenge rewards: each day's completions per difficulty move the
+// next day's multiplier up when the challenge was rarely completed (under half the target)
+// and down when it was trivial (over twice the target)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ChallengeBalance {
+    pub target_completions: u32, // Expected daily completions per difficulty; 0 = disabled
+    pub step_bps: u16,
+    pub min_multiplier_bps: u16,
+    pub max_multiplier_bps: u16,
+    pub day: u64, // Day `completions` are counted for
+    pub completions: [u32; ChallengeBalance::DIFFICULTIES],
+    pub multiplier_bps: [u16; ChallengeBalance::DIFFICULTIES],
+}
+
+impl ChallengeBalance {
+    pub const DIFFICULTIES: usize = 3;
+    pub const LEN: usize = 4 + 2 + 2 + 2 + 8 + 4 * Self::DIFFICULTIES + 2 * Self::DIFFICULTIES;
+    pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+    pub fn day_index(now: i64) -> u64 {
+        (now / Self::SECONDS_PER_DAY) as u64
+    }
+
+    // Close out the counted day once `now` has moved past it. Returns whether it rolled.
+    pub fn roll(&mut self, now: i64) -> bool {
+        let today = Self::day_index(now);
+        if today <= self.day {
+            return false;
+        }
+        if self.target_completions > 0 {
+            let target = self.target_completions as u64;
+            for (completions, multiplier) in self.completions.iter().zip(self.multiplier_bps.iter_mut()) {
+                let completions = *completions as u64;
+                if completions * 2 < target {
+                    *multiplier = multiplier.saturating_add(self.step_bps).min(self.max_multiplier_bps);
+                } else if completions > target * 2 {
+                    *multiplier = multiplier.saturating_sub(self.step_bps).max(self.min_multiplier_bps);
+                }
+            }
+        }
+        self.completions = [0; Self::DIFFICULTIES];
+        self.day = today;
+        true
+    }
+
+    pub fn record_completion(&mut self, difficulty: ChallengeDifficulty, now: i64) {
+        self.roll(now);
+        let completions = &mut self.completions[difficulty as usize];
+        *completions = completions.saturating_add(1);
+    }
+
+    pub fn scale(&self, difficulty: ChallengeDifficulty, amount: u64) -> Result<u64> {
+        if self.target_completions == 0 {
+            return Ok(amount);
+        }
+        u64::try_from(
+            amount as u128 * self.multiplier_bps[difficulty as usize] as u128 / BPS_DENOMINATOR as u128
+        ).map_err(|_| ErrorCode::InvalidRewardAmount.into())
+    }
+}
+
+// Awards above `threshold` vest linearly over `duration_secs` instead of paying out at once
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct VestingConfig {
+    pub threshold: u64, // 0 = vesting disabled
+    pub duration_secs: i64,
+}
+
+impl VestingConfig {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn applies_to(&self, amount: u64) -> bool {
+        self.threshold > 0 && amount > self.threshold
+    }
+}
+
+// Emission halvings: every `every_distributed` tokens paid out and/or every `every_slots`
+// slots after `start_slot`, all reward rates halve. A zero interval disables that trigger;
+// when both are set the era is the larger of the two.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct HalvingConfig {
+    pub every_distributed: u64,
+    pub every_slots: u64,
+    pub start_slot: u64,
+}
+
+impl HalvingConfig {
+    pub const LEN: usize = 8 + 8 + 8;
+    pub const MAX_ERA: u8 = 63; // Keeps the shift below u64's width
+
+    pub fn is_set(&self) -> bool {
+        self.every_distributed > 0 || self.every_slots > 0
+    }
+
+    pub fn era(&self, total_distributed: u64, slot: u64) -> u8 {
+        speedy_core::halving_era(
+            total_distributed,
+            self.every_distributed,
+            slot,
+            self.start_slot,
+            self.every_slots,
+            Self::MAX_ERA,
+        )
+    }
+}
+
+// Community track submissions: creators stake at least `min_stake` to propose a track and
+// curators vote with stake for `voting_secs`. Rejected creators lose `slash_bps` of their stake
+// to the vault; accepted tracks pay their creator `creator_cut_bps` of race awards.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CurationConfig {
+    pub min_stake: u64, // 0 = submissions disabled
+    pub voting_secs: i64,
+    pub slash_bps: u16,
+    pub creator_cut_bps: u16,
+}
+
+impl CurationConfig {
+    pub const LEN: usize = 8 + 8 + 2 + 2;
+}
+
+// Post-race MVP vote: participants vote for one another for `window_secs` after the race, and
+// the most voted participant receives `bonus` from the vault
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MvpConfig {
+    pub bonus: u64,       // 0 = voting disabled
+    pub window_secs: i64, // Counted from the race award
+    pub min_votes: u16,   // Fewer votes for the winner and no bonus is paid
+}
+
+impl MvpConfig {
+    pub const LEN: usize = 8 + 8 + 2;
+}
+
+// Tournament prizes above `threshold` sit in a per-player hold escrow for `hold_secs`,
+// during which a confirmed cheating verdict can claw them back
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PrizeHoldConfig {
+    pub threshold: u64, // 0 = holds disabled
+    pub hold_secs: i64,
+}
+
+impl PrizeHoldConfig {
+    pub const LEN: usize = 8 + 8;
+
+    pub fn applies_to(&self, amount: u64) -> bool {
+        self.threshold > 0 && amount > self.threshold
+    }
+}
+
+// Opt-in "auto-stake my winnings": awards are locked in the vault for `lock_secs` with a
+// `bonus_bps` top-up instead of being paid to the player's token account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AutoStakeConfig {
+    pub bonus_bps: u16,
+    pub lock_secs: i64,
+}
+
+impl AutoStakeConfig {
+    pub const LEN: usize = 2 + 8;
+}
+
+// Cold wallets that vault and treasury withdrawals may target. New destinations only become
+// usable after WITHDRAWAL_DESTINATION_DELAY_SECS, so a compromised authority key cannot add
+// its own wallet and drain the vault before anyone notices; removals apply immediately.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WithdrawalDestination {
+    pub wallet: Pubkey, // default pubkey = empty slot
+    pub active_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WithdrawalAllowlist {
+    pub destinations: [WithdrawalDestination; WithdrawalAllowlist::MAX_DESTINATIONS],
+}
+
+impl WithdrawalAllowlist {
+    pub const MAX_DESTINATIONS: usize = 4;
+    pub const LEN: usize = (32 + 8) * Self::MAX_DESTINATIONS;
+
+    pub fn is_allowed(&self, wallet: &Pubkey, now: i64) -> bool {
+        *wallet != Pubkey::default()
+            && self.destinations.iter().any(|d| d.wallet == *wallet && now >= d.active_at)
+    }
+
+    pub fn require_allowed(&self, wallet: &Pubkey, now: i64) -> Result<()> {
+        require!(self.is_allowed(wallet, now), ErrorCode::DestinationNotAllowed);
+        Ok(())
+    }
+}
+
+pub const WITHDRAWAL_DESTINATION_DELAY_SECS: i64 = 48 * 60 * 60;
+
+// Minimum time between proposing and executing an admin proposal
+pub const ADMIN_PROPOSAL_DELAY_SECS: i64 = 24 * 60 * 60;
+
+// Distribution anomaly detection: payouts pause automatically once the current window's
+// distribution exceeds `anomaly_multiplier_bps` of the average window
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct WatchdogConfig {
+    pub window_slots: u64,
+    pub anomaly_multiplier_bps: u32, // 0 = watchdog disabled
+    pub min_history_windows: u16,    // Windows observed before the watchdog arms; also caps the averaging weight
+}
+
+impl WatchdogConfig {
+    pub const LEN: usize = 8 + 4 + 2;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct WatchdogState {
+    pub window_start_slot: u64,
+    pub window_distributed: u64,
+    pub average_per_window: u64,
+    pub windows_observed: u32,
+}
+
+impl WatchdogState {
+    pub const LEN: usize = 8 + 8 + 8 + 4;
+}
+
+// Race award dispute settings. With a non-zero window, race awards are held in the vault as
+// pending receipts until the window closes; the reviewer can flag them for free, anyone else
+// posts `challenge_bond`, which is slashed if the oracle finds the award valid.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DisputeConfig {
+    pub window_secs: i64, // 0 = disputes disabled
+    pub reviewer: Pubkey,
+    pub challenge_bond: u64,
+}
+
+impl DisputeConfig {
+    pub const LEN: usize = 8 + 32 + 8;
+}
+
+// Tip paid from the vault to whoever runs a permissionless crank. Each crank kind pays at
+// most one tip per cooldown window so repeated calls can't farm the vault.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CrankTipConfig {
+    pub amount: u64, // 0 = tips disabled
+    pub cooldown_secs: i64,
+}
+
+impl CrankTipConfig {
+    pub const LEN: usize = 8 + 8;
+}
+
+// Soulbound role token criteria; a player must meet both thresholds to claim
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct VipRoleConfig {
+    pub mint: Pubkey, // Token-2022 non-transferable mint, authority is game_state
+    pub min_lifetime_earnings: u64,
+    pub min_token_balance: u64, // $SPEEDY held in the player's token account
+}
+
+impl VipRoleConfig {
+    pub const LEN: usize = 32 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct JackpotConfig {
+    pub burn_share_bps: u16,     // Share of every spend routed to the jackpot instead of burned
+    pub draw_interval_secs: i64, // Minimum time between draws
+    pub vault: Pubkey,           // Jackpot token account, owned by the jackpot PDA
+}
+
+impl JackpotConfig {
+    pub const LEN: usize = 2 + 8 + 32;
+}
+
+// Burn share of spends as a function of circulating supply (total distributed minus total
+// burned). Floor equal to ceiling gives a fixed share; the default burns everything.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BurnCurve {
+    pub floor_bps: u16,   // Burn share at or below `low_supply`
+    pub ceiling_bps: u16, // Burn share at or above `high_supply`
+    pub low_supply: u64,
+    pub high_supply: u64,
+}
+
+impl BurnCurve {
+    pub const LEN: usize = 2 + 2 + 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct HandicapConfig {
+    pub low_rating_threshold: u32,  // Ratings below this earn the catch-up multiplier
+    pub catch_up_bps: u16,
+    pub high_rating_threshold: u32, // Ratings above this earn the top-rated multiplier
+    pub top_rated_bps: u16,
+}
+
+impl HandicapConfig {
+    pub const LEN: usize = 4 + 2 + 4 + 2;
+}
+
+// Elimination royale rates, denominated like the active rate table
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EliminationRates {
+    pub per_survival_minute: u64,
+    pub per_elimination: u64,
+    pub podium_multiplier_bps: [u16; 3], // 1st, 2nd, 3rd place
+}
+
+impl EliminationRates {
+    pub const LEN: usize = 8 + 8 + 2 * 3;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct UsdPricingConfig {
+    pub enabled: bool,
+    pub price_feed_id: [u8; 32],   // Pyth feed id for $SPEEDY/USD
+    pub max_staleness_secs: i64,
+    pub max_confidence_bps: u16,   // Max confidence interval relative to price
+}
+
+impl UsdPricingConfig {
+    pub const LEN: usize = 1 + 32 + 8 + 2;
+}
+
+// Pyth pull-oracle receiver program
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+// SPL Memo program v2
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+// Mirrors of the Pyth receiver `PriceUpdateV2` layout (after the 8-byte discriminator)
+#[derive(AnchorDeserialize)]
+pub enum PythVerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct PythPriceMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct PythPriceUpdate {
+    pub write_authority: Pubkey,
+    pub verification_level: PythVerificationLevel,
+    pub price_message: PythPriceMessage,
+    pub posted_slot: u64,
+}
+
+pub use speedy_core::BPS_DENOMINATOR;
+
+pub const MAX_RELAY_TEAM_SIZE: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct ProbationConfig {
+    pub duration_secs: i64,        // Probation window from profile creation (0 = disabled)
+    pub reward_bps: u16,           // Share of computed rewards paid while on probation
+    pub release_after_races: u64,  // Completed races required before withheld rewards are released
+}
+
+impl ProbationConfig {
+    pub const LEN: usize = 8 + 2 + 8;
+}
+
+#[account]
+pub struct Clawback {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reason_hash: [u8; 32], // Hash of the off-chain incident report
+    pub proposed_at: i64,
+    pub executable_at: i64,
+    pub bump: u8,
+}
+
+impl Clawback {
+    pub const LEN: usize = 32 + 8 + 32 + 8 + 8 + 1;
+}
+
+// Latest `export_config` snapshot
+#[account]
+pub struct ConfigExport {
+    pub version: u8,
+    pub exported_at: i64,
+    pub config_hash: [u8; 32], // Hash of the Borsh-serialized config, passed to `import_config`
+    pub config: GameConfig,
+    pub bump: u8,
+}
+
+impl ConfigExport {
+    pub const LEN: usize = 1 + 8 + 32 + GameConfig::LEN + 1;
+}
+
+#[account]
+pub struct PlayerProfile {
+    pub player: Pubkey,
+    pub created_at: i64,
+    pub races_completed: u64,
+    pub withheld_rewards: u64, // Rewards held back during probation, still sitting in the vault
+    pub bump: u8,
+    pub rating: u32,           // ELO-style skill rating, updated from oracle-reported results
+    pub last_race_week: u64,   // `speedy_core::week_index` of the last completed race
+    pub balance_adjustment: i64, // Netted into the next awards: negative is owed by the player, positive to them
+    pub lifetime_earnings: u64,  // Total paid through profile-tracked awards
+    pub sponsored_lamports: u64, // Account rent paid for this player by the fee pool
+    pub vesting: VestingSchedule, // Large awards streamed out instead of paid at once
+    pub credits: u64, // In-game-only balance bought by burning $SPEEDY; never withdrawable
+    pub donation_bps: u16, // Opt-in charity round-up on spends
+    pub total_donated: u64,
+    pub auto_stake: bool,          // Route race/challenge/tournament awards into the staking pool
+    pub auto_staked: u64,          // Staked winnings plus bonuses, withdrawable after the unlock time
+    pub auto_stake_unlock_at: i64, // Pushed out by every new deposit
+    pub buffs: [Buff; PlayerProfile::MAX_BUFFS], // Time-limited reward modifiers
+    pub ticket_discount_bps: u16,   // Promo discount on the next ticket purchase
+    pub fee_loan: u64,              // Entry fees owed out of the vesting balance, repaid at claim
+    pub payout_currency: PoolCurrency, // Stable = claims are swapped to the buyback stablecoin
+    pub year_earnings: [YearEarnings; 2], // Unreported earnings, slot `year % 2`
+}
+
+impl PlayerProfile {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 1 + 4 + 8 + 8 + 8 + 8 + VestingSchedule::LEN + 8 + 2 + 8 + 1 + 8 + 8
+        + Buff::LEN * Self::MAX_BUFFS + 2 + 8 + 1 + YearEarnings::LEN * 2;
+    pub const MAX_BUFFS: usize = 4;
+
+    pub fn require_payout_currency(&self, currency: PoolCurrency) -> Result<()> {
+        require!(self.payout_currency == currency, ErrorCode::PayoutCurrencyMismatch);
+        Ok(())
+    }
+
+    pub fn new(player: Pubkey, bump: u8, now: i64) -> Self {
+        Self {
+            player,
+            created_at: now,
+            races_completed: 0,
+            withheld_rewards: 0,
+            bump,
+            rating: speedy_core::INITIAL_RATING,
+            last_race_week: 0,
+            balance_adjustment: 0,
+            lifetime_earnings: 0,
+            sponsored_lamports: 0,
+            vesting: VestingSchedule::default(),
+            credits: 0,
+            donation_bps: 0,
+            total_donated: 0,
+            auto_stake: false,
+            auto_staked: 0,
+            auto_stake_unlock_at: 0,
+            buffs: [Buff::default(); Self::MAX_BUFFS],
+            ticket_discount_bps: 0,
+            fee_loan: 0,
+            payout_currency: PoolCurrency::Speedy,
+            year_earnings: [YearEarnings::default(); 2],
+        }
+    }
+
+    // Earnings bucket for `year`. A bucket still holding the year before last is reset, so its
+    // statement has to be closed within a year of that year ending.
+    pub fn year_earnings_mut(&mut self, year: u16) -> &mut YearEarnings {
+        let slot = &mut self.year_earnings[(year % 2) as usize];
+        if slot.year != year {
+            *slot = YearEarnings { year, ..YearEarnings::default() };
+        }
+        slot
+    }
+
+    // Clear buffs that have run out
+    pub fn prune_buffs(&mut self, now: i64) {
+        for buff in self.buffs.iter_mut().filter(|buff| !buff.is_active(now)) {
+            *buff = Buff::default();
+        }
+    }
+
+    // Add a buff, replacing an existing one on the same target; fails when every slot holds
+    // an active buff on another target
+    pub fn add_buff(&mut self, target: BuffTarget, multiplier_bps: u16, duration_secs: i64, now: i64) -> Result<()> {
+        require!(multiplier_bps > 0 && duration_secs > 0, ErrorCode::InvalidBuff);
+        self.prune_buffs(now);
+        let slot = match self.buffs.iter().position(|buff| buff.is_active(now) && buff.target == target) {
+            Some(index) => index,
+            None => self.buffs.iter().position(|buff| !buff.is_active(now))
+                .ok_or(ErrorCode::BuffSlotsFull)?,
+        };
+        self.buffs[slot] = Buff {
+            target,
+            multiplier_bps,
+            expires_at: now.checked_add(duration_secs).ok_or(ErrorCode::InvalidBuff)?,
+        };
+        Ok(())
+    }
+
+    // Scale `amount` by every active buff on `target` or on all rewards, pruning expired ones
+    pub fn apply_buffs(&mut self, target: BuffTarget, amount: u64, now: i64) -> Result<u64> {
+        self.prune_buffs(now);
+        self.buffs.iter()
+            .filter(|buff| buff.is_active(now) && (buff.target == target || buff.target == BuffTarget::AllRewards))
+            .try_fold(amount, |amount, buff| speedy_core::apply_bps(amount, buff.multiplier_bps))
+            .ok_or(ErrorCode::InvalidRewardAmount.into())
+    }
+}
+
+// A player's profile-tracked earnings over one calendar year, by reward type
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct YearEarnings {
+    pub year: u16,
+    pub count: u32,
+    pub total: u64,
+    pub by_type: [u64; RewardType::COUNT], // Indexed by `RewardType as usize`
+}
+
+impl YearEarnings {
+    pub const LEN: usize = 2 + 4 + 8 + 8 * RewardType::COUNT;
+
+    pub fn record(&mut self, reward_type: RewardType, amount: u64) -> Result<()> {
+        self.count = self.count.saturating_add(1);
+        self.total = self.total.checked_add(amount).ok_or(ErrorCode::InvalidRewardAmount)?;
+        let by_type = &mut self.by_type[reward_type as usize];
+        *by_type = by_type.checked_add(amount).ok_or(ErrorCode::InvalidRewardAmount)?;
+        Ok(())
+    }
+}
+
+// Closed-out earnings of a player for one calendar year. Written once by
+// `close_yearly_statement` and never modified afterwards.
+#[account]
+pub struct YearlyStatement {
+    pub player: Pubkey,
+    pub year: u16,
+    pub reward_count: u32,
+    pub total: u64,
+    pub by_type: [u64; RewardType::COUNT],
+    pub closed_at: i64,
+    pub bump: u8,
+}
+
+impl YearlyStatement {
+    pub const LEN: usize = 32 + 2 + 4 + 8 + 8 * RewardType::COUNT + 8 + 1;
+}
+
+// Reward modifier: above 10_000 bps is a buff, below is a debuff. An expiry of 0 marks an
+// empty slot.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct Buff {
+    pub target: BuffTarget,
+    pub multiplier_bps: u16,
+    pub expires_at: i64,
+}
+
+impl Buff {
+    pub const LEN: usize = 1 + 2 + 8;
+
+    pub fn is_active(&self, now: i64) -> bool {
+        now < self.expires_at
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BuffTarget {
+    #[default]
+    AllRewards,
+    Races,
+    Challenges,
+    Tournaments,
+}
+
+// Buff sold for $SPEEDY by `purchase_buff`; a price of 0 closes the shop
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BuffShopItem {
+    pub target: BuffTarget,
+    pub multiplier_bps: u16,
+    pub duration_secs: i64,
+    pub price: u64, // Burned on purchase
+}
+
+impl BuffShopItem {
+    pub const LEN: usize = 1 + 2 + 8 + 8;
+}
+
+// Linear release of awards above `VestingConfig::threshold`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct VestingSchedule {
+    pub locked: u64,   // Amount vesting between start_time and end_time
+    pub start_time: i64,
+    pub end_time: i64,
+    pub released: u64, // Part of `locked` already moved to `carry` or claimed
+    pub carry: u64,    // Vested but unclaimed from schedules folded into the current one
+}
+
+impl VestingSchedule {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8;
+
+    // Part of `locked` vested as of `now`
+    pub fn vested(&self, now: i64) -> u64 {
+        if now >= self.end_time {
+            return self.locked;
+        }
+        let elapsed = now.saturating_sub(self.start_time).max(0) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        (self.locked as u128 * elapsed / duration) as u64
+    }
+
+    pub fn claimable(&self, now: i64) -> u64 {
+        self.carry.saturating_add(self.vested(now).saturating_sub(self.released))
+    }
+
+    // Everything not yet claimed, vested or not
+    pub fn outstanding(&self) -> u64 {
+        self.carry.saturating_add(self.locked.saturating_sub(self.released))
+    }
+
+    // Restart the schedule with `amount` added to whatever is still locked; anything already
+    // vested stays claimable
+    pub fn add(&mut self, amount: u64, duration_secs: i64, now: i64) -> Result<()> {
+        let vested = self.vested(now);
+        self.carry = self.carry.checked_add(vested.saturating_sub(self.released))
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.locked = (self.locked - vested).checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.start_time = now;
+        self.end_time = now.checked_add(duration_secs).ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.released = 0;
+        Ok(())
+    }
+
+    // Mark everything claimable as of `now` as paid and return it
+    pub fn take_claimable(&mut self, now: i64) -> u64 {
+        let claimable = self.claimable(now);
+        self.released = self.vested(now);
+        self.carry = 0;
+        claimable
+    }
+}
+
+// Charity round-up destination and running totals for community campaigns
+#[account]
+pub struct DonationStats {
+    pub charity_wallet: Pubkey,
+    pub campaign_id: u64,
+    pub campaign_donated: u64,
+    pub total_donated: u64,
+    pub donation_count: u64,
+    pub bump: u8,
+}
+
+impl DonationStats {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 1;
+
+    pub fn record(&mut self, amount: u64) -> Result<()> {
+        self.campaign_donated = self.campaign_donated.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.total_donated = self.total_donated.checked_add(amount)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.donation_count += 1;
+        Ok(())
+    }
+}
+
+// One attestation per verified device/identity, keyed by the verifier-provided hash
+#[account]
+pub struct Attestation {
+    pub player: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub verifier: Pubkey,
+    pub attested_at: i64,
+    pub welcome_bonus_claimed: bool,
+    pub bump: u8,
+}
+
+impl Attestation {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TokenRates {
+    pub race_completion: u64,
+    pub race_win: u64,
+    pub distance_per_100m: u64,
+    pub obstacle_avoided: u64,
+    pub bonus_collected: u64,
+    pub daily_challenge_easy: u64,
+    pub daily_challenge_medium: u64,
+    pub daily_challenge_hard: u64,
+    pub tournament_participation: u64,
+    pub tournament_winner: u64,
+    pub welcome_bonus: u64,
+    pub staking_per_hour_common: u64,
+    pub staking_per_hour_rare: u64,
+    pub staking_per_hour_epic: u64,
+    pub staking_per_hour_legendary: u64,
+}
+
+impl TokenRates {
+    pub const LEN: usize = 8 * 15; // 15 u64 fields
+
+    pub fn race_rates(&self) -> speedy_core::RaceRates {
+        speedy_core::RaceRates {
+            race_completion: self.race_completion,
+            race_win: self.race_win,
+            distance_per_100m: self.distance_per_100m,
+            obstacle_avoided: self.obstacle_avoided,
+            bonus_collected: self.bonus_collected,
+        }
+    }
+
+    // Apply a fallible transformation to every rate
+    pub fn try_map(&self, f: impl Fn(u64) -> Result<u64>) -> Result<Self> {
+        Ok(Self {
+            race_completion: f(self.race_completion)?,
+            race_win: f(self.race_win)?,
+            distance_per_100m: f(self.distance_per_100m)?,
+            obstacle_avoided: f(self.obstacle_avoided)?,
+            bonus_collected: f(self.bonus_collected)?,
+            daily_challenge_easy: f(self.daily_challenge_easy)?,
+            daily_challenge_medium: f(self.daily_challenge_medium)?,
+            daily_challenge_hard: f(self.daily_challenge_hard)?,
+            tournament_participation: f(self.tournament_participation)?,
+            tournament_winner: f(self.tournament_winner)?,
+            welcome_bonus: f(self.welcome_bonus)?,
+            staking_per_hour_common: f(self.staking_per_hour_common)?,
+            staking_per_hour_rare: f(self.staking_per_hour_rare)?,
+            staking_per_hour_epic: f(self.staking_per_hour_epic)?,
+            staking_per_hour_legendary: f(self.staking_per_hour_legendary)?,
+        })
+    }
+
+    pub fn field_mut(&mut self, field: RateField) -> &mut u64 {
+        match field {
+            RateField::RaceCompletion => &mut self.race_completion,
+            RateField::RaceWin => &mut self.race_win,
+            RateField::DistancePer100m => &mut self.distance_per_100m,
+            RateField::ObstacleAvoided => &mut self.obstacle_avoided,
+            RateField::BonusCollected => &mut self.bonus_collected,
+            RateField::DailyChallengeEasy => &mut self.daily_challenge_easy,
+            RateField::DailyChallengeMedium => &mut self.daily_challenge_medium,
+            RateField::DailyChallengeHard => &mut self.daily_challenge_hard,
+            RateField::TournamentParticipation => &mut self.tournament_participation,
+            RateField::TournamentWinner => &mut self.tournament_winner,
+            RateField::WelcomeBonus => &mut self.welcome_bonus,
+            RateField::StakingPerHourCommon => &mut self.staking_per_hour_common,
+            RateField::StakingPerHourRare => &mut self.staking_per_hour_rare,
+            RateField::StakingPerHourEpic => &mut self.staking_per_hour_epic,
+            RateField::StakingPerHourLegendary => &mut self.staking_per_hour_legendary,
+        }
+    }
+}
+
+// The most recent rate tables in effect, oldest overwritten first, so what a past race should
+// have paid can be recomputed on-chain
+#[account]
+pub struct RateHistory {
+    pub snapshots: Vec<RateSnapshot>,
+    pub next: u32,           // Slot overwritten by the next snapshot once the buffer is full
+    pub total_recorded: u64,
+    pub bump: u8,
+}
+
+impl RateHistory {
+    pub const MAX_SNAPSHOTS: usize = 24;
+    pub const LEN: usize = (4 + RateSnapshot::LEN * Self::MAX_SNAPSHOTS) + 4 + 8 + 1;
+
+    pub fn push(&mut self, snapshot: RateSnapshot) {
+        if self.snapshots.len() < Self::MAX_SNAPSHOTS {
+            self.snapshots.push(snapshot);
+        } else {
+            self.snapshots[self.next as usize] = snapshot;
+        }
+        self.next = ((self.next as usize + 1) % Self::MAX_SNAPSHOTS) as u32;
+        self.total_recorded = self.total_recorded.saturating_add(1);
+    }
+
+    // Rates in effect at `slot`, if that slot is still covered by the buffer
+    pub fn at_slot(&self, slot: u64) -> Option<&RateSnapshot> {
+        self.snapshots
+            .iter()
+            .filter(|snapshot| snapshot.effective_slot <= slot)
+            .max_by_key(|snapshot| snapshot.effective_slot)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RateSnapshot {
+    pub token_rates: TokenRates,
+    pub usd_rates: TokenRates,
+    pub usd_priced: bool, // Whether `usd_rates` were the active table
+    pub effective_slot: u64,
+    pub effective_at: i64,
+}
+
+impl RateSnapshot {
+    pub const LEN: usize = TokenRates::LEN * 2 + 1 + 8 + 8;
+}
+
+// One field of `TokenRates`, for partial rate updates
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RateField {
+    RaceCompletion,
+    RaceWin,
+    DistancePer100m,
+    ObstacleAvoided,
+    BonusCollected,
+    DailyChallengeEasy,
+    DailyChallengeMedium,
+    DailyChallengeHard,
+    TournamentParticipation,
+    TournamentWinner,
+    WelcomeBonus,
+    StakingPerHourCommon,
+    StakingPerHourRare,
+    StakingPerHourEpic,
+    StakingPerHourLegendary,
+}
+
+impl RateField {
+    pub const COUNT: usize = 15;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RateChange {
+    pub field: RateField,
+    pub value: u64,
+}
+
+// Display name plus an off-chain JSON document (artwork, rules); the content hash makes the
+// pointer tamper-evident
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Metadata {
+    pub name: String,
+    pub uri: String,
+    pub content_hash: [u8; 32], // sha256 of the document at `uri`
+}
+
+impl Metadata {
+    pub const MAX_NAME_LEN: usize = 32;
+    pub const MAX_URI_LEN: usize = 200;
+    pub const LEN: usize = (4 + Self::MAX_NAME_LEN) + (4 + Self::MAX_URI_LEN) + 32;
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.name.len() <= Self::MAX_NAME_LEN && self.uri.len() <= Self::MAX_URI_LEN,
+            ErrorCode::MetadataTooLong
+        );
+        Ok(())
+    }
+}
+
+// Settings for `create_tournament`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TournamentConfig {
+    pub entry_fee: u64,
+    pub max_entrants: u32,
+    pub registration_deadline: i64,
+    pub eligibility: TournamentEligibility,
+    pub fee_curve: EntryFeeCurve,
+    pub disqualification_forfeit_bps: u16,
+    pub entry_currency: PoolCurrency,
+    pub prize_currency: PoolCurrency,
+    pub prize_claim_secs: i64, // Prizes can be paid this long after registration closes; 0 = no deadline
+}
+
+// Currency of a tournament's entry fees or prizes
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PoolCurrency {
+    Speedy,
+    Stable, // Requires the stable side vault
+}
+
+// Entry gates checked at join time; unset gates are not enforced
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TournamentEligibility {
+    pub min_car_rarity: Option<CarRarity>, // Declared rarity must be co-signed by the oracle
+    pub min_level: u32,                    // 0 = no level requirement
+    pub allowlist_root: Option<[u8; 32]>,  // Merkle root of `allowlist_leaf(player)` leaves
+}
+
+impl TournamentEligibility {
+    pub const LEN: usize = (1 + 1) + 4 + (1 + 32);
+}
+
+#[account]
+pub struct Tournament {
+    pub tournament_id: u64,
+    pub entry_fee: u64,
+    pub max_entrants: u32,
+    pub registered_count: u32,
+    pub registration_deadline: i64,
+    pub prize_escrow: Pubkey, // ATA owned by this tournament PDA, holds entry fees in the entry currency
+    pub eligibility: TournamentEligibility,
+    pub fee_curve: EntryFeeCurve,
+    pub disqualification_forfeit_bps: u16, // Share of the entry fee kept when an entrant is disqualified
+    pub total_prizes_paid: u64,
+    pub sponsors: Vec<TournamentSponsor>,
+    pub waitlist_head: u64, // Position of the next waitlisted player to promote
+    pub waitlist_tail: u64, // Position assigned to the next waitlist join
+    pub prize_source: Pubkey, // prize_escrow, or the game vault of the prize currency for mixed-currency tournaments
+    pub prize_claim_deadline: i64, // After this, unpaid prizes can be rolled over; 0 = no deadline
+    pub metadata: Metadata,
+    pub bump: u8,
+}
+
+impl Tournament {
+    pub const MAX_SPONSORS: usize = 4;
+    pub const LEN: usize = 8 + 8 + 4 + 4 + 8 + 32 + TournamentEligibility::LEN + EntryFeeCurve::LEN
+        + 2 + 8 + (4 + TournamentSponsor::LEN * Self::MAX_SPONSORS) + 8 + 8 + 32 + 8 + Metadata::LEN + 1;
+
+    pub fn prize_claim_expired(&self, now: i64) -> bool {
+        self.prize_claim_deadline != 0 && now >= self.prize_claim_deadline
+    }
+
+    // Entry fee charged to the next registrant at `now`
+    pub fn current_entry_fee(&self, now: i64) -> Result<u64> {
+        speedy_core::dynamic_entry_fee(
+            self.entry_fee,
+            self.registered_count,
+            self.max_entrants,
+            self.fee_curve.fill_premium_bps,
+            self.fee_curve.late_discount_bps,
+            self.fee_curve.late_window_secs,
+            self.registration_deadline.saturating_sub(now),
+        ).ok_or(ErrorCode::InvalidRewardAmount.into())
+    }
+}
+
+// Entry fee pricing; an all-zero curve charges the flat `entry_fee`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct EntryFeeCurve {
+    pub fill_premium_bps: u16, // Extra fee when the bracket is full, scaled linearly by fill rate
+    pub late_discount_bps: u16, // Discount applied inside the late window
+    pub late_window_secs: i64,
+}
+
+impl EntryFeeCurve {
+    pub const LEN: usize = 2 + 2 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TournamentSponsor {
+    pub sponsor: Pubkey,
+    pub name_hash: [u8; 32], // Hash of the sponsor's display name / branding
+    pub amount: u64,
+}
+
+impl TournamentSponsor {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+#[account]
+pub struct TournamentEntry {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub fee_paid: u64,
+    pub joined_at: i64,
+    pub bump: u8,
+}
+
+impl TournamentEntry {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1;
+}
+
+// A large tournament prize held in escrow until `release_at`; the PDA owns the escrow
+#[account]
+pub struct PrizeHold {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub escrow: Pubkey, // Token account owned by this PDA, in the prize currency
+    pub release_at: i64,
+    pub status: PrizeHoldStatus,
+    pub bump: u8,
+}
+
+impl PrizeHold {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PrizeHoldStatus {
+    Held,
+    Released,
+    ClawedBack,
+}
+
+// Lamports set aside to pay account rent on behalf of players and the oracle, with running
+// totals per subsystem so SOL consumption can be budgeted. Balance above the treasury's own
+// rent-exempt minimum is available for fronting.
+#[account]
+pub struct RentTreasury {
+    pub fronted: [u64; RentSubsystem::COUNT],          // Lamports paid out for new accounts
+    pub recovered: [u64; RentSubsystem::COUNT],        // Lamports returned by closed accounts
+    pub accounts_fronted: [u32; RentSubsystem::COUNT],
+    pub bump: u8,
+}
+
+impl RentTreasury {
+    pub const LEN: usize = 8 * RentSubsystem::COUNT * 2 + 4 * RentSubsystem::COUNT + 1;
+
+    pub fn record_fronted(&mut self, subsystem: RentSubsystem, lamports: u64) -> Result<()> {
+        let index = subsystem as usize;
+        self.fronted[index] = self.fronted[index].checked_add(lamports)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        self.accounts_fronted[index] = self.accounts_fronted[index].saturating_add(1);
+        Ok(())
+    }
+
+    pub fn record_recovered(&mut self, subsystem: RentSubsystem, lamports: u64) -> Result<()> {
+        let index = subsystem as usize;
+        self.recovered[index] = self.recovered[index].checked_add(lamports)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RentSubsystem {
+    RaceReceipts,
+    PlayerProfiles,
+    Escrows,
+}
+
+impl RentSubsystem {
+    pub const COUNT: usize = 3;
+}
+
+// Exchange rates for the tournament ticket token; the PDA is the ticket mint authority
+#[account]
+pub struct TicketConfig {
+    pub ticket_mint: Pubkey, // 0-decimal, one unit per tournament entry
+    pub price: u64,          // $SPEEDY per ticket
+    pub refund_bps: u16,     // Share of the price returned for an unused ticket
+    pub tickets_sold: u64,
+    pub tickets_spent: u64,
+    pub bump: u8,
+}
+
+impl TicketConfig {
+    pub const LEN: usize = 32 + 8 + 2 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct PromoCode {
+    pub code_hash: [u8; 32], // hash of the plain code players type in
+    pub promo_id: u64,
+    pub effect: PromoEffect,
+    pub max_uses: u32,         // 0 = unlimited
+    pub uses: u32,
+    pub per_wallet_limit: u16, // 0 = unlimited
+    pub expires_at: i64,
+    pub bump: u8,
+}
+
+impl PromoCode {
+    pub const LEN: usize = 32 + 8 + PromoEffect::LEN + 4 + 4 + 2 + 8 + 1;
+}
+
+// Times one wallet has redeemed one promo code
+#[account]
+pub struct PromoRedemption {
+    pub uses: u16,
+    pub bump: u8,
+}
+
+impl PromoRedemption {
+    pub const LEN: usize = 2 + 1;
+}
+
+// Cosmetic items a player owns, keyed by the game's item id
+#[account]
+pub struct Inventory {
+    pub player: Pubkey,
+    pub items: Vec<InventoryItem>,
+    pub bump: u8,
+}
+
+impl Inventory {
+    pub const MAX_ITEMS: usize = 32;
+    pub const LEN: usize = 32 + (4 + InventoryItem::LEN * Self::MAX_ITEMS) + 1;
+
+    // Add `quantity` of an item and return the new owned quantity
+    pub fn add(&mut self, item_id: u64, quantity: u32) -> Result<u32> {
+        if let Some(item) = self.items.iter_mut().find(|item| item.item_id == item_id) {
+            item.quantity = item.quantity.checked_add(quantity).ok_or(ErrorCode::InvalidRewardAmount)?;
+            return Ok(item.quantity);
+        }
+        require!(self.items.len() < Self::MAX_ITEMS, ErrorCode::InventoryFull);
+        self.items.push(InventoryItem { item_id, quantity, equipped: false });
+        Ok(quantity)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct InventoryItem {
+    pub item_id: u64,
+    pub quantity: u32,
+    pub equipped: bool,
+}
+
+impl InventoryItem {
+    pub const LEN: usize = 8 + 4 + 1;
+}
+
+#[account]
+pub struct CosmeticListing {
+    pub item_id: u64,
+    pub price: u64, // $SPEEDY burned per unit; 0 = not for sale
+    pub sold: u64,
+    pub bump: u8,
+}
+
+impl CosmeticListing {
+    pub const LEN: usize = 8 + 8 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CosmeticSource {
+    Shop,
+    Lootbox,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum PromoEffect {
+    TokenGrant { amount: u64 },                                // Paid from the vault
+    RewardBoost { target: BuffTarget, multiplier_bps: u16, duration_secs: i64 }, // Granted as a buff
+    ShopDiscount { discount_bps: u16 },                        // Off the next ticket purchase
+    FreeTickets { quantity: u32 },
+    CosmeticUnlock { item_id: u64 },                           // Added to the player's inventory
+}
+
+impl PromoEffect {
+    pub const LEN: usize = 1 + (1 + 2 + 8);
+
+    pub fn validate(&self) -> Result<()> {
+        let valid = match *self {
+            PromoEffect::TokenGrant { amount } => amount > 0,
+            PromoEffect::RewardBoost { multiplier_bps, duration_secs, .. } => {
+                multiplier_bps > BPS_DENOMINATOR && duration_secs > 0
+            }
+            PromoEffect::ShopDiscount { discount_bps } => discount_bps > 0 && discount_bps <= BPS_DENOMINATOR,
+            PromoEffect::FreeTickets { quantity } => quantity > 0,
+            PromoEffect::CosmeticUnlock { .. } => true,
+        };
+        require!(valid, ErrorCode::InvalidPromoCode);
+        Ok(())
+    }
+}
+
+#[account]
+pub struct WaitlistEntry {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub position: u64,
+    pub fee_paid: u64, // Escrowed in the prize escrow, becomes the entry fee on promotion
+    pub joined_at: i64,
+    pub bump: u8,
+}
+
+impl WaitlistEntry {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct WeeklyReport {
+    pub week: u64,
+    pub total_distributed: u64, // Cumulative totals as of finalization
+    pub total_burned: u64,
+    pub total_staked: u64,
+    pub vault_balance: u64,
+    pub token_supply: u64,
+    pub finalized_at: i64,
+    pub bump: u8,
+}
+
+impl WeeklyReport {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+// Grants `holder` the duties of `role`; closed on revocation
+#[account]
+pub struct RoleAssignment {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted_at: i64,
+    pub oracle_epoch: u64, // Oracle assignments lapse once game_state.oracle_epoch moves past this
+    pub bump: u8,
+}
+
+impl RoleAssignment {
+    pub const LEN: usize = 1 + 32 + 8 + 8 + 1;
+}
+
+// One page of the append-only list of every account of a kind ever created, so indexers can
+// walk pages 0..=registry_page_index instead of scanning all program accounts
+#[account]
+pub struct RegistryPage {
+    pub kind: RegistryKind,
+    pub page: u64,
+    pub entries: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+impl RegistryPage {
+    pub const MAX_ENTRIES: usize = 32;
+    pub const LEN: usize = 1 + 8 + (4 + 32 * Self::MAX_ENTRIES) + 1;
+}
+
+// A player's open positions (prediction stakes, curation votes, track submissions, payment
+// streams, tournament and jackpot entries, queue tickets, pre-orders and vesting balances), so
+// wallets can list them with a single fetch instead of deriving each PDA. Every instruction that
+// opens or closes one of these takes the index address, so once the index exists it can't be
+// skipped. Positions opened before the index was created are not listed.
+#[account]
+pub struct PlayerIndex {
+    pub player: Pubkey,
+    pub entries: Vec<IndexEntry>,
+    pub bump: u8,
+}
+
+impl PlayerIndex {
+    pub const MAX_ENTRIES: usize = 32;
+    pub const LEN: usize = 32 + (4 + IndexEntry::LEN * Self::MAX_ENTRIES) + 1;
+
+    // Track an opened position; addresses already listed are left as they are
+    pub fn add(&mut self, kind: PositionKind, address: Pubkey) -> Result<()> {
+        if self.entries.iter().any(|entry| entry.address == address) {
+            return Ok(());
+        }
+        require!(self.entries.len() < Self::MAX_ENTRIES, ErrorCode::PlayerIndexFull);
+        self.entries.push(IndexEntry { kind, address });
+        Ok(())
+    }
+
+    pub fn remove(&mut self, address: &Pubkey) {
+        self.entries.retain(|entry| entry.address != *address);
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct IndexEntry {
+    pub kind: PositionKind,
+    pub address: Pubkey,
+}
+
+impl IndexEntry {
+    pub const LEN: usize = 1 + 32;
+}
+
+// The position kinds PlayerIndex tracks
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum PositionKind {
+    PredictionStake,
+    CurationVote,
+    TrackSubmission,
+    PaymentStream,
+    TournamentEntry,
+    QueueTicket,
+    Vesting, // The player's profile, while it holds a vesting balance
+    JackpotEntry,
+    Preorder,
+}
+
+// Record of a paid race award, one per player and race id
+#[account]
+pub struct RaceReceipt {
+    pub player: Pubkey,
+    pub race_id: u64,
+    pub amount: u64,
+    pub replay_hash: Option<[u8; 32]>, // Hash of the server-side replay, committed at award time
+    pub awarded_at: i64,
+    pub status: AwardStatus,
+    pub release_at: i64,      // End of the dispute window for pending awards
+    pub challenger: Pubkey,   // Default pubkey = not challenged
+    pub challenge_bond: u64,  // Bond posted by the challenger, held in the vault
+    pub rent_fronted: bool,   // Rent came from the rent treasury and returns there on close
+    pub bump: u8,
+}
+
+impl RaceReceipt {
+    pub const LEN: usize = 32 + 8 + 8 + (1 + 32) + 8 + 1 + 8 + 32 + 8 + 1 + 1;
+}
+
+// Running MVP tally for one race; created by the first vote
+#[account]
+pub struct MvpBallot {
+    pub race_id: u64,
+    pub closes_at: i64,
+    pub tallies: Vec<MvpTally>,
+    pub total_votes: u16,
+    pub settled: bool,
+    pub bump: u8,
+}
+
+impl MvpBallot {
+    pub const MAX_CANDIDATES: usize = 16;
+    pub const LEN: usize = 8 + 8 + (4 + MvpTally::LEN * Self::MAX_CANDIDATES) + 2 + 1 + 1;
+
+    pub fn record_vote(&mut self, candidate: Pubkey) -> Result<()> {
+        match self.tallies.iter_mut().find(|tally| tally.candidate == candidate) {
+            Some(tally) => tally.votes = tally.votes.saturating_add(1),
+            None => {
+                require!(self.tallies.len() < Self::MAX_CANDIDATES, ErrorCode::InvalidMvpVote);
+                self.tallies.push(MvpTally { candidate, votes: 1 });
+            }
+        }
+        self.total_votes = self.total_votes.saturating_add(1);
+        Ok(())
+    }
+
+    // Most voted candidate; ties go to whoever received a vote first
+    pub fn leader(&self) -> Option<MvpTally> {
+        self.tallies.iter().fold(None, |best: Option<MvpTally>, tally| match best {
+            Some(best) if best.votes >= tally.votes => Some(best),
+            _ => Some(*tally),
+        })
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MvpTally {
+    pub candidate: Pubkey,
+    pub votes: u16,
+}
+
+impl MvpTally {
+    pub const LEN: usize = 32 + 2;
+}
+
+// One per (race, voter); its existence is the voter's single MVP vote
+#[account]
+pub struct MvpVote {
+    pub race_id: u64,
+    pub voter: Pubkey,
+    pub candidate: Pubkey,
+    pub voted_at: i64,
+    pub bump: u8,
+}
+
+impl MvpVote {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+}
+
+// Budget letting an external program award $SPEEDY to its own players
+#[account]
+pub struct PartnerGrant {
+    pub partner_program: Pubkey,
+    pub budget_per_epoch: u64,
+    pub epoch_secs: i64,
+    pub epoch_index: u64,       // Epoch `spent_this_epoch` belongs to
+    pub spent_this_epoch: u64,
+    pub total_awarded: u64,
+    pub bump: u8,
+}
+
+impl PartnerGrant {
+    pub const LEN: usize = 32 + 8 + 8 + 8 + 8 + 8 + 1;
+}
+
+// Honeycomb Protocol missions that pay $SPEEDY, and profile traits that boost them
+#[account]
+pub struct HoneycombConfig {
+    pub honeycomb_program: Pubkey, // Only program allowed to report mission completions
+    pub mission_rewards: Vec<MissionReward>,
+    pub trait_multipliers: Vec<TraitMultiplier>,
+    pub bump: u8,
+}
+
+impl HoneycombConfig {
+    pub const MAX_MISSIONS: usize = 16;
+    pub const MAX_TRAITS: usize = 16;
+    pub const LEN: usize = 32 + (4 + MissionReward::LEN * Self::MAX_MISSIONS)
+        + (4 + TraitMultiplier::LEN * Self::MAX_TRAITS) + 1;
+
+    // Highest multiplier among the player's traits; unmapped traits leave the reward unchanged
+    pub fn multiplier_bps(&self, trait_hashes: &[[u8; 32]]) -> u32 {
+        self.trait_multipliers.iter()
+            .filter(|t| trait_hashes.contains(&t.trait_hash))
+            .map(|t| t.multiplier_bps)
+            .max()
+            .unwrap_or(BPS_DENOMINATOR as u32)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MissionReward {
+    pub mission_id: u64,
+    pub reward: u64,
+}
+
+impl MissionReward {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TraitMultiplier {
+    pub trait_hash: [u8; 32], // Hash of the Honeycomb trait label and value
+    pub multiplier_bps: u32,
+}
+
+impl TraitMultiplier {
+    pub const LEN: usize = 32 + 4;
+}
+
+// Marks a Honeycomb mission completion as paid
+#[account]
+pub struct MissionClaim {
+    pub completion_id: [u8; 32],
+    pub player: Pubkey,
+    pub mission_id: u64,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl MissionClaim {
+    pub const LEN: usize = 32 + 32 + 8 + 8 + 8 + 1;
+}
+
+// Wormhole emitters on other chains allowed to attest gameplay rewards
+#[account]
+pub struct BridgeConfig {
+    pub wormhole_program: Pubkey, // Core bridge that owns posted VAAs
+    pub emitters: Vec<BridgeEmitter>,
+    pub max_amount_per_claim: u64,
+    pub bump: u8,
+}
+
+impl BridgeConfig {
+    pub const MAX_EMITTERS: usize = 8;
+    pub const LEN: usize = 32 + (4 + BridgeEmitter::LEN * Self::MAX_EMITTERS) + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BridgeEmitter {
+    pub chain: u16,          // Wormhole chain id
+    pub address: [u8; 32],
+}
+
+impl BridgeEmitter {
+    pub const LEN: usize = 2 + 32;
+}
+
+// Borsh payload of a bridge reward VAA
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BridgeRewardPayload {
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+// Marks a bridge message as paid, one per emitter and sequence
+#[account]
+pub struct BridgeClaim {
+    pub emitter_chain: u16,
+    pub emitter_address: [u8; 32],
+    pub sequence: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl BridgeClaim {
+    pub const LEN: usize = 2 + 32 + 8 + 32 + 8 + 8 + 1;
+}
+
+// Region-specific reward budget, paid from its own vault shard
+#[account]
+pub struct RegionPool {
+    pub region: Region,
+    pub vault: Pubkey,               // Token account owned by this PDA
+    pub rates: Option<TokenRates>,   // Rates the game server prices this region's vouchers with
+    pub total_distributed: u64,
+    pub bump: u8,
+}
+
+impl RegionPool {
+    pub const LEN: usize = 1 + 32 + (1 + TokenRates::LEN) + 8 + 1;
+
+    pub fn rates_or(&self, game_state: &GameState) -> Result<TokenRates> {
+        match self.rates {
+            Some(rates) => rates.try_map(|rate| game_state.halve(rate)),
+            None => game_state.active_rates(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    NorthAmerica,
+    Europe,
+    AsiaPacific,
+}
+
+// Sensitive admin change staged by `propose_admin_action`
+#[account]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub action: AdminAction,
+    pub summary_hash: [u8; 32], // Hash of the Borsh-serialized action, confirmed at execution
+    pub proposed_at: i64,
+    pub executable_at: i64, // proposed_at + ADMIN_PROPOSAL_DELAY_SECS
+    pub bump: u8,
+}
+
+impl Proposal {
+    pub const LEN: usize = 8 + AdminAction::LEN + 32 + 8 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum AdminAction {
+    UpdateTokenRates(TokenRates),
+    SweepToColdStorage { destination_token_account: Pubkey, amount: u64 },
+    WithdrawSaleProceeds { destination: Pubkey, lamports: u64 },
+    WithdrawUnsoldSaleTokens { destination_token_account: Pubkey },
+}
+
+impl AdminAction {
+    pub const LEN: usize = 1 + TokenRates::LEN; // Largest variant
+
+    pub fn summary_hash(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.try_to_vec()?).to_bytes())
+    }
+}
+
+// Time override consulted by `current_timestamp` in `test-clock` builds
+#[account]
+pub struct TestClock {
+    pub unix_timestamp: i64,
+    pub bump: u8,
+}
+
+impl TestClock {
+    pub const LEN: usize = 8 + 1;
+}
+
+#[account]
+pub struct League {
+    pub league_id: u64,
+    pub standings: Vec<LeagueStanding>, // Registration order until finalized, then rank order
+    pub prize_by_rank: Vec<u64>,
+    pub fixtures_reported: u64,         // Bitmap over `speedy_core::fixture_index`
+    pub promotion_count: u8,
+    pub relegation_count: u8,
+    pub finalized: bool,
+    pub metadata: Metadata, // Season name and off-chain artwork/rules
+    pub bump: u8,
+}
+
+impl League {
+    pub const MAX_PARTICIPANTS: usize = 8; // 28 fixtures fit the reported bitmap
+    pub const WIN_POINTS: u32 = 3;
+    pub const DRAW_POINTS: u32 = 1;
+    pub const LEN: usize = 8 + (4 + LeagueStanding::LEN * Self::MAX_PARTICIPANTS)
+        + (4 + 8 * Self::MAX_PARTICIPANTS) + 8 + 1 + 1 + 1 + Metadata::LEN + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LeagueStanding {
+    pub player: Pubkey,
+    pub points: u32,
+    pub played: u8,
+    pub flag: LeagueFlag,
+}
+
+impl LeagueStanding {
+    pub const LEN: usize = 32 + 4 + 1 + 1;
+}
+
+#[account]
+pub struct PredictionMarket {
+    pub week: u64,
+    pub candidates: Vec<Pubkey>,
+    pub outcome_pools: Vec<u64>, // Total staked per candidate
+    pub total_pool: u64,
+    pub close_time: i64,
+    pub rake_bps: u16,
+    pub settled: bool,
+    pub winning_outcome: Option<u8>, // None after settlement means the market is void
+    pub escrow: Pubkey,
+    pub bump: u8,
+}
+
+impl PredictionMarket {
+    pub const MAX_OUTCOMES: usize = 8;
+    pub const LEN: usize = 8 + (4 + 32 * Self::MAX_OUTCOMES) + (4 + 8 * Self::MAX_OUTCOMES)
+        + 8 + 8 + 2 + 1 + (1 + 1) + 32 + 1;
+}
+
+#[account]
+pub struct PredictionStake {
+    pub week: u64,
+    pub player: Pubkey,
+    pub outcome: u8,
+    pub amount: u64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl PredictionStake {
+    pub const LEN: usize = 8 + 32 + 1 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct Jackpot {
+    pub current_draw_id: u64,
+    pub entry_count: u32, // Entries in the current draw
+    pub last_draw_at: i64,
+    pub reserved_prizes: u64, // Drawn but unclaimed prizes still in the jackpot vault
+    pub seed_commitment: [u8; 32], // Hash of the seed the oracle reveals to draw the current round
+    pub bump: u8,
+}
+
+impl Jackpot {
+    pub const LEN: usize = 8 + 4 + 8 + 8 + 32 + 1;
+}
+
+// Upcoming car release sold by escrowed pre-order
+#[account]
+pub struct CarDrop {
+    pub drop_id: u64,
+    pub price: u64, // $SPEEDY per car
+    pub supply: u32,
+    pub preordered: u32,
+    pub drop_time: i64,
+    pub cancelled: bool,
+    pub car_mint: Pubkey, // 0-decimal mint, one unit per car; minted by this PDA
+    pub escrow: Pubkey,   // $SPEEDY token account owned by this PDA
+    pub bump: u8,
+}
+
+impl CarDrop {
+    pub const LEN: usize = 8 + 8 + 4 + 4 + 8 + 1 + 32 + 32 + 1;
+}
+
+// A player's pre-order in one car drop; closed on delivery or refund
+#[account]
+pub struct Preorder {
+    pub player: Pubkey,
+    pub drop_id: u64,
+    pub quantity: u32,
+    pub amount_locked: u64,
+    pub bump: u8,
+}
+
+impl Preorder {
+    pub const LEN: usize = 32 + 8 + 4 + 8 + 1;
+}
+
+// Declining-price primary sale of a car mint
+#[account]
+pub struct DutchAuction {
+    pub auction_id: u64,
+    pub config: DutchAuctionConfig,
+    pub sold: u32,
+    pub closed: bool,
+    pub car_mint: Pubkey, // 0-decimal mint, one unit per car; minted by this PDA
+    pub bump: u8,
+}
+
+impl DutchAuction {
+    pub const LEN: usize = 8 + DutchAuctionConfig::LEN + 4 + 1 + 32 + 1;
+
+    // Price per car at `now`: start price minus the decay so far, never below the floor
+    pub fn price_at(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.config.start_time).max(0) as u64;
+        self.config.start_price
+            .saturating_sub(self.config.decay_per_second.saturating_mul(elapsed))
+            .max(self.config.floor_price)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct DutchAuctionConfig {
+    pub rarity: CarRarity,
+    pub start_price: u64,
+    pub floor_price: u64,
+    pub decay_per_second: u64,
+    pub start_time: i64,
+    pub supply: u32,
+    pub per_wallet_cap: u32,
+}
+
+impl DutchAuctionConfig {
+    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 4 + 4;
+}
+
+// Cars a wallet has bought from one auction, for the per-wallet cap
+#[account]
+pub struct AuctionPurchase {
+    pub player: Pubkey,
+    pub auction_id: u64,
+    pub quantity: u32,
+    pub bump: u8,
+}
+
+impl AuctionPurchase {
+    pub const LEN: usize = 32 + 8 + 4 + 1;
+}
+
+// Physical merch item redeemable by burning $SPEEDY
+#[account]
+pub struct RedemptionOffer {
+    pub item_id: u64,
+    pub cost: u64,
+    pub supply: u32,
+    pub redeemed: u32,
+    pub deadline: i64,
+    pub fulfiller: Pubkey, // Key that marks redemptions as shipped
+    pub bump: u8,
+}
+
+impl RedemptionOffer {
+    pub const LEN: usize = 8 + 8 + 4 + 4 + 8 + 32 + 1;
+}
+
+// One redeemed item, keyed by offer and redemption index
+#[account]
+pub struct RedemptionReceipt {
+    pub player: Pubkey,
+    pub item_id: u64,
+    pub index: u32,
+    pub shipping_hash: [u8; 32], // Hash of the off-chain shipping details
+    pub redeemed_at: i64,
+    pub shipped: bool,
+    pub tracking_hash: [u8; 32], // Hash of the carrier tracking reference, set when shipped
+    pub bump: u8,
+}
+
+impl RedemptionReceipt {
+    pub const LEN: usize = 32 + 8 + 4 + 32 + 8 + 1 + 32 + 1;
+}
+
+#[account]
+pub struct JackpotEntry {
+    pub draw_id: u64,
+    pub player: Pubkey,
+    pub index: u32,
+    pub bump: u8,
+}
+
+impl JackpotEntry {
+    pub const LEN: usize = 8 + 32 + 4 + 1;
+}
+
+// Draw history, one account per draw
+#[account]
+pub struct JackpotDraw {
+    pub draw_id: u64,
+    pub randomness: [u8; 32], // Hash of the revealed seed and draw id
+    pub entry_count: u32,
+    pub winning_index: u32,
+    pub prize: u64,
+    pub winner: Pubkey, // Set when the prize is claimed
+    pub drawn_at: i64,
+    pub claimed: bool,
+    pub bump: u8,
+}
+
+impl JackpotDraw {
+    pub const LEN: usize = 8 + 32 + 4 + 4 + 8 + 32 + 8 + 1 + 1;
+}
+
+#[account]
+pub struct QueueTicket {
+    pub player: Pubkey,
+    pub stake_amount: u64,
+    pub entered_at: i64,
+    pub bump: u8,
+}
+
+impl QueueTicket {
+    pub const LEN: usize = 32 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct Grant {
+    pub grant_id: u64,
+    pub recipient: Pubkey,
+    pub total_amount: u64,
+    pub released_amount: u64,
+    pub milestones_released: u8,     // Milestones are released in order
+    pub milestone_amounts: Vec<u64>,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+impl Grant {
+    pub const MAX_MILESTONES: usize = 12;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 1 + (4 + 8 * Self::MAX_MILESTONES) + 8 + 1;
+}
+
+#[account]
+pub struct WeeklyStandings {
+    pub week: u64,
+    pub merkle_root: [u8; 32],
+    pub total_prize: u64,
+    pub claimed_amount: u64,
+    pub committed_at: i64,
+    pub bump: u8,
+}
+
+impl WeeklyStandings {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 1;
+}
+
+#[account]
+pub struct StandingsClaim {
+    pub week: u64,
+    pub player: Pubkey,
+    pub rank: u32,
+    pub amount: u64,
+    pub claimed_at: i64,
+    pub bump: u8,
+}
+
+impl StandingsClaim {
+    pub const LEN: usize = 8 + 32 + 4 + 8 + 8 + 1;
+}
+
+// Top scores for one season and track, best first
+#[account]
+pub struct Leaderboard {
+    pub season: u64,
+    pub track_id: u64,
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    pub const MAX_ENTRIES: usize = 20;
+    pub const LEN: usize = 8 + 8 + (4 + LeaderboardEntry::LEN * Self::MAX_ENTRIES) + 1;
+
+    // Replace the player's entry and return their 1-based rank, or 0 if the score falls off
+    // the bottom of a full board. Callers only insert improved scores, so a player pushed off
+    // the board never had a better entry on it.
+    pub fn insert(&mut self, entry: LeaderboardEntry) -> u32 {
+        self.entries.retain(|existing| existing.player != entry.player);
+        let index = self.entries.partition_point(|existing| existing.ranks_above(&entry));
+        if index >= Self::MAX_ENTRIES {
+            return 0;
+        }
+        self.entries.insert(index, entry);
+        self.entries.truncate(Self::MAX_ENTRIES);
+        index as u32 + 1
+    }
+}
+
+// A player's best score for one season and track; it only ever increases
+#[account]
+pub struct PlayerBest {
+    pub season: u64,
+    pub track_id: u64,
+    pub player: Pubkey,
+    pub best_score: u64,
+    pub best_slot: u64,
+    pub submissions: u32, // Including ignored ones
+    pub bump: u8,
+}
+
+impl PlayerBest {
+    pub const LEN: usize = 8 + 8 + 32 + 8 + 8 + 4 + 1;
+}
+
+// A race track whose ownership NFT holder earns `owner_cut_bps` of race awards paid on it
+#[account]
+pub struct Track {
+    pub track_id: u64,
+    pub owner_mint: Pubkey,  // Ownership NFT; default = no owner
+    pub owner_cut_bps: u16,
+    pub total_owner_paid: u64,
+    pub races_paid: u64,
+    pub bump: u8,
+}
+
+impl Track {
+    pub const LEN: usize = 8 + 32 + 2 + 8 + 8 + 1;
+    pub const MAX_OWNER_CUT_BPS: u16 = 1_000;
+}
+
+// A proposed community track under curation; creator and curator stakes sit in the escrow
+// owned by this PDA
+#[account]
+pub struct TrackSubmission {
+    pub track_id: u64,
+    pub creator: Pubkey,
+    pub content_hash: [u8; 32], // Hash of the track data, published off-chain
+    pub stake: u64,
+    pub votes_for: u64,         // Curator stake voting to accept
+    pub votes_against: u64,
+    pub voting_ends_at: i64,
+    pub status: SubmissionStatus,
+    pub escrow: Pubkey,
+    pub bump: u8,
+}
+
+impl TrackSubmission {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 32 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    Voting,
+    Accepted,
+    Rejected,
+}
+
+// A curator's staked vote on one submission; the stake is returned once voting is over
+#[account]
+pub struct CurationVote {
+    pub track_id: u64,
+    pub curator: Pubkey,
+    pub stake: u64,
+    pub approve: bool,
+    pub bump: u8,
+}
+
+impl CurationVote {
+    pub const LEN: usize = 8 + 32 + 8 + 1 + 1;
+}
+
+// Fastest replay-verified time on a track and the bounty waiting for whoever beats it
+#[account]
+pub struct WorldRecord {
+    pub track_id: u64,
+    pub holder: Pubkey,
+    pub time_ms: u64,
+    pub replay_hash: [u8; 32],
+    pub set_at: i64,
+    pub bounty: u64,      // Paid from the vault to the next record holder
+    pub records_set: u32,
+    pub bump: u8,
+}
+
+impl WorldRecord {
+    pub const LEN: usize = 8 + 32 + 8 + 32 + 8 + 8 + 4 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LeaderboardEntry {
+    pub player: Pubkey,
+    pub score: u64,
+    pub slot: u64, // Submission slot, the first tie-breaker
+}
+
+impl LeaderboardEntry {
+    pub const LEN: usize = 32 + 8 + 8;
+
+    pub fn ranks_above(&self, other: &LeaderboardEntry) -> bool {
+        speedy_core::leaderboard_order(
+            (self.score, self.slot, &self.player.to_bytes()),
+            (other.score, other.slot, &other.player.to_bytes()),
+        ) == std::cmp::Ordering::Less
+    }
+}
+
+#[account]
+pub struct PaymentStream {
+    pub stream_id: u64,
+    pub recipient: Pubkey,
+    pub rate_per_second: u64,
+    pub start_time: i64,
+    pub cliff_time: i64, // Nothing is claimable before the cliff
+    pub end_time: i64,   // Pulled forward on cancellation
+    pub withdrawn: u64,
+    pub bump: u8,
+}
+
+impl PaymentStream {
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    // Total amount streamed as of `now`, including what has already been withdrawn
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        speedy_core::stream_vested_amount(
+            self.rate_per_second,
+            self.start_time,
+            self.cliff_time,
+            self.end_time,
+            now,
+        ).ok_or(ErrorCode::InvalidRewardAmount.into())
+    }
+}
+
+#[account]
+pub struct BuybackConfig {
+    pub amm_program: Pubkey,
+    pub stable_mint: Pubkey,
+    pub stable_treasury: Pubkey, // Stablecoin ATA owned by game_state
+    pub total_stable_spent: u64,
+    pub total_tokens_burned: u64,
+    pub bump: u8,
+}
+
+impl BuybackConfig {
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SaleConfig {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub base_price_lamports: u64,  // Lamports per whole token at the start of the sale
+    pub price_slope_lamports: u64, // Price increase per whole token sold (0 = fixed price)
+    pub per_wallet_cap: u64,       // Max raw token units per buyer
+}
+
+impl SaleConfig {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8;
+}
+
+#[account]
+pub struct TokenSale {
+    pub config: SaleConfig,
+    pub sale_vault: Pubkey,
+    pub tokens_sold: u64,
+    pub lamports_raised: u64,
+    pub bump: u8,
+}
+
+impl TokenSale {
+    pub const LEN: usize = SaleConfig::LEN + 32 + 8 + 8 + 1;
+}
+
+// Payments provider allowed to release sale tokens for fiat purchases
+#[account]
+pub struct OnrampProvider {
+    pub provider: Pubkey, // ed25519 key that signs onramp vouchers
+    pub budget_remaining: u64,
+    pub total_redeemed: u64,
+    pub bump: u8,
+}
+
+impl OnrampProvider {
+    pub const LEN: usize = 32 + 8 + 8 + 1;
+}
+
+// Marks a provider's order id as redeemed
+#[account]
+pub struct OnrampOrder {
+    pub provider: Pubkey,
+    pub order_id: u64,
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub redeemed_at: i64,
+    pub bump: u8,
+}
+
+impl OnrampOrder {
+    pub const LEN: usize = 32 + 8 + 32 + 8 + 8 + 1;
+}
+
+// Fiat purchase authorization signed by a payments provider
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OnrampVoucher {
+    pub wallet: Pubkey,
+    pub amount: u64,
+    pub order_id: u64,
+}
+
+impl OnrampVoucher {
+    // Bytes the provider signs; the domain tag keeps them distinct from reward vouchers
+    pub fn message(&self) -> Vec<u8> {
+        [
+            b"speedy_onramp".as_ref(),
+            self.wallet.as_ref(),
+            &self.amount.to_le_bytes(),
+            &self.order_id.to_le_bytes(),
+        ].concat()
+    }
+}
+
+#[account]
+pub struct SalePurchase {
+    pub buyer: Pubkey,
+    pub amount_purchased: u64,
+    pub bump: u8,
+}
+
+impl SalePurchase {
+    pub const LEN: usize = 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EliminationStats {
+    pub race_id: u64,
+    pub survival_secs: u64,
+    pub eliminations: u64,
+    pub placement: u16, // 1 = last racer standing
+}
+
+// Reward authorization signed off-chain by the oracle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RewardVoucher {
+    pub player: Pubkey,
+    pub reward_type: RewardType,
+    pub reference_id: u64, // race, challenge, tournament or car id depending on reward_type
+    pub amount: u64,
+    pub nonce: u64,
+    pub expires_at: i64,
+    pub oracle_epoch: u64, // Must match game_state.oracle_epoch at redemption
+}
+
+impl RewardVoucher {
+    // Bytes the oracle signs
+    pub fn message(&self) -> Vec<u8> {
+        speedy_core::voucher_message(
+            &self.player.to_bytes(),
+            self.reward_type as u8,
+            self.reference_id,
+            self.amount,
+            self.nonce,
+            self.expires_at,
+            self.oracle_epoch,
+        )
+    }
+
+    // Hash of the signed bytes; keys the UsedVoucher replay registry
+    pub fn hash(&self) -> [u8; 32] {
+        hash(&self.message()).to_bytes()
+    }
+
+    // Bytes the oracle signs for a voucher paid from a regional pool
+    pub fn regional_message(&self, region: Region) -> Vec<u8> {
+        [self.message().as_slice(), b"region", &[region as u8]].concat()
+    }
+
+    pub fn regional_hash(&self, region: Region) -> [u8; 32] {
+        hash(&self.regional_message(region)).to_bytes()
+    }
+}
+
+#[account]
+pub struct UsedVoucher {
+    pub voucher_hash: [u8; 32],
+    pub player: Pubkey,
+    pub redeemed_at: i64,
+    pub bump: u8,
+}
+
+impl UsedVoucher {
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RaceStats {
+    pub race_id: u64,
+    pub completed: bool,
+    pub won: bool,
+    pub distance: u64,
+    pub obstacles_avoided: u64,
+    pub bonus_boxes_collected: u64,
+    pub lap_time: u64,
+    pub score: u64,
+}
+
+impl RaceStats {
+    pub fn performance(&self) -> speedy_core::RacePerformance {
+        speedy_core::RacePerformance {
+            completed: self.completed,
+            won: self.won,
+            distance: self.distance,
+            obstacles_avoided: self.obstacles_avoided,
+            bonus_boxes_collected: self.bonus_boxes_collected,
+        }
+    }
+}
+
+// Reward calculation for one game mode. Adding a mode means adding a strategy and a
+// GameMode variant rather than another award instruction.
+pub trait RewardStrategy {
+    // Reward in the units of the active rate table (before USD conversion and probation)
+    fn reward(&self, game_state: &GameState) -> Result<u64>;
+    // Race id reported in the reward event
+    fn race_id(&self) -> u64;
+    // Whether the race counts toward the player's completed races
+    fn completed(&self) -> bool;
+    fn name(&self) -> &'static str;
+}
+
+// Standard circuit race: completion, win, distance, obstacles and bonus boxes
+pub struct CircuitRace<'a>(pub &'a RaceStats);
+
+impl RewardStrategy for CircuitRace<'_> {
+    fn reward(&self, game_state: &GameState) -> Result<u64> {
+        speedy_core::race_reward(&game_state.active_rates()?.race_rates(), &self.0.performance())
+            .ok_or(ErrorCode::InvalidRewardAmount.into())
+    }
+
+    fn race_id(&self) -> u64 {
+        self.0.race_id
+    }
+
+    fn completed(&self) -> bool {
+        self.0.completed
+    }
+
+    fn name(&self) -> &'static str {
+        "circuit race"
+    }
+}
+
+// Drift/stunt event: paid on score instead of distance
+pub struct DriftMode<'a>(pub &'a RaceStats);
+
+impl RewardStrategy for DriftMode<'_> {
+    fn reward(&self, game_state: &GameState) -> Result<u64> {
+        let drift = &game_state.drift_rates;
+        let reward = speedy_core::score_reward(self.0.score, drift.per_1000_points, drift.max_reward)
+            .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.halve(reward)
+    }
+
+    fn race_id(&self) -> u64 {
+        self.0.race_id
+    }
+
+    fn completed(&self) -> bool {
+        self.0.completed
+    }
+
+    fn name(&self) -> &'static str {
+        "drift event"
+    }
+}
+
+// Elimination royale: survival time and eliminations with podium multipliers
+pub struct EliminationRoyale<'a>(pub &'a EliminationStats);
+
+impl RewardStrategy for EliminationRoyale<'_> {
+    fn reward(&self, game_state: &GameState) -> Result<u64> {
+        let rates = &game_state.elimination_rates;
+        let reward = speedy_core::elimination_reward(
+            rates.per_survival_minute,
+            rates.per_elimination,
+            &rates.podium_multiplier_bps,
+            self.0.survival_secs,
+            self.0.eliminations,
+            self.0.placement,
+        )
+        .ok_or(ErrorCode::InvalidRewardAmount)?;
+        game_state.halve(reward)
+    }
+
+    fn race_id(&self) -> u64 {
+        self.0.race_id
+    }
+
+    fn completed(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &'static str {
+        "elimination royale"
+    }
+}
+
+// Game mode of a race, carrying the stats its reward strategy needs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum GameMode {
+    Circuit(RaceStats),
+    Drift(RaceStats),
+    Elimination(EliminationStats),
+}
+
+impl GameMode {
+    pub fn strategy(&self) -> Box<dyn RewardStrategy + '_> {
+        match self {
+            GameMode::Circuit(stats) => Box::new(CircuitRace(stats)),
+            GameMode::Drift(stats) => Box::new(DriftMode(stats)),
+            GameMode::Elimination(stats) => Box::new(EliminationRoyale(stats)),
+        }
+    }
+}
+
+// Enums
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum ChallengeDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum TournamentPlacement {
+    Participation,
+    Winner,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum CarRarity {
+    Common,
+    Rare,
+    Epic,
+    Legendary,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[repr(u8)]
+pub enum RewardType {
+    RaceCompletion,
+    DailyChallenge,
+    Tournament,
+    WelcomeBonus,
+    Staking,
+    LeaderboardPrize,
+    RelayRace,
+    LeaguePrize,
+    Jackpot,
+    Partner,
+    HoneycombMission,
+    Bridge,
+    Promo,
+}
+
+impl RewardType {
+    pub const COUNT: usize = 13;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AwardStatus {
+    Paid,
+    Pending,  // Inside the dispute window
+    Disputed, // Challenged, waiting for oracle re-verification
+    Voided,   // Challenge upheld; the award stays in the vault
+}
+
+// Typed counterpart of `RewardType` + the overloaded `race_id`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RewardSubject {
+    Race { race_id: u64 },
+    Challenge { challenge_id: u64 },
+    Tournament { tournament_id: u64 },
+    WelcomeBonus,
+    Staking { car_id: u64 },
+    Leaderboard { week: u64 },
+    Relay { race_id: u64 },
+    League { league_id: u64 },
+    Jackpot { draw_id: u64 },
+    Partner { award_id: u64 }, // Id chosen by the partner program
+    HoneycombMission { mission_id: u64 },
+    Bridge { sequence: u64 }, // Sequence of the attesting bridge message
+    Promo { promo_id: u64 },
+}
+
+impl RewardSubject {
+    // Decode the id a v1 event carries in `race_id` for the given reward type
+    pub fn from_legacy(reward_type: RewardType, id: u64) -> Self {
+        match reward_type {
+            RewardType::RaceCompletion => Self::Race { race_id: id },
+            RewardType::DailyChallenge => Self::Challenge { challenge_id: id },
+            RewardType::Tournament => Self::Tournament { tournament_id: id },
+            RewardType::WelcomeBonus => Self::WelcomeBonus,
+            RewardType::Staking => Self::Staking { car_id: id },
+            RewardType::LeaderboardPrize => Self::Leaderboard { week: id },
+            RewardType::RelayRace => Self::Relay { race_id: id },
+            RewardType::LeaguePrize => Self::League { league_id: id },
+            RewardType::Jackpot => Self::Jackpot { draw_id: id },
+            RewardType::Partner => Self::Partner { award_id: id },
+            RewardType::HoneycombMission => Self::HoneycombMission { mission_id: id },
+            RewardType::Bridge => Self::Bridge { sequence: id },
+            RewardType::Promo => Self::Promo { promo_id: id },
+        }
+    }
+}
+
+// Operational duties that can be delegated away from the authority
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Oracle,      // Oracle-signed results, prizes and scores
+    Treasurer,   // Vault funding, sweeps, buybacks, sale proceeds and payment streams
+    RateManager, // Token, USD, mode and region reward rates
+    Moderator,   // Clawbacks and balance adjustments
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryKind {
+    Tournament,
+    League,
+    PredictionMarket,
+}
+
+impl RegistryKind {
+    pub const COUNT: usize = 3;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum CrankKind {
+    WaitlistPromotion,
+    QueueTicketRefund,
+    WeeklyReport,
+}
+
+impl CrankKind {
+    pub const COUNT: usize = 3;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum LeagueFlag {
+    None,
+    Promoted,
+    Relegated,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum FixtureOutcome {
+    HomeWin,
+    AwayWin,
+    Draw,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub enum SpendType {
+    TournamentEntry,
+    CarUpgrade,
+    CarCustomization,
+    StakingBoost,
+}
+
+// Events
+#[event]
+pub struct TokenReward {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reward_type: RewardType,
+    pub race_id: u64, // Deprecated: overloaded per reward type, read TokenRewardV2::subject instead
+    pub timestamp: i64,
+}
+
+// Would-be TokenReward from `preview_award`; no tokens moved
+#[event]
+pub struct AwardPreview {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reward_type: RewardType,
+    pub race_id: u64,
+    pub timestamp: i64,
+    pub withheld: u64, // Amount probation would hold back
+    pub released: u64, // Previously withheld amount that would be released
+    pub balance_adjustment_applied: i64,
+}
+
+// v2 events add balances for reconciliation; v1 events are still emitted alongside them
+#[event]
+pub struct TokenRewardV2 {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reward_type: RewardType,
+    pub race_id: u64, // Deprecated, kept for one release: use `subject`
+    pub timestamp: i64,
+    pub vault_balance_before: u64, // Balance of the paying account (vault or prize escrow)
+    pub vault_balance_after: u64,
+    pub player_balance_after: u64,
+    pub subject: RewardSubject, // What the reward was paid for, with explicitly named ids
+    pub halving_era: u8, // Emission era in effect when the reward was paid
+}
+
+#[event]
+pub struct CreditsChanged {
+    pub player: Pubkey,
+    pub delta: i64,
+    pub credits: u64,      // Balance after the change
+    pub reference_id: u64, // Game-side purchase id for deductions; 0 for conversions
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenSpendV2 {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub spend_type: SpendType,
+    pub timestamp: i64,
+    pub burned: u64,
+    pub jackpot_share: u64,
+    pub player_balance_before: u64,
+    pub player_balance_after: u64,
+    pub donation: u64, // Charity round-up share, sent to the charity wallet
+    pub burn_bps: u16,       // Burn curve share applied to the remainder
+    pub treasury_share: u64, // Part of the remainder sent to the vault instead of burned
+}
+
+#[event]
+pub struct PlayerAttested {
+    pub player: Pubkey,
+    pub attestation_hash: [u8; 32],
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClawbackProposed {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub reason_hash: [u8; 32],
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrizeHeld {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub release_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrizeHoldClawedBack {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub verdict_hash: [u8; 32], // Hash of the off-chain cheating verdict
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnclaimedPrizesRolledOver {
+    pub tournament_id: u64,
+    pub next_tournament_id: Option<u64>, // None = returned to the game vault
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ClawbackExecuted {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub from_withheld: u64,
+    pub added_debt: u64,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceAdjusted {
+    pub player: Pubkey,
+    pub delta: i64,
+    pub balance_adjustment: i64,
+    pub reason_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BalanceAdjustmentApplied {
+    pub player: Pubkey,
+    pub applied: i64, // Negative when debt was recovered, positive when a credit was paid
+    pub remaining: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OracleEpochBumped {
+    pub oracle_epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeysRotated {
+    pub oracle: Pubkey,
+    pub co_authority: Pubkey,
+    pub revoked_treasurer: Option<Pubkey>,
+    pub treasurer: Option<Pubkey>,
+    pub oracle_epoch: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoleChanged {
+    pub role: Role,
+    pub holder: Pubkey,
+    pub granted: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YearlyStatementClosed {
+    pub player: Pubkey,
+    pub year: u16,
+    pub reward_count: u32,
+    pub total: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProbationRewardsReleased {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MintMigrated {
+    pub legacy_mint: Pubkey,
+    pub new_mint: Pubkey,
+    pub new_vault: Pubkey,
+    pub numerator: u64,
+    pub denominator: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct LegacyTokensSwapped {
+    pub player: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SaleTokensPurchased {
+    pub buyer: Pubkey,
+    pub amount: u64,
+    pub lamports_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GrantMilestoneReleased {
+    pub grant_id: u64,
+    pub recipient: Pubkey,
+    pub milestone: u8,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardVested {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub vesting_end: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardAutoStaked {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub bonus: u64,
+    pub unlock_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EverythingClaimed {
+    pub player: Pubkey,
+    pub race_awards: u64,
+    pub unstaked: u64,
+    pub vested: u64,      // Including the part that repaid entry fee loans
+    pub loan_repaid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PayoutSwapped {
+    pub player: Pubkey,
+    pub tokens_in: u64,
+    pub stable_out: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinningsUnstaked {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EntryFeeLoaned {
+    pub player: Pubkey,
+    pub tournament_id: u64,
+    pub amount: u64,
+    pub outstanding: u64, // Total fee loans the player now owes
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedRewardsClaimed {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PaymentStreamClaimed {
+    pub stream_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BuybackExecuted {
+    pub stable_spent: u64,
+    pub tokens_burned: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct FundVault<'info> {
-    #[account(mut)]
-    pub authority: Signer<'info>,
+#[event]
+pub struct RatingUpdated {
+    pub player: Pubkey,
+    pub old_rating: u32,
+    pub new_rating: u32,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"game_state"],
-        bump = game_state.bump,
-    )]
-    pub game_state: Account<'info, GameState>,
+#[event]
+pub struct QueueTicketResolved {
+    pub player: Pubkey,
+    pub stake_amount: u64,
+    pub forfeited: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = game_state.token_mint,
-        associated_token::authority = authority,
-    )]
-    pub authority_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct EntrantDisqualified {
+    pub tournament_id: u64,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub forfeited: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        address = game_state.vault,
-    )]
-    pub vault: Account<'info, TokenAccount>,
+#[event]
+pub struct AdminActionProposed {
+    pub proposal_id: u64,
+    pub action: AdminAction,
+    pub summary_hash: [u8; 32],
+    pub executable_at: i64,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct AdminActionExecuted {
+    pub proposal_id: u64,
+    pub summary_hash: [u8; 32],
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct AwardTokens<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+#[event]
+pub struct DutchAuctionClosed {
+    pub auction_id: u64,
+    pub sold: u32,
+    pub final_price: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"game_state"],
-        bump = game_state.bump,
-    )]
-    pub game_state: Account<'info, GameState>,
+#[event]
+pub struct UpgradeAuthorityVerified {
+    pub expected: Pubkey,
+    pub actual: Option<Pubkey>, // None once the program is made immutable
+    pub deployed_slot: u64,
+    pub matched: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        address = game_state.token_mint,
-    )]
-    pub token_mint: Account<'info, Mint>,
+#[event]
+pub struct Solvency {
+    pub vault_balance: u64,
+    pub vault_liabilities: u64,
+    pub jackpot_balance: u64,     // 0 when the jackpot was not checked
+    pub jackpot_liabilities: u64,
+    pub solvent: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        address = game_state.vault,
-    )]
-    pub vault: Account<'info, TokenAccount>,
+#[event]
+pub struct ChallengeRewardsRecomputed {
+    pub day: u64,
+    pub multiplier_bps: [u16; ChallengeBalance::DIFFICULTIES],
+    pub timestamp: i64,
+}
 
-    #[account(
-        init_if_needed,
-        payer = player,
-        associated_token::mint = token_mint,
-        associated_token::authority = player,
-    )]
-    pub player_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct HalvingEraStarted {
+    pub era: u8,
+    pub total_distributed: u64,
+    pub slot: u64,
+}
 
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
+#[event]
+pub struct WatchdogTripped {
+    pub window_distributed: u64,
+    pub average_per_window: u64,
+    pub slot: u64,
 }
 
-#[derive(Accounts)]
-pub struct SpendTokens<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
+#[event]
+pub struct AwardChallenged {
+    pub player: Pubkey,
+    pub race_id: u64,
+    pub challenger: Pubkey,
+    pub bond: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        seeds = [b"game_state"],
-        bump = game_state.bump,
-    )]
-    pub game_state: Account<'info, GameState>,
+#[event]
+pub struct AwardDisputeResolved {
+    pub player: Pubkey,
+    pub race_id: u64,
+    pub challenge_upheld: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        address = game_state.token_mint,
-    )]
-    pub token_mint: Account<'info, Mint>,
+#[event]
+pub struct FeatureToggled {
+    pub feature: u64,
+    pub enabled: bool,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        associated_token::mint = token_mint,
-        associated_token::authority = player,
-    )]
-    pub player_token_account: Account<'info, TokenAccount>,
+#[event]
+pub struct CrankTipPaid {
+    pub kind: CrankKind,
+    pub recipient: Pubkey, // Token account the tip was sent to
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    pub token_program: Program<'info, Token>,
+#[event]
+pub struct WeeklyReportFinalized {
+    pub week: u64,
+    pub total_distributed: u64,
+    pub total_burned: u64,
+    pub total_staked: u64,
+    pub vault_balance: u64,
+    pub token_supply: u64,
+    pub timestamp: i64,
 }
 
-#[derive(Accounts)]
-pub struct UpdateGameState<'info> {
-    pub authority: Signer<'info>,
+#[event]
+pub struct TournamentSponsored {
+    pub tournament_id: u64,
+    pub sponsor: Pubkey,
+    pub name_hash: [u8; 32],
+    pub amount: u64,
+    pub timestamp: i64,
+}
 
-    #[account(
-        mut,
-        seeds = [b"game_state"],
-        bump = game_state.bump,
-    )]
-    pub game_state: Account<'info, GameState>,
+#[event]
+pub struct JackpotDrawn {
+    pub draw_id: u64,
+    pub entry_count: u32,
+    pub winning_index: u32,
+    pub prize: u64,
+    pub timestamp: i64,
 }
 
-// Data Structures
-#[account]
-pub struct GameState {
-    pub authority: Pubkey,
-    pub token_mint: Pubkey,
-    pub vault: Pubkey,        // Vault that holds tokens for rewards
-    pub total_distributed: u64,
-    pub token_rates: TokenRates,
-    pub bump: u8,
-    pub is_initialized: bool,
+#[event]
+pub struct TokenSpend {
+    pub player: Pubkey,
+    pub amount: u64,
+    pub spend_type: SpendType,
+    pub timestamp: i64,
 }
 
-impl GameState {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + TokenRates::LEN + 1 + 1; // Added 8 bytes for discriminator
+#[event]
+pub struct CosmeticAcquired {
+    pub player: Pubkey,
+    pub item_id: u64,
+    pub quantity: u32,
+    pub owned: u32,
+    pub source: CosmeticSource,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct TokenRates {
-    pub race_completion: u64,
-    pub race_win: u64,
-    pub distance_per_100m: u64,
-    pub obstacle_avoided: u64,
-    pub bonus_collected: u64,
-    pub daily_challenge_easy: u64,
-    pub daily_challenge_medium: u64,
-    pub daily_challenge_hard: u64,
-    pub tournament_participation: u64,
-    pub tournament_winner: u64,
-    pub welcome_bonus: u64,
-    pub staking_per_hour_common: u64,
-    pub staking_per_hour_rare: u64,
-    pub staking_per_hour_epic: u64,
-    pub staking_per_hour_legendary: u64,
+#[event]
+pub struct CosmeticEquipped {
+    pub player: Pubkey,
+    pub item_id: u64,
+    pub equipped: bool,
 }
 
-impl TokenRates {
-    pub const LEN: usize = 8 * 15; // 15 u64 fields
+#[event]
+pub struct BuffGranted {
+    pub player: Pubkey,
+    pub target: BuffTarget,
+    pub multiplier_bps: u16,
+    pub expires_at: i64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct RaceStats {
+#[event]
+pub struct PromoRedeemed {
+    pub player: Pubkey,
+    pub promo_id: u64,
+    pub effect: PromoEffect,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RaceSettled {
+    pub player: Pubkey,
     pub race_id: u64,
-    pub completed: bool,
-    pub won: bool,
-    pub distance: u64,
-    pub obstacles_avoided: u64,
-    pub bonus_boxes_collected: u64,
-    pub lap_time: u64,
-    pub score: u64,
+    pub amount: u64,
+    pub status: AwardStatus, // Paid, or Pending inside a dispute window
+    pub auto_staked: bool,
+    pub races_completed: u64,
+    pub rating: u32,
+    pub timestamp: i64,
 }
 
-// Enums
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub enum ChallengeDifficulty {
-    Easy,
-    Medium,
-    Hard,
+#[event]
+pub struct MvpAwarded {
+    pub race_id: u64,
+    pub winner: Pubkey,
+    pub votes: u16,
+    pub total_votes: u16,
+    pub bonus: u64, // 0 when the winner fell short of the minimum votes
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub enum TournamentPlacement {
-    Participation,
-    Winner,
+#[event]
+pub struct RateChanged {
+    pub field: RateField,
+    pub old_value: u64,
+    pub new_value: u64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub enum CarRarity {
-    Common,
-    Rare,
-    Epic,
-    Legendary,
+#[event]
+pub struct TrackSubmissionFinalized {
+    pub track_id: u64,
+    pub creator: Pubkey,
+    pub accepted: bool,
+    pub votes_for: u64,
+    pub votes_against: u64,
+    pub slashed: u64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub enum RewardType {
-    RaceCompletion,
-    DailyChallenge,
-    Tournament,
-    WelcomeBonus,
-    Staking,
+#[event]
+pub struct TrackOwnerPaid {
+    pub track_id: u64,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub enum SpendType {
-    TournamentEntry,
-    CarUpgrade,
-    CarCustomization,
-    StakingBoost,
+#[event]
+pub struct WorldRecordSet {
+    pub track_id: u64,
+    pub holder: Pubkey,
+    pub time_ms: u64,
+    pub previous_holder: Pubkey, // Default pubkey for a track's first record
+    pub previous_time_ms: u64,
+    pub bounty_paid: u64,
+    pub replay_hash: [u8; 32],
+    pub timestamp: i64,
 }
 
-// Events
 #[event]
-pub struct TokenReward {
+pub struct NewPersonalBest {
+    pub season: u64,
+    pub track_id: u64,
     pub player: Pubkey,
-    pub amount: u64,
-    pub reward_type: RewardType,
-    pub race_id: u64,
-    pub timestamp: i64,
+    pub score: u64,
+    pub previous_best: u64, // 0 on the first submission
+    pub slot: u64,
+    pub rank: u32, // 1-based; 0 = below a full board
 }
 
 #[event]
-pub struct TokenSpend {
+pub struct ScoreIgnored {
+    pub season: u64,
+    pub track_id: u64,
     pub player: Pubkey,
-    pub amount: u64,
-    pub spend_type: SpendType,
-    pub timestamp: i64,
+    pub score: u64,
+    pub best_score: u64,
 }
 
 // Error Codes
@@ -659,4 +18985,352 @@ pub enum ErrorCode {
     GameStateNotInitialized,
     #[msg("Insufficient vault balance")]
     InsufficientVaultBalance,
+    #[msg("Attestation verifier is not configured")]
+    AttestationNotEnabled,
+    #[msg("A verified attestation is required for this payout")]
+    AttestationRequired,
+    #[msg("Attestation belongs to a different player")]
+    AttestationMismatch,
+    #[msg("Welcome bonus already claimed for this attestation")]
+    WelcomeBonusAlreadyClaimed,
+    #[msg("Invalid probation configuration")]
+    InvalidProbationConfig,
+    #[msg("Oracle is not configured")]
+    OracleNotConfigured,
+    #[msg("Voucher was issued for a different player")]
+    VoucherPlayerMismatch,
+    #[msg("Missing ed25519 signature verification instruction")]
+    MissingSignatureVerification,
+    #[msg("Signature verification does not match the expected signer or message")]
+    InvalidSignatureVerification,
+    #[msg("Voucher has expired")]
+    VoucherExpired,
+    #[msg("Clock tolerance must not be negative")]
+    InvalidClockTolerance,
+    #[msg("Invalid mint migration rate")]
+    InvalidMigrationRate,
+    #[msg("No mint migration is active")]
+    NoMintMigration,
+    #[msg("Invalid sale configuration")]
+    InvalidSaleConfig,
+    #[msg("Token sale is not active")]
+    SaleNotActive,
+    #[msg("Token sale has not ended")]
+    SaleNotEnded,
+    #[msg("Purchase exceeds the per-wallet cap")]
+    SaleWalletCapExceeded,
+    #[msg("Price exceeds the allowed slippage")]
+    SlippageExceeded,
+    #[msg("Invalid or missing price feed")]
+    InvalidPriceFeed,
+    #[msg("Price feed is stale")]
+    StalePrice,
+    #[msg("Price confidence interval is too wide")]
+    PriceConfidenceTooWide,
+    #[msg("AMM program does not match the buyback configuration")]
+    InvalidAmmProgram,
+    #[msg("Invalid grant milestone schedule")]
+    InvalidGrantSchedule,
+    #[msg("All grant milestones have been released")]
+    GrantCompleted,
+    #[msg("Invalid payment stream schedule")]
+    InvalidStreamSchedule,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("Invalid merkle proof")]
+    InvalidMerkleProof,
+    #[msg("Reward destination must be the player's own undelegated token account")]
+    InvalidPlayerTokenAccount,
+    #[msg("Relay team members and shares do not match")]
+    InvalidRelayTeam,
+    #[msg("Invalid match result")]
+    InvalidMatchResult,
+    #[msg("Invalid handicap configuration")]
+    InvalidHandicapConfig,
+    #[msg("Invalid ranked queue configuration")]
+    InvalidRankedQueueConfig,
+    #[msg("Invalid tournament configuration")]
+    InvalidTournamentConfig,
+    #[msg("Tournament registration is closed")]
+    RegistrationClosed,
+    #[msg("Tournament is full")]
+    TournamentFull,
+    #[msg("Player does not meet the tournament eligibility criteria")]
+    NotEligible,
+    #[msg("Tournament has free slots; join directly")]
+    TournamentNotFull,
+    #[msg("Tournament registration is still open")]
+    RegistrationOpen,
+    #[msg("All sponsor slots on this tournament are taken")]
+    SponsorSlotsFull,
+    #[msg("Invalid league configuration")]
+    InvalidLeagueConfig,
+    #[msg("Invalid or already reported fixture")]
+    InvalidFixture,
+    #[msg("League season is already finalized")]
+    LeagueFinalized,
+    #[msg("League season is not finalized")]
+    LeagueNotFinalized,
+    #[msg("Not all fixtures have been reported")]
+    SeasonIncomplete,
+    #[msg("Invalid jackpot configuration")]
+    InvalidJackpotConfig,
+    #[msg("Missing or invalid jackpot vault")]
+    InvalidJackpotVault,
+    #[msg("Draw id does not match the current jackpot draw")]
+    InvalidJackpotDraw,
+    #[msg("Jackpot draw interval has not elapsed")]
+    JackpotDrawTooEarly,
+    #[msg("No entries in the current jackpot draw")]
+    NoJackpotEntries,
+    #[msg("Jackpot already claimed")]
+    JackpotAlreadyClaimed,
+    #[msg("Entry is not the jackpot winner")]
+    NotJackpotWinner,
+    #[msg("VIP role already claimed")]
+    VipRoleAlreadyClaimed,
+    #[msg("Invalid clawback")]
+    InvalidClawback,
+    #[msg("Clawback timelock has not elapsed")]
+    ClawbackTimelocked,
+    #[msg("Imported config does not match the expected hash")]
+    InvalidConfigImport,
+    #[msg("Invalid prediction market or stake")]
+    InvalidPredictionMarket,
+    #[msg("Prediction market is closed for staking")]
+    PredictionMarketClosed,
+    #[msg("Prediction market is already settled")]
+    PredictionMarketSettled,
+    #[msg("Prediction market is not settled yet")]
+    PredictionMarketNotSettled,
+    #[msg("Prediction already claimed")]
+    PredictionAlreadyClaimed,
+    #[msg("Prediction did not back the winning outcome")]
+    PredictionLost,
+    #[msg("Queue ticket has not expired yet")]
+    QueueTicketNotExpired,
+    #[msg("Week has not ended yet")]
+    WeekNotOver,
+    #[msg("Stable side vault is not initialized")]
+    StableVaultNotInitialized,
+    #[msg("Stable side vault is already initialized")]
+    StableVaultAlreadyInitialized,
+    #[msg("Player accounts already exist")]
+    NothingToSponsor,
+    #[msg("Fee pool cannot cover the account rent")]
+    InsufficientFeePool,
+    #[msg("Player would exceed the fee sponsorship cap")]
+    SponsorCapExceeded,
+    #[msg("Test clock is only available in test-clock builds")]
+    TestClockDisabled,
+    #[msg("Account layout does not match this program version")]
+    LayoutMismatch,
+    #[msg("Feature is disabled")]
+    FeatureDisabled,
+    #[msg("Unknown feature flag")]
+    InvalidFeature,
+    #[msg("Metadata name or URI is too long")]
+    MetadataTooLong,
+    #[msg("Invalid race receipt account")]
+    InvalidRaceReceipt,
+    #[msg("Race already has a receipt")]
+    RaceAlreadyReceipted,
+    #[msg("Invalid dispute config")]
+    InvalidDisputeConfig,
+    #[msg("Award is not pending")]
+    AwardNotPending,
+    #[msg("Award is not disputed")]
+    AwardNotDisputed,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+    #[msg("Payouts are paused")]
+    Paused,
+    #[msg("Invalid watchdog config")]
+    InvalidWatchdogConfig,
+    #[msg("Destination is not on the withdrawal allowlist")]
+    DestinationNotAllowed,
+    #[msg("Destination is already on the withdrawal allowlist")]
+    DestinationAlreadyListed,
+    #[msg("Withdrawal allowlist is full")]
+    AllowlistFull,
+    #[msg("Account is not a game vault")]
+    InvalidVault,
+    #[msg("Summary hash does not match the proposal")]
+    ProposalHashMismatch,
+    #[msg("Accounts required by the proposed action are missing")]
+    ProposalAccountsMissing,
+    #[msg("Instruction must be invoked by the partner program")]
+    InvalidCpiCaller,
+    #[msg("Partner grant budget exceeded for this epoch")]
+    PartnerBudgetExceeded,
+    #[msg("Invalid partner grant")]
+    InvalidPartnerGrant,
+    #[msg("Mission has no configured reward")]
+    UnknownMission,
+    #[msg("Invalid Honeycomb config")]
+    InvalidHoneycombConfig,
+    #[msg("Bridge message is malformed or does not match the claim")]
+    InvalidBridgeMessage,
+    #[msg("Bridge emitter is not allowlisted")]
+    UnknownBridgeEmitter,
+    #[msg("Bridge message has already been claimed")]
+    BridgeMessageAlreadyClaimed,
+    #[msg("Invalid bridge config")]
+    InvalidBridgeConfig,
+    #[msg("Onramp provider budget exceeded")]
+    OnrampBudgetExceeded,
+    #[msg("Oracle heartbeat is stale; authority co-signature required")]
+    OracleHeartbeatStale,
+    #[msg("Invalid heartbeat window")]
+    InvalidHeartbeatWindow,
+    #[msg("Invalid vesting config")]
+    InvalidVestingConfig,
+    #[msg("Insufficient credits")]
+    InsufficientCredits,
+    #[msg("Challenge day has not ended")]
+    ChallengeDayNotOver,
+    #[msg("Invalid challenge balance config")]
+    InvalidChallengeBalance,
+    #[msg("No recent passing solvency check")]
+    SolvencyCheckStale,
+    #[msg("Invalid solvency max age")]
+    InvalidSolvencyMaxAge,
+    #[msg("Invalid car drop")]
+    InvalidCarDrop,
+    #[msg("Car drop is closed for pre-orders")]
+    CarDropClosed,
+    #[msg("Car drop is sold out")]
+    CarDropSoldOut,
+    #[msg("Car drop time has not been reached")]
+    CarDropNotReady,
+    #[msg("Car drop has not been cancelled")]
+    CarDropNotCancelled,
+    #[msg("Invalid auction config")]
+    InvalidAuctionConfig,
+    #[msg("Auction is not open")]
+    AuctionNotOpen,
+    #[msg("Auction price is above the buyer's maximum")]
+    AuctionPriceAboveMax,
+    #[msg("Auction is sold out")]
+    AuctionSoldOut,
+    #[msg("Wallet purchase cap reached")]
+    AuctionWalletCapReached,
+    #[msg("Invalid redemption offer")]
+    InvalidRedemptionOffer,
+    #[msg("Redemption deadline has passed")]
+    RedemptionClosed,
+    #[msg("Redemption offer is sold out")]
+    RedemptionSoldOut,
+    #[msg("Redemption already marked as shipped")]
+    RedemptionAlreadyShipped,
+    #[msg("Invalid donation share")]
+    InvalidDonationBps,
+    #[msg("Charity accounts missing or wrong")]
+    InvalidCharityAccount,
+    #[msg("Program upgrade authority does not match the expected authority")]
+    UpgradeAuthorityMismatch,
+    #[msg("Invalid auto-stake configuration")]
+    InvalidAutoStakeConfig,
+    #[msg("Staked winnings are still locked")]
+    StakeLocked,
+    #[msg("Invalid ticket configuration")]
+    InvalidTicketConfig,
+    #[msg("Tournament entry fee exceeds the ticket value")]
+    TicketValueTooLow,
+    #[msg("Invalid promo code")]
+    InvalidPromoCode,
+    #[msg("Promo code has expired")]
+    PromoCodeExpired,
+    #[msg("Promo code has no uses left")]
+    PromoCodeExhausted,
+    #[msg("Promo code already redeemed the maximum times by this wallet")]
+    PromoWalletLimitReached,
+    #[msg("Invalid buff")]
+    InvalidBuff,
+    #[msg("No free buff slot")]
+    BuffSlotsFull,
+    #[msg("Inventory account missing or wrong")]
+    InvalidInventory,
+    #[msg("Inventory is full")]
+    InventoryFull,
+    #[msg("Cosmetic is not for sale")]
+    CosmeticNotForSale,
+    #[msg("Cosmetic not owned")]
+    CosmeticNotOwned,
+    #[msg("Prize hold accounts are required for this prize")]
+    PrizeHoldRequired,
+    #[msg("Prize is still in its hold period")]
+    PrizeHoldActive,
+    #[msg("Prize hold period has ended")]
+    PrizeHoldExpired,
+    #[msg("Prize hold already released or clawed back")]
+    PrizeHoldSettled,
+    #[msg("Invalid prize hold configuration")]
+    InvalidPrizeHoldConfig,
+    #[msg("Payouts above the co-sign threshold require an authority co-signature")]
+    CosignatureRequired,
+    #[msg("Race award is still pending or disputed")]
+    AwardNotSettled,
+    #[msg("Rent treasury account required")]
+    RentTreasuryRequired,
+    #[msg("Invalid MVP vote")]
+    InvalidMvpVote,
+    #[msg("MVP voting has closed for this race")]
+    MvpVotingClosed,
+    #[msg("MVP voting is still open")]
+    MvpVotingOpen,
+    #[msg("MVP bonus already settled")]
+    MvpAlreadySettled,
+    #[msg("Account is not the MVP vote winner")]
+    NotMvpWinner,
+    #[msg("Time does not beat the world record")]
+    NotAWorldRecord,
+    #[msg("Track owner accounts missing or wrong")]
+    InvalidTrackOwner,
+    #[msg("Invalid track submission")]
+    InvalidTrackSubmission,
+    #[msg("Track curation voting has closed")]
+    CurationVotingClosed,
+    #[msg("Track curation voting is still open")]
+    CurationVotingOpen,
+    #[msg("Rate history account required")]
+    RateHistoryRequired,
+    #[msg("Player index is full")]
+    PlayerIndexFull,
+    #[msg("Tournament prize claim deadline has passed")]
+    PrizeClaimExpired,
+    #[msg("Tournament prizes can still be claimed")]
+    PrizeClaimWindowOpen,
+    #[msg("Claim does not match the player's payout currency")]
+    PayoutCurrencyMismatch,
+    #[msg("Year has not ended or has no unreported earnings")]
+    YearlyStatementUnavailable,
+    #[msg("Key rotation must be co-signed by the co-authority")]
+    CoAuthorityRequired,
+    #[msg("Invalid key rotation")]
+    InvalidKeyRotation,
+    #[msg("Voucher was signed for an earlier oracle epoch")]
+    StaleVoucher,
+    #[msg("Halving schedule is already configured")]
+    HalvingAlreadyConfigured,
+    #[msg("Invalid halving schedule")]
+    InvalidHalvingConfig,
+    #[msg("Invalid burn curve")]
+    InvalidBurnCurve,
+    #[msg("Revealed seed does not match the jackpot commitment")]
+    InvalidJackpotSeed,
+    #[msg("Game state uses the original layout; run migrate_game_state")]
+    GameStateMigrationRequired,
+    #[msg("This change must go through an admin proposal")]
+    ProposalRequired,
+    #[msg("Proposal delay has not passed yet")]
+    ProposalTimelocked,
+    #[msg("The game has already migrated its mint")]
+    MintMigrationActive,
+    #[msg("Settle pending awards, vesting, stakes and fee loans before migrating the mint")]
+    MigrationLiabilitiesOutstanding,
+    #[msg("Only closed positions and tournament or jackpot entries can be pruned")]
+    PositionStillOpen,
 }
\ No newline at end of file